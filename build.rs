@@ -119,22 +119,8 @@ pub fn main() {
     build_sdl_image();
     build_sdl_ttf();
 
-    // TODO: cfg features
-    // let features = MetadataCommand::new()
-    //     .no_deps()
-    //     .exec()
-    //     .expect("Failed to get metadata")
-    //     .packages[0]
-    //     .features
-    //     .keys()
-    //     .filter_map(|k| {
-    //         match env::var(format!("CARGO_FEATURE_{}", k.replace("-", "_").to_uppercase()).as_str())
-    //         {
-    //             Ok(_) => Some(k.to_owned()),
-    //             Err(_) => None,
-    //         }
-    //     })
-    //     .collect::<Vec<_>>();
-
-    parser::parse(PathBuf::from("../"))
+    for var in parser::BUILD_ENVS {
+        println!("cargo:rerun-if-env-changed={var}");
+    }
+    parser::parse()
 }
@@ -31,6 +31,49 @@ impl ErrorSpan {
     pub fn byte_range(&self) -> Range<usize> {
         self.byte_start..self.byte_end
     }
+
+    // Bytes are relative to the start of `contents` rather than the global source map
+    // Used for files that failed to parse, which have no span to offset from
+    pub fn from_file_contents(contents: &str, span: &proc_macro2::Span) -> Self {
+        let start = span.start();
+        let end = span.end();
+        Self {
+            byte_start: byte_offset(contents, start),
+            byte_end: byte_offset(contents, end),
+            line_start: start.line,
+            line_end: end.line,
+            column_start: start.column,
+            column_end: end.column,
+        }
+    }
+}
+
+impl ErrorSpan {
+    // Given the span of the first token in a file, returns the byte where the file starts
+    // Leading whitespace and comments have no tokens
+    pub fn file_start(&self, contents: &str) -> usize {
+        let offset = byte_offset(
+            contents,
+            LineColumn {
+                line: self.line_start,
+                column: self.column_start,
+            },
+        );
+        self.byte_start.saturating_sub(offset)
+    }
+}
+
+fn byte_offset(contents: &str, LineColumn { line, column }: LineColumn) -> usize {
+    let mut lines = contents.split_inclusive('\n');
+    let line_start = lines
+        .by_ref()
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum::<usize>();
+    line_start
+        + lines.next().map_or(0, |l| {
+            l.chars().take(column).map(char::len_utf8).sum::<usize>()
+        })
 }
 
 impl Default for ErrorSpan {
@@ -31,6 +31,15 @@ pub fn render(
     term::emit(&mut writer, &config, files, diagnostic).map(|_| writer.to_string())
 }
 
+// How diagnostics are printed by the build script
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageFormat {
+    // Rendered text, one cargo warning per line
+    Human,
+    // One rustc JSON diagnostic per line in a file, whose path is printed as a cargo warning
+    Json,
+}
+
 impl<T> From<DiagnosticLevel> for CodespanDiagnostic<T> {
     fn from(value: DiagnosticLevel) -> Self {
         match value {
@@ -137,11 +146,34 @@ impl Diagnostic {
         self
     }
 
+    // Sets the text to replace the primary spans with
+    pub fn with_replacement(mut self, replacement: Option<String>) -> Self {
+        for span in self.spans.iter_mut().filter(|s| s.is_primary) {
+            span.suggestion_applicability =
+                replacement.as_ref().map(|_| Applicability::MaybeIncorrect);
+            span.suggested_replacement = replacement.clone();
+        }
+        self
+    }
+
+    pub fn with_code(mut self, code: Option<DiagnosticCode>) -> Self {
+        self.code = code;
+        self
+    }
+
+    pub fn with_children(mut self, children: impl IntoIterator<Item = Diagnostic>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
     pub fn to_json(&self) -> serde_json::Result<String> {
         serde_json::to_string(self)
     }
 
-    pub fn emit(&self) -> serde_json::Result<()> {
-        self.to_json().map(|msg| println!("cargo:warning={msg}"))
+    // Prints one cargo warning per rendered line
+    pub fn emit(&self) {
+        for line in self.rendered.as_ref().unwrap_or(&self.message).lines() {
+            println!("cargo:warning={line}");
+        }
     }
 }
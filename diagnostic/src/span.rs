@@ -1,9 +1,9 @@
-use std::{cell::LazyCell, ops::Range};
+use std::{ops::Range, sync::LazyLock};
 
 use proc_macro2::Span;
 
-const SPAN_REGEX: LazyCell<regex::Regex> =
-    LazyCell::new(|| regex::Regex::new(r"bytes\((?P<start>\d+)\.\.(?P<end>\d+)\)").unwrap());
+static SPAN_REGEX: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"bytes\((?P<start>\d+)\.\.(?P<end>\d+)\)").unwrap());
 
 pub trait ToRange {
     fn to_range(&self) -> Result<Range<usize>, String>;
@@ -1,13 +1,11 @@
-use std::{
-    env::{self, temp_dir},
-    fs,
-    path::PathBuf,
-};
+use std::{env, fs, path::PathBuf};
 
 use proc_macro::TokenStream;
-use quote::{format_ident, quote, ToTokens, TokenStreamExt};
+use quote::{format_ident, quote, quote_spanned, ToTokens, TokenStreamExt};
 use shared::{
-    constants::{INDEX, INDEX_SEP, STATE_DATA, STATE_ENTER_EVENT, STATE_EXIT_EVENT, STATE_LABEL},
+    constants::{
+        INDEX_ENV, INDEX_SEP, STATE_DATA, STATE_ENTER_EVENT, STATE_EXIT_EVENT, STATE_LABEL,
+    },
     parsing::{ComponentMacroArgs, GlobalMacroArgs},
     syn::parse_tokens,
 };
@@ -157,6 +155,17 @@ pub fn state(_input: TokenStream, item: TokenStream) -> TokenStream {
     .into()
 }
 
+// Build script the game crate needs so `game_crate!()` can find its generated code
+// Migrating from older versions, which read an index file in the temp directory:
+// add this build.rs next to the game's Cargo.toml, nothing else changes
+const GAME_BUILD_SCRIPT: &str = r#"fn main() {
+    println!("cargo:rerun-if-env-changed=DEP_HYPERFOLD_INDEX");
+    println!(
+        "cargo:rustc-env=HYPERFOLD_INDEX={}",
+        std::env::var("DEP_HYPERFOLD_INDEX").unwrap()
+    );
+}"#;
+
 #[proc_macro]
 pub fn game_crate(input: TokenStream) -> TokenStream {
     let input: Empty = syn::parse(input).unwrap();
@@ -167,10 +176,20 @@ pub fn game_crate(input: TokenStream) -> TokenStream {
     )))
     .expect("Could not canonicalize manifest directory");
 
+    // The engine's build script exports the index to the game's build script through `links`
+    let index = match env::var(INDEX_ENV) {
+        Ok(index) => index,
+        Err(_) => {
+            let msg = format!(
+                "{INDEX_ENV} is not set. The engine's build script passes it to the game's build script as DEP_HYPERFOLD_INDEX, which must forward it. Add this build.rs to the game crate:\n\n{GAME_BUILD_SCRIPT}"
+            );
+            return quote_spanned!(input.span()=> compile_error!(#msg);).into();
+        }
+    };
     let data = try_catch!(
         input,
-        fs::read_to_string(temp_dir().join(INDEX)),
-        "Could not read index file"
+        fs::read_to_string(&index),
+        format!("Could not read index file: {index}")
     );
     let file = try_catch!(
         input,
@@ -0,0 +1,38 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+};
+
+// Everything that affects parsing results or codegen
+const SOURCES: [&str; 4] = ["Cargo.toml", "src", "../shared/src", "../diagnostic/src"];
+
+fn add_files(path: PathBuf, files: &mut Vec<PathBuf>) {
+    match fs::read_dir(&path) {
+        Ok(dir) => dir
+            .filter_map(|e| e.ok())
+            .for_each(|e| add_files(e.path(), files)),
+        Err(_) => files.push(path),
+    }
+}
+
+// The parse cache is discarded when the parser's version changes
+fn main() {
+    let mut files = Vec::new();
+    for src in SOURCES {
+        println!("cargo:rerun-if-changed={src}");
+        add_files(PathBuf::from(src), &mut files);
+    }
+    files.sort();
+
+    let mut hasher = DefaultHasher::new();
+    for file in files {
+        file.hash(&mut hasher);
+        fs::read(&file).unwrap_or_default().hash(&mut hasher);
+    }
+    println!(
+        "cargo:rustc-env=HYPERFOLD_PARSER_VERSION={:016x}",
+        hasher.finish()
+    );
+}
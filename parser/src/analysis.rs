@@ -0,0 +1,142 @@
+use std::path::PathBuf;
+
+use diagnostic::{Diagnostic, DiagnosticLevel, ErrorSpan, ResultsTrait};
+use proc_macro2::TokenStream;
+
+use shared::{
+    syn::error::{Error, MutateResults, Renderer},
+    traits::CollectVec,
+};
+
+use crate::{
+    cache::ParseCache, codegen, codegen::Crates, config::Config, get_renderer, lint::lint,
+    parse::AstCrate, profile::Profiler, resolve::Items, utils::paths::Crate,
+};
+
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    // Used when the main crate's Cargo.toml does not set them
+    // The engine defaults to the path of the `hyperfold_engine` dependency
+    pub engine_dir: Option<PathBuf>,
+    pub macros_dir: Option<PathBuf>,
+    // Warn about unused components, events, and systems
+    pub lint: bool,
+    pub codegen: bool,
+    // Resolve dependencies with `cargo metadata`, which may write Cargo.lock or use the network
+    // Otherwise only path dependencies are found
+    pub cargo_metadata: bool,
+    // Enabled features of the main crate besides its default features
+    pub features: Vec<String>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            engine_dir: None,
+            macros_dir: None,
+            lint: true,
+            codegen: true,
+            cargo_metadata: true,
+            features: Vec::new(),
+        }
+    }
+}
+
+// Everything the build script would produce for a crate
+pub struct Analysis {
+    pub config: Config,
+    pub crates: Crates,
+    pub items: Items,
+    // Generated code by crate index, excludes the macros crate
+    // Empty if codegen is disabled or failed
+    pub code: Vec<(usize, TokenStream)>,
+    // Errors followed by warnings
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Analysis {
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| matches!(d.level, DiagnosticLevel::Error | DiagnosticLevel::Ice))
+    }
+}
+
+// Runs the same steps as `parse()` without reading or writing any files in OUT_DIR
+// or printing anything. Only `cargo metadata` has side effects, see `ParseOptions`
+// Fails if the crates could not be parsed
+pub fn parse_crate(dir: PathBuf, options: &ParseOptions) -> Result<Analysis, Vec<Diagnostic>> {
+    parse_crate_with_cache(dir, options, &ParseCache::disabled())
+}
+
+// Unchanged files are restored from `cache` instead of being parsed
+pub(crate) fn parse_crate_with_cache(
+    dir: PathBuf,
+    options: &ParseOptions,
+    cache: &ParseCache,
+) -> Result<Analysis, Vec<Diagnostic>> {
+    let config = Config::load(dir, options.engine_dir.clone(), options.macros_dir.clone())
+        .map(|config| Config {
+            features: options.features.clone(),
+            ..config
+        })
+        .map_err(|errs| render(&Renderer::new("", ErrorSpan::default()), &errs))?;
+
+    let profiler = Profiler::disabled();
+    let (mut crates, mut errors) = AstCrate::parse(&config, cache, options.cargo_metadata)
+        .map_err(|errs| {
+            render(
+                &Renderer::new(config.get_main_file(), ErrorSpan::default()),
+                &errs,
+            )
+        })?
+        .take();
+    let (items, mut warnings) = Items::resolve(&mut crates, &profiler)
+        .record_errs(&mut errors)
+        .take();
+
+    if options.lint {
+        lint(&items, &crates).record_errs(&mut warnings);
+    }
+
+    let code = match options.codegen {
+        true => codegen::codegen(&mut crates, &items, &profiler)
+            .record_errs(&mut errors)
+            .map(|code| {
+                let macro_cr_idx = crates.get_crate_index(Crate::Macros);
+                crates
+                    .iter_except([macro_cr_idx])
+                    .map(|cr| cr.idx)
+                    .zip(code)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        // Systems are otherwise only validated during codegen
+        false => {
+            for sys in &items.systems {
+                sys.validate(&items)
+                    .with_mod(sys.span.cr_idx, sys.span.m_idx)
+                    .record_errs(&mut errors);
+            }
+            Vec::new()
+        }
+    };
+
+    errors.extend(warnings);
+    let diagnostics = render(
+        &get_renderer(&config.get_main_file(), &crates, &errors),
+        &errors,
+    );
+
+    Ok(Analysis {
+        config,
+        crates,
+        items,
+        code,
+        diagnostics,
+    })
+}
+
+fn render(renderer: &Renderer, errors: &Vec<Error>) -> Vec<Diagnostic> {
+    errors.map_vec(|e| e.render(renderer))
+}
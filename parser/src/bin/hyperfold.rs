@@ -0,0 +1,203 @@
+use std::{
+    env,
+    io::{self, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use diagnostic::Diagnostic;
+use parser::{
+    format_code, list_items, parse_crate, parse_features, write_docs, Analysis, Crate, ParseOptions,
+};
+
+const USAGE: &str = "Usage: hyperfold <COMMAND> [DIR] [OPTIONS]
+
+Runs the Hyperfold parser on the game crate in DIR (default: current directory)
+
+Commands:
+  check    Resolve and validate the crate, printing diagnostics
+  expand   Print the generated code for a crate
+  list     List components, events, systems, and states
+  docs     Write Markdown reference pages for every ECS item
+
+Options:
+  --engine <DIR>          Engine crate, if not set in Cargo.toml
+  --macros <DIR>          Macros crate, if not set in Cargo.toml
+  --crate <NAME>          Crate to expand (default: the game crate)
+  --features <FEATURES>   Features of the game crate to enable besides its default features
+  --message-format <FMT>  Diagnostic format: human or json (default: human)
+  --out <DIR>             Docs directory (default: DIR/target/hyperfold-docs)
+  --all                   Also list or document items from the engine
+  --no-cargo              Read Cargo.toml files instead of running cargo metadata
+  -h, --help              Print this message";
+
+enum Command {
+    Check,
+    Expand,
+    List,
+    Docs,
+}
+
+struct Args {
+    command: Command,
+    dir: PathBuf,
+    options: ParseOptions,
+    cr_name: Option<String>,
+    out: Option<PathBuf>,
+    json: bool,
+    all: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let command = match args.next().as_deref() {
+        Some("check") => Command::Check,
+        Some("expand") => Command::Expand,
+        Some("list") => Command::List,
+        Some("docs") => Command::Docs,
+        Some(cmd) => return Err(format!("Unknown command: {cmd}")),
+        None => return Err("Missing command".to_string()),
+    };
+
+    let mut dir = None;
+    let mut options = ParseOptions {
+        codegen: matches!(command, Command::Expand),
+        lint: matches!(command, Command::Check | Command::Expand),
+        ..ParseOptions::default()
+    };
+    let mut cr_name = None;
+    let mut out = None;
+    let mut json = false;
+    let mut all = false;
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value for {arg}"));
+        match arg.as_str() {
+            "--engine" => options.engine_dir = Some(PathBuf::from(value()?)),
+            "--macros" => options.macros_dir = Some(PathBuf::from(value()?)),
+            "--crate" => cr_name = Some(value()?),
+            "--features" => options.features.extend(parse_features(&value()?)),
+            "--out" => out = Some(PathBuf::from(value()?)),
+            "--message-format" => {
+                json = match value()?.as_str() {
+                    "human" => false,
+                    "json" => true,
+                    fmt => return Err(format!("Unknown message format: {fmt}")),
+                }
+            }
+            "--all" => all = true,
+            "--no-cargo" => options.cargo_metadata = false,
+            _ if arg.starts_with('-') => return Err(format!("Unknown option: {arg}")),
+            _ if dir.is_none() => dir = Some(PathBuf::from(arg)),
+            _ => return Err(format!("Unexpected argument: {arg}")),
+        }
+    }
+
+    Ok(Args {
+        command,
+        dir: dir.unwrap_or_else(|| PathBuf::from(".")),
+        options,
+        cr_name,
+        out,
+        json,
+        all,
+    })
+}
+
+// Diagnostics go to stderr so `expand` and `list` output can be piped
+fn print_diagnostics(diagnostics: &Vec<Diagnostic>, json: bool) {
+    for d in diagnostics {
+        match json {
+            true => eprintln!("{}", d.to_json().unwrap_or_default()),
+            false => eprintln!("{}", d.rendered.as_ref().unwrap_or(&d.message)),
+        }
+    }
+}
+
+fn expand(analysis: &Analysis, cr_name: Option<&str>) -> Result<String, String> {
+    let crates = analysis.crates.get_crates();
+    let main_cr_idx = analysis.crates.get_crate_index(Crate::Main);
+    let cr_idx = match cr_name {
+        // Directory name or the name used by the game crate
+        Some(name) => crates
+            .iter()
+            .find(|cr| {
+                cr.name == name
+                    || analysis.crates.get_crate_path(main_cr_idx, cr.idx)
+                        == Some(vec![name.to_string()])
+            })
+            .map(|cr| cr.idx)
+            .ok_or(format!("No crate named: {name}"))?,
+        None => main_cr_idx,
+    };
+    analysis
+        .code
+        .iter()
+        .find(|(i, _)| *i == cr_idx)
+        .map(|(_, code)| format_code(code))
+        .ok_or(format!(
+            "No code was generated for: {}",
+            crates[cr_idx].name
+        ))
+}
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+    let args = match parse_args(args.into_iter()) {
+        Ok(args) => args,
+        Err(msg) => {
+            eprintln!("{msg}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let analysis = match parse_crate(args.dir.clone(), &args.options) {
+        Ok(analysis) => analysis,
+        Err(diagnostics) => {
+            print_diagnostics(&diagnostics, args.json);
+            return ExitCode::FAILURE;
+        }
+    };
+    print_diagnostics(&analysis.diagnostics, args.json);
+
+    let output = match args.command {
+        Command::Check => Ok(None),
+        Command::Expand => expand(&analysis, args.cr_name.as_deref()).map(Some),
+        Command::List => Ok(Some(list_items(
+            &analysis.items,
+            &analysis.crates,
+            args.all,
+        ))),
+        Command::Docs => {
+            let out = args
+                .out
+                .unwrap_or_else(|| args.dir.join("target").join("hyperfold-docs"));
+            write_docs(&analysis.items, &analysis.crates, &out, args.all)
+                .map(|pages| Some(format!("Wrote {pages} pages to {}", out.display())))
+                .map_err(|errs| {
+                    errs.iter()
+                        .map(|e| e.message())
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+        }
+    };
+    match output {
+        Ok(output) => {
+            // Ignore errors from closed pipes, e.g. `hyperfold expand | head`
+            if let Some(output) = output {
+                writeln!(io::stdout(), "{output}").ok();
+            }
+            match analysis.has_errors() {
+                true => ExitCode::FAILURE,
+                false => ExitCode::SUCCESS,
+            }
+        }
+        Err(msg) => {
+            eprintln!("{msg}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use std::path::PathBuf;
+
+use diagnostic::ok;
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use serde::{Deserialize, Serialize};
+
+use crate::parse::{
+    AstAttribute, AstEngineCall, AstEnum, AstFunction, AstItemData, AstItems, AstMacroCall, AstMod,
+    AstModType, AstStruct, AstTypeAlias, AstUse, EngineCallKind, ModDecl, ModSource, Visibility,
+};
+
+use super::tokens::{CachedSpan, CachedToken, KeptTokens, Tokens};
+
+// Converts parsed items to a form that can be saved
+// Spans and tokens are restored from the lexed file when loading
+pub trait Cache: Sized {
+    type Cached;
+
+    // None if the item can't be restored from its file
+    // Tokens needed to restore the item are added to `kept`
+    fn to_cache(&self, kept: &mut KeptTokens) -> Option<Self::Cached>;
+
+    fn from_cache(cached: &Self::Cached, tokens: &Tokens) -> Option<Self>;
+}
+
+// Types without spans are saved as is
+macro_rules! cache_as_is {
+    ($($ty: ty),*) => {
+        $(impl Cache for $ty {
+            type Cached = Self;
+
+            fn to_cache(&self, _: &mut KeptTokens) -> Option<Self> {
+                Some(self.clone())
+            }
+
+            fn from_cache(cached: &Self, _: &Tokens) -> Option<Self> {
+                Some(cached.clone())
+            }
+        })*
+    };
+}
+
+cache_as_is!(String, Visibility, EngineCallKind, ModSource);
+
+// Defines the saved form of a struct, every field must implement `Cache`
+macro_rules! cache_struct {
+    ($ty: ident => $cached: ident { $($field: ident: $field_ty: ty),* $(,)? }) => {
+        #[derive(Serialize, Deserialize)]
+        pub struct $cached {
+            $($field: <$field_ty as Cache>::Cached),*
+        }
+
+        impl Cache for $ty {
+            type Cached = $cached;
+
+            fn to_cache(&self, kept: &mut KeptTokens) -> Option<$cached> {
+                Some($cached {
+                    $($field: self.$field.to_cache(kept)?),*
+                })
+            }
+
+            fn from_cache(cached: &$cached, tokens: &Tokens) -> Option<Self> {
+                Some(Self {
+                    $($field: Cache::from_cache(&cached.$field, tokens)?),*
+                })
+            }
+        }
+    };
+}
+
+cache_struct!(AstItemData => CachedItemData {
+    path: Vec<String>,
+    ident: String,
+    span: Span,
+    generics: Vec<String>,
+    docs: String,
+});
+cache_struct!(AstAttribute => CachedAttribute {
+    path: Vec<String>,
+    args: TokenStream,
+    span: Span,
+});
+cache_struct!(AstStruct => CachedStruct {
+    attrs: Vec<AstAttribute>,
+    data: AstItemData,
+});
+cache_struct!(AstEnum => CachedEnum {
+    attrs: Vec<AstAttribute>,
+    data: AstItemData,
+});
+cache_struct!(AstFunction => CachedFunction {
+    sig: syn::Signature,
+    attrs: Vec<AstAttribute>,
+    data: AstItemData,
+});
+cache_struct!(AstMacroCall => CachedMacroCall {
+    args: TokenStream,
+    data: AstItemData,
+});
+cache_struct!(AstTypeAlias => CachedTypeAlias {
+    ty: syn::Type,
+    data: AstItemData,
+    vis: Visibility,
+});
+cache_struct!(AstUse => CachedUse {
+    ident: String,
+    path: Vec<String>,
+    vis: Visibility,
+    span: Span,
+});
+cache_struct!(AstEngineCall => CachedEngineCall {
+    kind: EngineCallKind,
+    path: Vec<String>,
+    generics: Vec<syn::Type>,
+    span: Span,
+});
+cache_struct!(AstItems => CachedItems {
+    structs: Vec<AstStruct>,
+    enums: Vec<AstEnum>,
+    functions: Vec<AstFunction>,
+    macro_calls: Vec<AstMacroCall>,
+    type_aliases: Vec<AstTypeAlias>,
+    engine_calls: Vec<AstEngineCall>,
+});
+
+impl<T: Cache> Cache for Vec<T> {
+    type Cached = Vec<T::Cached>;
+
+    fn to_cache(&self, kept: &mut KeptTokens) -> Option<Self::Cached> {
+        self.iter().map(|t| t.to_cache(kept)).collect()
+    }
+
+    fn from_cache(cached: &Self::Cached, tokens: &Tokens) -> Option<Self> {
+        cached.iter().map(|t| T::from_cache(t, tokens)).collect()
+    }
+}
+
+impl Cache for Span {
+    type Cached = CachedSpan;
+
+    fn to_cache(&self, kept: &mut KeptTokens) -> Option<CachedSpan> {
+        Some(kept.span(self))
+    }
+
+    fn from_cache(cached: &CachedSpan, tokens: &Tokens) -> Option<Self> {
+        tokens.span(cached)
+    }
+}
+
+impl Cache for TokenStream {
+    type Cached = Vec<CachedToken>;
+
+    fn to_cache(&self, kept: &mut KeptTokens) -> Option<Self::Cached> {
+        kept.tokens(self.clone())
+    }
+
+    fn from_cache(cached: &Self::Cached, tokens: &Tokens) -> Option<Self> {
+        tokens.tokens(cached)
+    }
+}
+
+// Syntax trees are saved as their tokens and parsed again
+macro_rules! cache_syn {
+    ($($ty: ty),*) => {
+        $(impl Cache for $ty {
+            type Cached = Vec<CachedToken>;
+
+            fn to_cache(&self, kept: &mut KeptTokens) -> Option<Self::Cached> {
+                kept.tokens(self.to_token_stream())
+            }
+
+            fn from_cache(cached: &Self::Cached, tokens: &Tokens) -> Option<Self> {
+                syn::parse2(tokens.tokens(cached)?).ok()
+            }
+        })*
+    };
+}
+
+cache_syn!(syn::Signature, syn::Type);
+
+// Mods only have uses and items after parsing
+#[derive(Serialize, Deserialize)]
+pub struct CachedMod {
+    path: Vec<String>,
+    span: CachedSpan,
+    vis: Visibility,
+    uses: Vec<CachedUse>,
+    items: CachedItems,
+}
+
+impl CachedMod {
+    pub fn new(m: &AstMod, kept: &mut KeptTokens) -> Option<Self> {
+        Some(Self {
+            path: m.path.to_vec(),
+            span: m.span.to_cache(kept)?,
+            vis: m.vis.clone(),
+            uses: m.uses.to_cache(kept)?,
+            items: m.items.to_cache(kept)?,
+        })
+    }
+
+    pub fn restore(&self, file: &PathBuf, ty: AstModType, tokens: &Tokens) -> Option<AstMod> {
+        let mut m = AstMod::new(
+            0,
+            file.to_owned(),
+            self.path.to_vec(),
+            self.path.last()?.to_string(),
+            ty,
+            Span::from_cache(&self.span, tokens)?,
+        );
+        m.vis = self.vis.clone();
+        m.uses = Cache::from_cache(&self.uses, tokens)?;
+        m.items = Cache::from_cache(&self.items, tokens)?;
+        Some(m)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum CachedModDecl {
+    Inline {
+        m: CachedMod,
+        mods: Vec<CachedModDecl>,
+    },
+    File {
+        path: Vec<String>,
+        source: ModSource,
+        vis: Visibility,
+        span: CachedSpan,
+    },
+}
+
+impl CachedModDecl {
+    // Inline mods with errors aren't cached so their errors are reported
+    pub fn new(m: &ModDecl, kept: &mut KeptTokens) -> Option<Self> {
+        Some(match m {
+            ModDecl::Inline { m, mods } => Self::Inline {
+                m: m.errors
+                    .is_empty()
+                    .then(|| CachedMod::new(&m.value, kept))??,
+                mods: mods
+                    .iter()
+                    .map(|m| Self::new(m, kept))
+                    .collect::<Option<_>>()?,
+            },
+            ModDecl::File {
+                path,
+                source,
+                vis,
+                span,
+            } => Self::File {
+                path: path.to_vec(),
+                source: source.clone(),
+                vis: vis.clone(),
+                span: span.to_cache(kept)?,
+            },
+        })
+    }
+
+    pub fn restore(&self, file: &PathBuf, tokens: &Tokens) -> Option<ModDecl> {
+        Some(match self {
+            Self::Inline { m, mods } => ModDecl::Inline {
+                m: Box::new(ok(m.restore(file, AstModType::Internal, tokens)?)),
+                mods: mods
+                    .iter()
+                    .map(|m| m.restore(file, tokens))
+                    .collect::<Option<_>>()?,
+            },
+            Self::File {
+                path,
+                source,
+                vis,
+                span,
+            } => ModDecl::File {
+                path: path.to_vec(),
+                source: source.clone(),
+                vis: vis.clone(),
+                span: Span::from_cache(span, tokens)?,
+            },
+        })
+    }
+}
@@ -0,0 +1,315 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    mem,
+};
+
+use diagnostic::ErrorSpan;
+use proc_macro2::Span;
+use shared::{
+    parsing::{ComponentMacroArgs, EventMacroArgs, GlobalMacroArgs, SystemMacroArgs},
+    syn::ToRange,
+};
+
+use crate::{
+    codegen::Crates,
+    component_set::{
+        ComponentSet, ComponentSetItem, ComponentSetLabels, LabelItem, LabelSymbol,
+        LabelsExpression,
+    },
+    parse::{
+        AliasSymbol, AstModType, BuiltinSymbol, ComponentSymbol, GenericArg, GlobalSymbol,
+        HardcodedSymbol, ItemPath, ItemSpan, Symbol, SymbolType, Visibility,
+    },
+    resolve::{
+        GenericKind, ItemComponent, ItemData, ItemEvent, ItemGeneric, ItemGlobal, ItemIds,
+        ItemInstance, ItemState, Items,
+    },
+    system::{FnArg, FnArgType, ItemSystem},
+};
+
+// Hashes everything codegen depends on besides the parser, whose changes reset the cache
+pub struct ItemHasher {
+    state: DefaultHasher,
+    files: FileStarts,
+}
+
+impl ItemHasher {
+    pub fn new(crates: &Crates) -> Self {
+        Self {
+            state: DefaultHasher::new(),
+            files: FileStarts::new(crates),
+        }
+    }
+
+    pub fn add(&mut self, t: &impl HashItem) {
+        t.hash_item(self)
+    }
+
+    // For data without spans
+    pub fn add_hash(&mut self, t: impl Hash) {
+        t.hash(&mut self.state)
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.state.finish()
+    }
+}
+
+pub trait HashItem {
+    fn hash_item(&self, h: &mut ItemHasher);
+}
+
+// Types without spans use their `Hash` implementation
+macro_rules! hash_as_is {
+    ($($ty: ty),*) => {
+        $(impl HashItem for $ty {
+            fn hash_item(&self, h: &mut ItemHasher) {
+                self.hash(&mut h.state)
+            }
+        })*
+    };
+}
+
+hash_as_is!(
+    bool,
+    usize,
+    u32,
+    String,
+    Visibility,
+    ItemPath,
+    GenericArg,
+    GenericKind,
+    FnArgType,
+    AliasSymbol,
+    BuiltinSymbol,
+    HardcodedSymbol,
+    ComponentMacroArgs,
+    GlobalMacroArgs,
+    EventMacroArgs
+);
+
+// Hashes every field of a struct
+macro_rules! hash_struct {
+    ($($ty: ident { $($field: ident),* $(,)? }),* $(,)?) => {
+        $(impl HashItem for $ty {
+            fn hash_item(&self, h: &mut ItemHasher) {
+                $(self.$field.hash_item(h);)*
+            }
+        })*
+    };
+}
+
+hash_struct!(
+    Items {
+        components,
+        globals,
+        events,
+        states,
+        component_sets,
+        systems,
+        generics,
+        instances,
+        ids,
+    },
+    ItemIds {
+        components,
+        globals,
+        events,
+        states,
+        component_sets,
+    },
+    ItemData {
+        path,
+        mod_idx,
+        span,
+        docs
+    },
+    ItemComponent { data, args },
+    ItemGlobal { data, args },
+    ItemEvent { data, args, state },
+    ItemState {
+        data,
+        data_path,
+        enter_event,
+        exit_event,
+        label,
+    },
+    ItemGeneric { data, kind, params },
+    ItemInstance {
+        generic,
+        args,
+        symbol,
+    },
+    ItemSystem {
+        path,
+        args,
+        attr_args,
+        span,
+        docs,
+    },
+    FnArg {
+        ty,
+        is_mut,
+        ref_cnt,
+        span,
+    },
+    ItemSpan {
+        span,
+        m_idx,
+        cr_idx
+    },
+    ComponentSet {
+        path,
+        args,
+        labels,
+        span,
+    },
+    ComponentSetItem {
+        var,
+        ty,
+        sym,
+        ref_cnt,
+        is_mut,
+        is_opt,
+    },
+    LabelSymbol { comp, span },
+    LabelsExpression {
+        labels,
+        true_symbols,
+        false_symbols,
+        unknown_symbols,
+    },
+    Symbol { kind, path, vis },
+    ComponentSymbol { idx, args, span },
+    GlobalSymbol { idx, args, span },
+);
+
+impl HashItem for SymbolType {
+    fn hash_item(&self, h: &mut ItemHasher) {
+        mem::discriminant(self).hash(&mut h.state);
+        match self {
+            SymbolType::Component(c) => c.hash_item(h),
+            SymbolType::Global(g) | SymbolType::Trait(g) => g.hash_item(h),
+            SymbolType::Event(i)
+            | SymbolType::State(i)
+            | SymbolType::ComponentSet(i)
+            | SymbolType::Generic(i) => i.hash_item(h),
+            SymbolType::System(i, span) => (i, span).hash_item(h),
+            SymbolType::Hardcoded(sym) => sym.hash_item(h),
+            SymbolType::Builtin(sym) => sym.hash_item(h),
+            SymbolType::TypeAlias(sym) => sym.hash_item(h),
+        }
+    }
+}
+
+impl HashItem for LabelItem {
+    fn hash_item(&self, h: &mut ItemHasher) {
+        mem::discriminant(self).hash(&mut h.state);
+        match self {
+            LabelItem::Item { not, sym } => (not, sym).hash_item(h),
+            LabelItem::Expression { op, items, span } => {
+                op.hash(&mut h.state);
+                (items, span).hash_item(h)
+            }
+        }
+    }
+}
+
+impl HashItem for ComponentSetLabels {
+    fn hash_item(&self, h: &mut ItemHasher) {
+        mem::discriminant(self).hash(&mut h.state);
+        match self {
+            ComponentSetLabels::Constant(b) => b.hash_item(h),
+            ComponentSetLabels::Expression(e) => e.hash_item(h),
+        }
+    }
+}
+
+impl HashItem for SystemMacroArgs {
+    fn hash_item(&self, h: &mut ItemHasher) {
+        mem::discriminant(self).hash(&mut h.state);
+        match self {
+            SystemMacroArgs::Init() => (),
+            SystemMacroArgs::System {
+                states,
+                allow_unreachable,
+            } => (states, allow_unreachable).hash_item(h),
+        }
+    }
+}
+
+impl<T: HashItem> HashItem for &T {
+    fn hash_item(&self, h: &mut ItemHasher) {
+        (*self).hash_item(h)
+    }
+}
+
+impl<T: HashItem> HashItem for Vec<T> {
+    fn hash_item(&self, h: &mut ItemHasher) {
+        self.len().hash(&mut h.state);
+        self.iter().for_each(|t| t.hash_item(h));
+    }
+}
+
+impl<T: HashItem> HashItem for Option<T> {
+    fn hash_item(&self, h: &mut ItemHasher) {
+        self.is_some().hash(&mut h.state);
+        if let Some(t) = self {
+            t.hash_item(h);
+        }
+    }
+}
+
+impl<A: HashItem, B: HashItem> HashItem for (A, B) {
+    fn hash_item(&self, h: &mut ItemHasher) {
+        self.0.hash_item(h);
+        self.1.hash_item(h);
+    }
+}
+
+// Spans have offsets into every source lexed so far
+// Parsing lexes more than the cache does, so spans are hashed as positions in their files
+impl HashItem for Span {
+    fn hash_item(&self, h: &mut ItemHasher) {
+        let range = self.to_range().ok();
+        range
+            .map(|r| (h.files.position(r.start), h.files.position(r.end)))
+            .hash(&mut h.state)
+    }
+}
+
+// Contents of each file by its offset in the lexed sources
+struct FileStarts(Vec<(usize, String)>);
+
+impl FileStarts {
+    fn new(crates: &Crates) -> Self {
+        let mut files = crates
+            .iter()
+            .flat_map(|cr| cr.mods.iter())
+            .filter(|m| !matches!(m.ty, AstModType::Internal))
+            .filter_map(|m| {
+                let contents = fs::read_to_string(&m.file).ok()?;
+                Some((ErrorSpan::from(&m.span).file_start(&contents), contents))
+            })
+            .collect::<Vec<_>>();
+        files.sort_by_key(|(start, _)| *start);
+        Self(files)
+    }
+
+    // File, line, and column of a byte offset
+    fn position(&self, byte: usize) -> Option<(usize, usize, usize)> {
+        let i = self
+            .0
+            .partition_point(|(start, _)| *start <= byte)
+            .checked_sub(1)?;
+        let (start, contents) = &self.0[i];
+        let text = contents.get(..byte - start)?;
+        Some((
+            i,
+            text.matches('\n').count(),
+            text.rsplit('\n').next().map_or(0, |l| l.chars().count()),
+        ))
+    }
+}
@@ -0,0 +1,278 @@
+mod ast;
+mod hash;
+mod tokens;
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::{hash_map::DefaultHasher, HashMap},
+    env, fs,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use proc_macro2::TokenStream;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    codegen::Crates,
+    parse::{AstMod, AstModType, CfgEnv, ModDecl},
+    resolve::Items,
+};
+
+use ast::{CachedMod, CachedModDecl};
+use hash::ItemHasher;
+use tokens::{skeleton, skip_prefix, KeptTokens, Tokens};
+
+const CACHE_FILE: &str = "hyperfold_parse_cache.json";
+// Hash of the parser's sources, set by build.rs
+const CACHE_VERSION: &str = env!("HYPERFOLD_PARSER_VERSION");
+
+// Everything parsing a file depends on
+pub struct ModKey<'a> {
+    pub file: &'a PathBuf,
+    pub contents: &'a str,
+    pub path: &'a Vec<String>,
+    pub ty: AstModType,
+    pub cfg: &'a CfgEnv,
+}
+
+impl<'a> ModKey<'a> {
+    fn hash(&self) -> u64 {
+        hash((self.contents, self.path, &self.ty, self.cfg))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct FileCache {
+    hash: u64,
+    // Byte ranges of the tokens needed to restore the items
+    keep: Vec<(usize, usize)>,
+    m: CachedMod,
+    mods: Vec<CachedModDecl>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct CacheData {
+    version: String,
+    files: HashMap<String, FileCache>,
+    codegen: Option<u64>,
+}
+
+// Persists parsing results in OUT_DIR across builds
+// Unchanged files aren't parsed, instead their cached items are restored
+// Spans and tokens come from lexing a skeleton of the file which only has the tokens items use
+pub struct ParseCache {
+    file: Option<PathBuf>,
+    old: RefCell<CacheData>,
+    new: RefCell<CacheData>,
+    restored: Cell<usize>,
+}
+
+impl ParseCache {
+    pub fn load() -> Self {
+        Self::from_file(
+            env::var("OUT_DIR")
+                .ok()
+                .map(|dir| PathBuf::from(dir).join(CACHE_FILE)),
+        )
+    }
+
+    // Nothing is reused or saved
+    pub fn disabled() -> Self {
+        Self::from_file(None)
+    }
+
+    fn from_file(file: Option<PathBuf>) -> Self {
+        let old = file
+            .as_ref()
+            .and_then(|f| fs::read_to_string(f).ok())
+            .and_then(|data| serde_json::from_str::<CacheData>(&data).ok())
+            .filter(|data| data.version == CACHE_VERSION)
+            .unwrap_or_default();
+        Self {
+            file,
+            old: RefCell::new(old),
+            new: RefCell::new(CacheData {
+                version: CACHE_VERSION.to_string(),
+                ..Default::default()
+            }),
+            restored: Cell::new(0),
+        }
+    }
+
+    pub fn save(&self) {
+        if let Some(file) = &self.file {
+            if let Ok(data) = serde_json::to_string(&*self.new.borrow()) {
+                fs::write(file, data).ok();
+            }
+        }
+    }
+
+    // Returns the parsed mod and its mod declarations if the file hasn't changed
+    pub fn get_mod(&self, key: &ModKey) -> Option<(AstMod, Vec<ModDecl>)> {
+        let file = key.file.display().to_string();
+        let cache = self
+            .old
+            .borrow_mut()
+            .files
+            .remove(&file)
+            .filter(|c| c.hash == key.hash())?;
+        let tokens = Tokens::lex(&skeleton(skip_prefix(key.contents), &cache.keep)?)?;
+        let m = cache.m.restore(key.file, key.ty, &tokens)?;
+        let mods = cache
+            .mods
+            .iter()
+            .map(|m| m.restore(key.file, &tokens))
+            .collect::<Option<_>>()?;
+        self.new.borrow_mut().files.insert(file, cache);
+        self.restored.set(self.restored.get() + 1);
+        Some((m, mods))
+    }
+
+    // Number of files restored instead of parsed
+    pub fn num_restored(&self) -> usize {
+        self.restored.get()
+    }
+
+    // Files whose items can't be restored aren't cached
+    // `tokens` must come from `parse_file()` so spans aren't moved by lexing the file again
+    pub fn insert_mod(&self, key: &ModKey, tokens: TokenStream, m: &AstMod, mods: &[ModDecl]) {
+        let mut kept = KeptTokens::default();
+        if let Some(cache) = CachedMod::new(m, &mut kept).and_then(|m| {
+            let mods = mods
+                .iter()
+                .map(|m| CachedModDecl::new(m, &mut kept))
+                .collect::<Option<_>>()?;
+            Some(FileCache {
+                hash: key.hash(),
+                keep: kept.ranges(skip_prefix(key.contents), tokens)?,
+                m,
+                mods,
+            })
+        }) {
+            self.new
+                .borrow_mut()
+                .files
+                .insert(key.file.display().to_string(), cache);
+        }
+    }
+
+    pub fn is_codegen_current(&self, hash: u64) -> bool {
+        self.old.borrow().codegen == Some(hash)
+    }
+
+    pub fn set_codegen(&self, hash: u64) {
+        self.new.borrow_mut().codegen = Some(hash);
+    }
+}
+
+// Same as `syn::parse_file()` but also returns the file's tokens for the cache
+pub fn parse_file(contents: &str) -> syn::Result<(syn::File, TokenStream)> {
+    let tokens = TokenStream::from_str(skip_prefix(contents))?;
+    Ok((syn::parse2(tokens.clone())?, tokens))
+}
+
+fn hash(t: impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Hashes everything codegen depends on besides the parser, whose changes reset the cache
+// Manifests are included for the crates' names and versions
+pub fn hash_items(items: &Items, crates: &Crates) -> u64 {
+    let mut h = ItemHasher::new(crates);
+    h.add(items);
+    for cr in crates.iter() {
+        let mut deps = cr.deps.iter().collect::<Vec<_>>();
+        deps.sort();
+        h.add_hash((
+            &cr.dir,
+            &cr.name,
+            deps,
+            fs::read_to_string(cr.dir.join("Cargo.toml")).unwrap_or_default(),
+        ));
+    }
+    h.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use crate::{
+        analysis::parse_crate_with_cache, format_code, test::copy_fixture, Analysis, ParseOptions,
+    };
+
+    use super::{hash_items, ParseCache, CACHE_FILE};
+
+    // Returns the codegen, diagnostics, codegen hash, and number of files restored from the cache
+    fn parse(dir: &PathBuf, cache: &ParseCache) -> (Vec<String>, Vec<String>, u64, usize) {
+        let options = ParseOptions {
+            cargo_metadata: false,
+            ..ParseOptions::default()
+        };
+        let Analysis {
+            crates,
+            items,
+            code,
+            diagnostics,
+            ..
+        } = parse_crate_with_cache(dir.to_owned(), &options, cache)
+            .unwrap_or_else(|_| panic!("Failed to parse {}", dir.display()));
+        cache.save();
+        (
+            code.iter().map(|(_, code)| format_code(code)).collect(),
+            diagnostics
+                .into_iter()
+                .map(|d| d.rendered.unwrap_or(d.message))
+                .collect(),
+            hash_items(&items, &crates),
+            cache.num_restored(),
+        )
+    }
+
+    fn check_cache(name: &str, file: &str) {
+        let dir = copy_fixture(name, "cache");
+        let cache_file = dir.join(CACHE_FILE);
+        let load = || ParseCache::from_file(Some(cache_file.to_owned()));
+
+        let (code, diagnostics, hash, restored) = parse(&dir, &load());
+        assert_eq!(restored, 0);
+        assert!(cache_file.is_file());
+
+        // Every file is restored and the results match
+        let cached = parse(&dir, &load());
+        assert!(cached.3 > 0, "No files were restored from the cache");
+        assert_eq!(cached, (code, diagnostics, hash, cached.3));
+
+        // Moving every span in the edited file moves its items' spans in the diagnostics and codegen
+        let file = dir.join(file);
+        let contents = fs::read_to_string(&file).unwrap();
+        fs::write(
+            &file,
+            format!("// Moves every item down a line\n{contents}"),
+        )
+        .unwrap();
+        let edited = parse(&dir, &load());
+        let uncached = parse(&dir, &ParseCache::disabled());
+        assert_eq!(edited.3, cached.3 - 1);
+        assert_eq!(edited.0, uncached.0);
+        assert_eq!(edited.1, uncached.1);
+        assert_eq!(edited.2, uncached.2);
+        assert_ne!(edited.2, hash);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn restores_mods() {
+        check_cache("basic", "src/platform/linux.rs");
+    }
+
+    #[test]
+    fn restores_diagnostics() {
+        check_cache("lints", "src/main.rs");
+    }
+}
@@ -0,0 +1,300 @@
+use std::{collections::HashMap, str::FromStr};
+
+use proc_macro2::{
+    Delimiter, Group, Ident, LineColumn, Literal, Punct, Spacing, Span, TokenStream, TokenTree,
+};
+use serde::{Deserialize, Serialize};
+
+// Line and column of a token boundary
+type Pos = (usize, usize);
+
+fn pos(lc: LineColumn) -> Pos {
+    (lc.line, lc.column)
+}
+
+// Skips the byte order mark and shebang like `syn::parse_file()` so positions are the same
+pub fn skip_prefix(contents: &str) -> &str {
+    let contents = contents.strip_prefix('\u{feff}').unwrap_or(contents);
+    match contents.strip_prefix("#!") {
+        Some(rest) if !rest.trim_start().starts_with('[') => {
+            contents.find('\n').map_or("", |i| &contents[i..])
+        }
+        _ => contents,
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CachedSpan(Pos, Pos);
+
+// Token tree with the position of each token so it can be found in the file
+#[derive(Serialize, Deserialize)]
+pub enum CachedToken {
+    Ident(Pos),
+    Punct(char, bool, Pos),
+    Literal(Pos),
+    Group(char, CachedSpan, Vec<CachedToken>),
+}
+
+// Positions of the tokens that cached items refer to
+#[derive(Default)]
+pub struct KeptTokens {
+    starts: Vec<Pos>,
+    ends: Vec<Pos>,
+}
+
+impl KeptTokens {
+    pub fn span(&mut self, span: &Span) -> CachedSpan {
+        self.starts.push(pos(span.start()));
+        self.ends.push(pos(span.end()));
+        CachedSpan(pos(span.start()), pos(span.end()))
+    }
+
+    fn start(&mut self, span: Span) -> Pos {
+        self.starts.push(pos(span.start()));
+        pos(span.start())
+    }
+
+    // None if the tokens didn't come from the file, e.g. invisible groups
+    pub fn tokens(&mut self, tokens: TokenStream) -> Option<Vec<CachedToken>> {
+        tokens
+            .into_iter()
+            .map(|tt| {
+                Some(match tt {
+                    TokenTree::Ident(i) => CachedToken::Ident(self.start(i.span())),
+                    TokenTree::Punct(p) => CachedToken::Punct(
+                        p.as_char(),
+                        p.spacing() == Spacing::Joint,
+                        self.start(p.span()),
+                    ),
+                    TokenTree::Literal(l) => CachedToken::Literal(self.start(l.span())),
+                    TokenTree::Group(g) => CachedToken::Group(
+                        match g.delimiter() {
+                            Delimiter::Parenthesis => '(',
+                            Delimiter::Brace => '{',
+                            Delimiter::Bracket => '[',
+                            Delimiter::None => return None,
+                        },
+                        self.span(&g.span()),
+                        self.tokens(g.stream())?,
+                    ),
+                })
+            })
+            .collect()
+    }
+
+    // Byte ranges of the kept tokens in the file, groups keep both delimiters
+    pub fn ranges(&self, contents: &str, tokens: TokenStream) -> Option<Vec<(usize, usize)>> {
+        let mut ranges = TokenRanges {
+            lines: Lines::new(contents),
+            starts: HashMap::new(),
+            ends: HashMap::new(),
+        };
+        ranges.add_tokens(tokens);
+
+        let mut keep = Vec::new();
+        for p in &self.starts {
+            keep.extend(ranges.starts.get(p)?);
+        }
+        for p in &self.ends {
+            keep.extend(ranges.ends.get(p)?);
+        }
+        keep.sort();
+        keep.dedup();
+        Some(keep)
+    }
+}
+
+// Byte ranges needed to keep each token, indexed by where the token starts and ends
+struct TokenRanges<'a> {
+    lines: Lines<'a>,
+    starts: HashMap<Pos, Vec<(usize, usize)>>,
+    ends: HashMap<Pos, Vec<(usize, usize)>>,
+}
+
+impl<'a> TokenRanges<'a> {
+    fn add_tokens(&mut self, tokens: TokenStream) {
+        for tt in tokens {
+            match tt {
+                TokenTree::Group(g) => {
+                    let (open, close) = (g.span_open(), g.span_close());
+                    let ranges = vec![self.lines.range(&open), self.lines.range(&close)];
+                    self.add_ranges(open, ranges.to_vec());
+                    self.add_ranges(close, ranges);
+                    self.add_tokens(g.stream());
+                }
+                tt => self.add_ranges(tt.span(), vec![self.lines.range(&tt.span())]),
+            }
+        }
+    }
+
+    // Doc comments become several tokens with the same span, so the first one is kept
+    fn add_ranges(&mut self, span: Span, ranges: Vec<(usize, usize)>) {
+        self.starts
+            .entry(pos(span.start()))
+            .or_insert(ranges.to_vec());
+        self.ends.entry(pos(span.end())).or_insert(ranges);
+    }
+}
+
+// Converts line/column positions to byte offsets
+struct Lines<'a> {
+    contents: &'a str,
+    starts: Vec<usize>,
+}
+
+impl<'a> Lines<'a> {
+    fn new(contents: &'a str) -> Self {
+        Self {
+            contents,
+            starts: [0]
+                .into_iter()
+                .chain(contents.match_indices('\n').map(|(i, _)| i + 1))
+                .collect(),
+        }
+    }
+
+    fn offset(&self, lc: LineColumn) -> usize {
+        let start = self.starts.get(lc.line - 1).copied().unwrap_or_default();
+        start
+            + self.contents[start..]
+                .chars()
+                .take(lc.column)
+                .map(char::len_utf8)
+                .sum::<usize>()
+    }
+
+    fn range(&self, span: &Span) -> (usize, usize) {
+        (self.offset(span.start()), self.offset(span.end()))
+    }
+}
+
+// Replaces everything except the kept ranges with whitespace
+// Lines keep the same bytes and characters so spans don't move
+pub fn skeleton(contents: &str, keep: &Vec<(usize, usize)>) -> Option<String> {
+    let mut skeleton = String::with_capacity(contents.len());
+    let mut i = 0;
+    for (start, end) in keep {
+        skeleton.push_str(&blank(contents.get(i..*start)?)?);
+        skeleton.push_str(contents.get(*start..*end)?);
+        i = *end;
+    }
+    skeleton.push_str(&blank(contents.get(i..)?)?);
+    Some(skeleton)
+}
+
+// Whitespace with the same width as each character
+// Four byte characters have none, so they are put in a comment padded with spaces
+fn blank(text: &str) -> Option<String> {
+    let mut chars = text
+        .chars()
+        .map(|c| match (c, c.len_utf8()) {
+            ('\n' | '\r', _) => c,
+            (_, 1) => ' ',
+            (_, 2) => '\u{85}',
+            (_, 3) => '\u{2000}',
+            _ => '\u{10000}',
+        })
+        .collect::<Vec<_>>();
+    let wide = chars.iter().position(|c| c.len_utf8() == 4);
+    let Some(first) = wide else {
+        return Some(chars.into_iter().collect());
+    };
+    let last = chars.iter().rposition(|c| c.len_utf8() == 4)?;
+    // The comment can't touch kept tokens, e.g. `/` would make it a line comment
+    let is_space = |i: usize| chars.get(i) == Some(&' ');
+    let open = (1..first.saturating_sub(2))
+        .find(|i| is_space(i - 1) && is_space(*i) && is_space(i + 1))?;
+    let close = (last + 1..chars.len().saturating_sub(2))
+        .find(|i| is_space(*i) && is_space(i + 1) && is_space(i + 2))?;
+    chars.splice(open..open + 2, ['/', '*']);
+    chars.splice(close..close + 2, ['*', '/']);
+    Some(chars.into_iter().collect())
+}
+
+// Tokens of a file, found by lexing its skeleton
+// Spans are indexed by the positions where they start and end
+// Idents and literals are reused since creating them adds sources, which moves later spans
+pub struct Tokens {
+    starts: HashMap<Pos, Span>,
+    ends: HashMap<Pos, Span>,
+    idents: HashMap<Pos, Ident>,
+    literals: HashMap<Pos, Literal>,
+}
+
+impl Tokens {
+    pub fn lex(contents: &str) -> Option<Self> {
+        let mut tokens = Self {
+            starts: HashMap::new(),
+            ends: HashMap::new(),
+            idents: HashMap::new(),
+            literals: HashMap::new(),
+        };
+        tokens.add_tokens(TokenStream::from_str(contents).ok()?);
+        Some(tokens)
+    }
+
+    fn add_tokens(&mut self, tokens: TokenStream) {
+        for tt in tokens {
+            match tt {
+                TokenTree::Group(g) => {
+                    self.add_span(g.span_open());
+                    self.add_span(g.span_close());
+                    self.add_tokens(g.stream());
+                }
+                TokenTree::Ident(i) => {
+                    self.add_span(i.span());
+                    self.idents.insert(pos(i.span().start()), i);
+                }
+                TokenTree::Literal(l) => {
+                    self.add_span(l.span());
+                    self.literals.insert(pos(l.span().start()), l);
+                }
+                TokenTree::Punct(p) => self.add_span(p.span()),
+            }
+        }
+    }
+
+    fn add_span(&mut self, span: Span) {
+        self.starts.entry(pos(span.start())).or_insert(span);
+        self.ends.entry(pos(span.end())).or_insert(span);
+    }
+
+    pub fn span(&self, CachedSpan(start, end): &CachedSpan) -> Option<Span> {
+        self.starts.get(start)?.join(*self.ends.get(end)?)
+    }
+
+    pub fn tokens(&self, tokens: &[CachedToken]) -> Option<TokenStream> {
+        tokens
+            .iter()
+            .map(|t| {
+                Some(match t {
+                    CachedToken::Ident(p) => TokenTree::Ident(self.idents.get(p)?.clone()),
+                    CachedToken::Punct(c, joint, p) => {
+                        let mut punct = Punct::new(
+                            *c,
+                            match joint {
+                                true => Spacing::Joint,
+                                false => Spacing::Alone,
+                            },
+                        );
+                        punct.set_span(*self.starts.get(p)?);
+                        TokenTree::Punct(punct)
+                    }
+                    CachedToken::Literal(p) => TokenTree::Literal(self.literals.get(p)?.clone()),
+                    CachedToken::Group(d, span, tokens) => {
+                        let mut group = Group::new(
+                            match d {
+                                '(' => Delimiter::Parenthesis,
+                                '{' => Delimiter::Brace,
+                                _ => Delimiter::Bracket,
+                            },
+                            self.tokens(tokens)?,
+                        );
+                        group.set_span(self.span(span)?);
+                        TokenTree::Group(group)
+                    }
+                })
+            })
+            .collect()
+    }
+}
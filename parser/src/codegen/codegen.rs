@@ -1,20 +1,24 @@
 use diagnostic::{zip_match, CatchErr, CombineResults, ZipResults};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use std::{env::temp_dir, fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use shared::{
-    constants::{INDEX, INDEX_SEP},
+    constants::{INDEX, INDEX_ENV, INDEX_SEP},
     syn::{
         error::{CriticalResult, StrToError},
         ToRange,
     },
-    traits::{Catch, CollectVec, CollectVecInto, ThenOk},
+    traits::{Catch, CollectVec, CollectVecInto, NoneOr, ThenOk},
 };
 
 use crate::{
     codegen::Traits,
     parse::AstCrate,
+    profile::Profiler,
     resolve::Items,
     utils::{
         constants::NAMESPACE,
@@ -22,29 +26,46 @@ use crate::{
     },
 };
 
-use super::Crates;
+use super::{source_map::write_source_map, Crates};
 
-pub fn codegen(crates: &mut Crates, items: &Items) -> CriticalResult<Vec<TokenStream>> {
+pub fn codegen(
+    crates: &mut Crates,
+    items: &Items,
+    profiler: &Profiler,
+) -> CriticalResult<Vec<TokenStream>> {
     let main_cr_idx = crates.get_crate_index(Crate::Main);
     let macro_cr_idx = crates.get_crate_index(Crate::Macros);
 
     // Generate globals struct
-    let globals = super::globals(main_cr_idx, &items.globals, crates);
+    let globals = super::globals(main_cr_idx, &items.globals, &items.ids, crates);
 
     // Generate components struct
-    let components = super::components(main_cr_idx, &items.components, crates);
+    let components = super::components(main_cr_idx, &items.components, &items.ids, crates);
 
     // Generate component trait implementations
-    let component_traits = super::component_trait_impls(main_cr_idx, &items.components, crates);
+    let component_traits =
+        super::component_trait_impls(main_cr_idx, &items.components, &items.ids, crates);
 
     // Generate events/states enums
-    let events_enums = super::events_enums(&items.events, &items.states);
+    let events_enums = super::events_enums(&items.events, &items.states, &items.ids);
 
     // Generate events struct
-    let events = super::events(main_cr_idx, &items.events, &items.states, crates);
+    let events = super::events(
+        main_cr_idx,
+        &items.events,
+        &items.states,
+        &items.ids,
+        crates,
+    );
 
     // Generate event trait implementations
-    let event_traits = super::event_trait_impls(main_cr_idx, &items.events, &items.states, crates);
+    let event_traits = super::event_trait_impls(
+        main_cr_idx,
+        &items.events,
+        &items.states,
+        &items.ids,
+        crates,
+    );
 
     // Generate event/component traits
     let trait_defs = crates
@@ -81,6 +102,15 @@ pub fn codegen(crates: &mut Crates, items: &Items) -> CriticalResult<Vec<TokenSt
         .combine_results()
         .map(|use_stmts| quote!(#(use #use_stmts;)*));
 
+    // Record the size of each section for the profile
+    profiler.codegen_section("globals", globals.as_ref().ok());
+    profiler.codegen_section("components", components.as_ref().ok());
+    profiler.codegen_section("component_traits", component_traits.as_ref().ok());
+    profiler.codegen_section("event_enums", Some(&events_enums));
+    profiler.codegen_section("events", events.as_ref().ok());
+    profiler.codegen_section("event_traits", event_traits.as_ref().ok());
+    profiler.codegen_section("systems", manager_impl.as_ref().ok());
+
     // Write codegen to file
     let namespace = format_ident!("{NAMESPACE}");
     let allows = quote!(
@@ -88,6 +118,7 @@ pub fn codegen(crates: &mut Crates, items: &Items) -> CriticalResult<Vec<TokenSt
         #[allow(unused_variables)]
         #[allow(unused_parens)]
         #[allow(dead_code)]
+        #[allow(unused_doc_comments)]
     );
     zip_match!(
         (
@@ -144,15 +175,49 @@ pub fn codegen(crates: &mut Crates, items: &Items) -> CriticalResult<Vec<TokenSt
     )
 }
 
+// Pretty prints generated code, falls back to the unformatted tokens if it doesn't parse
+pub fn format_code(code: &TokenStream) -> String {
+    syn::parse2(code.clone()).map_or_else(|_| code.to_string(), |file| prettyplease::unparse(&file))
+}
+
+pub fn get_codegen_dir() -> CriticalResult<PathBuf> {
+    Ok(PathBuf::from(
+        std::env::var("OUT_DIR").catch_err("No out dir specified".trace())?,
+    ))
+}
+
+fn get_codegen_file(out: &Path, i: usize) -> PathBuf {
+    out.join(format!("{}.rs", i))
+}
+
+// Reads the codegen from the previous build
+pub fn read_codegen(num_crates: usize) -> CriticalResult<Vec<String>> {
+    let out = get_codegen_dir()?;
+    (0..num_crates)
+        .map(|i| {
+            let file = get_codegen_file(&out, i);
+            fs::read_to_string(&file)
+                .catch_err(format!("Could not read: {}", file.display()).trace())
+        })
+        .collect()
+}
+
 pub fn write_codegen(code: Vec<(&AstCrate, String)>) -> CriticalResult<()> {
-    let out = PathBuf::from(std::env::var("OUT_DIR").catch_err("No out dir specified".trace())?);
+    let out = get_codegen_dir()?;
 
     let mut index_lines = Vec::new();
-    for (i, (cr, code)) in code.into_iter().enumerate() {
-        // Write to file
-        let file = out.join(format!("{}.rs", i));
-        fs::write(file.to_owned(), code)
-            .catch_err(format!("Could not write to: {}", file.display()).trace())?;
+    let mut files = Vec::new();
+    for (i, (cr, code)) in code.iter().enumerate() {
+        // Write to file, leaving it untouched if unchanged
+        let file = get_codegen_file(&out, i);
+        if fs::read_to_string(&file)
+            .ok()
+            .is_none_or_into(|c| &c != code)
+        {
+            fs::write(file.to_owned(), code)
+                .catch_err(format!("Could not write to: {}", file.display()).trace())?;
+        }
+        files.push((file.to_owned(), code.as_str()));
         index_lines.push(format!(
             "{}{}{}",
             cr.dir.to_string_lossy().to_string(),
@@ -161,7 +226,15 @@ pub fn write_codegen(code: Vec<(&AstCrate, String)>) -> CriticalResult<()> {
         ));
     }
 
+    write_source_map(&out, files)?;
+
     // Create index file
-    fs::write(temp_dir().join(INDEX), index_lines.join("\n"))
-        .catch_err(format!("Could not write to index file: {INDEX}").trace())
+    let index = out.join(INDEX);
+    fs::write(&index, index_lines.join("\n"))
+        .catch_err(format!("Could not write to index file: {}", index.display()).trace())?;
+
+    // Expose the index to this crate and, through `links`, to dependents' build scripts
+    println!("cargo:rustc-env={INDEX_ENV}={}", index.display());
+    println!("cargo:index={}", index.display());
+    Ok(())
 }
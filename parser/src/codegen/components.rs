@@ -10,7 +10,7 @@ use shared::{
 
 use crate::{
     parse::ItemPath,
-    resolve::ItemComponent,
+    resolve::{ItemComponent, ItemIds},
     utils::{
         idents::{component_var, CodegenIdents, CODEGEN_IDENTS},
         paths::{Crate, ENGINE_GLOBALS, ENGINE_PATHS, ENGINE_TRAITS},
@@ -18,14 +18,17 @@ use crate::{
 };
 
 use super::{
+    source_doc,
     traits::{trait_defs, GetTraitTypes},
-    Crates,
+    Crates, SourceKind,
 };
 
 struct CodegenArgs<'a> {
     struct_name: &'a syn::Ident,
     components: &'a Vec<ItemComponent>,
+    vars: Vec<syn::Ident>,
     types: Vec<syn::Path>,
+    docs: Vec<TokenStream>,
     entity_set: syn::Path,
     entity_trash: syn::Path,
     entity_map: syn::Path,
@@ -36,17 +39,17 @@ fn codegen<'a>(
     CodegenArgs {
         struct_name,
         components,
+        vars,
         types,
+        docs,
         entity_set,
         entity_trash,
         entity_map,
         singleton,
     }: CodegenArgs<'a>,
 ) -> TokenStream {
-    let mut vars = Vec::new();
     let [mut tys, mut news, mut adds, mut appends, mut removes] = array::from_fn(|_| Vec::new());
-    for (i, (c, ty)) in components.iter().zip(types).enumerate() {
-        let var = component_var(i);
+    for ((c, var), ty) in components.iter().zip(&vars).zip(types) {
         if c.args.is_singleton {
             tys.push(quote!(#singleton<#ty>));
             news.push(quote!(#singleton::None));
@@ -65,13 +68,12 @@ fn codegen<'a>(
             appends.push(quote!(self.#var.extend(cm.#var.drain());));
             removes.push(quote!(self.#var.remove(&eid);));
         }
-        vars.push(var);
     }
 
     quote!(
         struct #struct_name {
             eids: #entity_set,
-            #(#vars: #tys),*
+            #(#docs #vars: #tys),*
         }
 
         impl #struct_name {
@@ -100,12 +102,22 @@ fn codegen<'a>(
 pub fn components(
     cr_idx: usize,
     components: &Vec<ItemComponent>,
+    ids: &ItemIds,
     crates: &Crates,
 ) -> CriticalResult<TokenStream> {
-    let vars = (0..components.len()).map_vec_into(|i| component_var(i));
+    let vars = (0..components.len()).map_vec_into(|i| component_var(ids, i));
     let types = components
         .map_vec(|c| crates.get_item_syn_path(cr_idx, &c.data.path))
         .combine_results();
+    let docs = components.map_vec(|c| {
+        source_doc(
+            crates,
+            SourceKind::Component,
+            &c.data.path,
+            c.data.mod_idx,
+            &c.data.span,
+        )
+    });
 
     let entity_set = crates.get_syn_path(cr_idx, &ENGINE_PATHS.entity_set);
     let entity_trash = crates.get_syn_path(cr_idx, &ENGINE_GLOBALS.entity_trash);
@@ -117,7 +129,9 @@ pub fn components(
             codegen(CodegenArgs {
                 struct_name: &CODEGEN_IDENTS.components,
                 components,
+                vars,
                 types,
+                docs,
                 entity_set,
                 entity_trash,
                 entity_map,
@@ -149,6 +163,7 @@ pub fn component_trait_defs(
 pub fn component_trait_impls(
     cr_idx: usize,
     components: &Vec<ItemComponent>,
+    ids: &ItemIds,
     crates: &Crates,
 ) -> CriticalResult<TokenStream> {
     let macro_cr_idx = crates.get_crate_index(Crate::Macros);
@@ -174,7 +189,7 @@ pub fn component_trait_impls(
         (types, crate_paths, add_comp_trait, entity, singleton) => {
             let mut adds = Vec::new();
             for (i, c) in components.iter().enumerate() {
-                let var = component_var(i);
+                let var = component_var(ids, i);
                 adds.push(if c.args.is_singleton {
                     quote!(self.#var = #singleton::new(e, t))
                 } else {
@@ -1,4 +1,5 @@
 use diagnostic::{CombineResults, ToErr};
+use proc_macro2::Span;
 use shared::{
     syn::{
         error::{CriticalResult, GetVec, StrToError},
@@ -8,7 +9,7 @@ use shared::{
 };
 
 use crate::{
-    parse::{AstCrate, AstMod, ItemPath, ItemSpan},
+    parse::{AstCrate, AstMod, GenericArg, ItemPath, ItemSpan},
     utils::{
         constants::NAMESPACE,
         paths::{Crate, CratePath},
@@ -143,14 +144,67 @@ impl Crates {
         } else {
             0
         };
+        let generics = self.get_generics(start_idx, &path.generics)?;
         self.get_crate_path(start_idx, path.cr_idx).map_or(
             format!("No path from crate {start_idx} to crate {}", path.cr_idx)
                 .trace()
                 .as_err(),
-            |pre| Ok([pre, path.path[i..].to_vec()].concat()),
+            |pre| {
+                let mut path = [pre, path.path[i..].to_vec()].concat();
+                if let Some(last) = path.last_mut() {
+                    last.push_str(&generics);
+                }
+                Ok(path)
+            },
         )
     }
 
+    // Renders generic arguments, e.g. "<crate::Player, u32>"
+    fn get_generics(&self, start_idx: usize, generics: &Vec<GenericArg>) -> CriticalResult<String> {
+        if generics.is_empty() {
+            return Ok(String::new());
+        }
+        generics
+            .map_vec(|arg| match arg {
+                GenericArg::Item(path) => self.get_item_path(start_idx, path).map(|p| p.join("::")),
+                GenericArg::Builtin(ty, generics) => self
+                    .get_generics(start_idx, generics)
+                    .map(|g| format!("{ty}{g}")),
+            })
+            .combine_results()
+            .map(|args| format!("<{}>", args.join(", ")))
+    }
+
+    // Crate that declares the item's traits, it must be able to name every crate in the path
+    pub fn get_item_owner(&self, path: &ItemPath) -> usize {
+        fn add_crates(path: &ItemPath, cr_idxs: &mut Vec<usize>) {
+            cr_idxs.push(path.cr_idx);
+            for arg in &path.generics {
+                add_generic_crates(arg, cr_idxs);
+            }
+        }
+        fn add_generic_crates(arg: &GenericArg, cr_idxs: &mut Vec<usize>) {
+            match arg {
+                GenericArg::Item(path) => add_crates(path, cr_idxs),
+                GenericArg::Builtin(_, generics) => {
+                    generics.iter().for_each(|g| add_generic_crates(g, cr_idxs))
+                }
+            }
+        }
+
+        let mut cr_idxs = Vec::new();
+        add_crates(path, &mut cr_idxs);
+        cr_idxs
+            .iter()
+            .find(|start| {
+                cr_idxs
+                    .iter()
+                    .all(|end| self.get_crate_path(**start, *end).is_some())
+            })
+            .copied()
+            .unwrap_or(self.get_crate_index(Crate::Main))
+    }
+
     pub fn get_path(&self, start_idx: usize, item: &CratePath) -> CriticalResult<Vec<String>> {
         self.get_item_path(
             start_idx,
@@ -194,6 +248,24 @@ impl Crates {
             .and_then(|cr| cr.mods.try_get(mod_idx))
     }
 
+    // "file:line:column" of a span in a module, 1-based
+    pub fn get_location(&self, cr_idx: usize, mod_idx: usize, span: &Span) -> String {
+        let start = span.start();
+        format!(
+            "{}:{}:{}",
+            self.get_mod(cr_idx, mod_idx)
+                .map_or_else(|_| "<unknown>".to_string(), |m| m.get_file()),
+            start.line,
+            start.column + 1
+        )
+    }
+
+    // Path as written in the main crate
+    pub fn get_item_name(&self, path: &ItemPath) -> String {
+        self.get_item_path(self.get_crate_index(Crate::Main), path)
+            .map_or_else(|_| path.to_string(), |path| path.join("::"))
+    }
+
     pub fn get_mod_mut(&mut self, cr_idx: usize, mod_idx: usize) -> CriticalResult<&mut AstMod> {
         self.crates
             .try_get_mut(cr_idx)
@@ -9,7 +9,7 @@ use shared::{
 
 use crate::{
     parse::ItemPath,
-    resolve::{ItemEvent, ItemState},
+    resolve::{ItemEvent, ItemIds, ItemState},
     utils::{
         idents::{
             event_var, event_variant, state_var, state_variant, CodegenIdents, CODEGEN_IDENTS,
@@ -23,23 +23,27 @@ use super::{
     Crates,
 };
 
-pub fn events_enums(events: &Vec<ItemEvent>, states: &Vec<ItemState>) -> TokenStream {
+pub fn events_enums(
+    events: &Vec<ItemEvent>,
+    states: &Vec<ItemState>,
+    ids: &ItemIds,
+) -> TokenStream {
     let CodegenIdents {
         event_enum,
-        event_enum_len,
         state_enum,
         ..
     } = &*CODEGEN_IDENTS;
 
-    let num_variants = events.len();
-    let e_variants = (0..events.len()).map_vec_into(|i| event_variant(i));
+    // Discriminants are the ids so adding an event doesn't renumber the others
+    let (e_variants, e_ids) =
+        (0..events.len()).unzip_vec_into(|i| (event_variant(ids, i), ids.event(i)));
 
     let (s_variants, s_enter_events, s_exit_events) = states.enumer_unzipn_vec(
         |(i, s)| {
             (
-                state_variant(i),
-                event_variant(s.enter_event),
-                event_variant(s.exit_event),
+                state_variant(ids, i),
+                event_variant(ids, s.enter_event),
+                event_variant(ids, s.exit_event),
             )
         },
         Unzip3::unzip3_vec,
@@ -52,12 +56,11 @@ pub fn events_enums(events: &Vec<ItemEvent>, states: &Vec<ItemState>) -> TokenSt
         }
 
         #[derive(Hash, Clone, Copy, Eq, PartialEq, Debug)]
+        #[repr(u32)]
         enum #event_enum {
-            #(#e_variants),*
+            #(#e_variants = #e_ids),*
         }
 
-        const #event_enum_len: usize = #num_variants;
-
         impl #event_enum {
             fn enters_state(&self) -> Option<#state_enum> {
                 match self {
@@ -80,6 +83,7 @@ pub fn events(
     cr_idx: usize,
     events: &Vec<ItemEvent>,
     states: &Vec<ItemState>,
+    ids: &ItemIds,
     crates: &Crates,
 ) -> CriticalResult<TokenStream> {
     let CodegenIdents {
@@ -93,13 +97,19 @@ pub fn events(
     } = &*CODEGEN_IDENTS;
 
     let (e_vars, e_variants) =
-        (0..events.len()).unzip_vec_into(|i| (event_var(i), event_variant(i)));
+        (0..events.len()).unzip_vec_into(|i| (event_var(ids, i), event_variant(ids, i)));
     let e_types = events
         .map_vec(|e| crates.get_item_syn_path(cr_idx, &e.data.path))
         .combine_results();
 
     let (s_vars, s_variants, s_exit_events) = states.enumer_unzipn_vec(
-        |(i, s)| (state_var(i), state_variant(i), event_variant(s.exit_event)),
+        |(i, s)| {
+            (
+                state_var(ids, i),
+                state_variant(ids, i),
+                event_variant(ids, s.exit_event),
+            )
+        },
         Unzip3::unzip3_vec,
     );
     let s_types = states
@@ -225,19 +235,20 @@ pub fn event_trait_impls(
     cr_idx: usize,
     events: &Vec<ItemEvent>,
     states: &Vec<ItemState>,
+    ids: &ItemIds,
     crates: &Crates,
 ) -> CriticalResult<TokenStream> {
     let macro_cr_idx = crates.get_crate_index(Crate::Macros);
 
     // Implement trait for every event
     let (e_vars, e_variants) =
-        (0..events.len()).unzip_vec_into(|i| (event_var(i), event_variant(i)));
+        (0..events.len()).unzip_vec_into(|i| (event_var(ids, i), event_variant(ids, i)));
     let e_types = events
         .map_vec(|e| crates.get_item_syn_path(cr_idx, &e.data.path))
         .combine_results();
 
     // Implement trait for every state
-    let s_vars = (0..states.len()).map_vec_into(|i| state_var(i));
+    let s_vars = (0..states.len()).map_vec_into(|i| state_var(ids, i));
     let s_types = states
         .map_vec(|s| crates.get_item_syn_path(cr_idx, &s.data.path))
         .combine_results();
@@ -7,7 +7,7 @@ use shared::{
 };
 
 use crate::{
-    resolve::ItemGlobal,
+    resolve::{ItemGlobal, ItemIds},
     utils::idents::{global_var, CODEGEN_IDENTS},
 };
 
@@ -34,7 +34,7 @@ fn codegen(
         impl #struct_name {
             fn new() -> Self {
                 Self {
-                    #(#vars: #types::new()),*
+                    #(#vars: <#types>::new()),*
                 }
             }
         }
@@ -44,9 +44,10 @@ fn codegen(
 pub fn globals(
     cr_idx: usize,
     globals: &Vec<ItemGlobal>,
+    ids: &ItemIds,
     crates: &Crates,
 ) -> CriticalResult<TokenStream> {
-    let vars = (0..globals.len()).map_vec_into(|i| global_var(i));
+    let vars = (0..globals.len()).map_vec_into(|i| global_var(ids, i));
     let types = globals
         .map_vec(|g| crates.get_item_syn_path(cr_idx, &g.data.path))
         .combine_results();
@@ -14,7 +14,7 @@ use crate::{
     },
 };
 
-use super::Crates;
+use super::{source_doc, Crates, SourceKind};
 
 pub fn manager_def() -> TokenStream {
     let CodegenIdents {
@@ -23,7 +23,6 @@ pub fn manager_def() -> TokenStream {
         components,
         events,
         event_enum,
-        event_enum_len,
         cfoo_var: comps_var,
         gfoo_var: globals_var,
         efoo_var: events_var,
@@ -38,7 +37,7 @@ pub fn manager_def() -> TokenStream {
             #globals_var: #globals,
             #events_var: #events,
             #stack_var: Vec<std::collections::VecDeque<(#event_enum, usize)>>,
-            #services_var: [Vec<Box<dyn Fn(&mut #components, &mut #globals, &mut #events)>>; #event_enum_len]
+            #services_var: std::collections::HashMap<#event_enum, Vec<Box<dyn Fn(&mut #components, &mut #globals, &mut #events)>>>
         }
     )
 }
@@ -85,7 +84,6 @@ pub fn manager_impl(
         components,
         events,
         event_enum,
-        event_enum_len,
         cfoo_var,
         gfoo_var,
         efoo_var,
@@ -95,12 +93,12 @@ pub fn manager_impl(
         ..
     } = &*CODEGEN_IDENTS;
 
-    let global_paths = ENGINE_GLOBALS.get_global_vars(crates, cr_idx);
+    let global_paths = ENGINE_GLOBALS.get_global_vars(crates, cr_idx, &items.ids);
     let result = codegen_systems(cr_idx, items, crates);
     let init_events = init_events_fn(cr_idx, items, crates);
     let path_to_engine = crates.get_named_crate_syn_path(cr_idx, Crate::Engine);
     let component_set_fns =
-        ComponentSet::codegen_get_keys_fns(cr_idx, &items.component_sets, crates);
+        ComponentSet::codegen_get_keys_fns(cr_idx, &items.component_sets, &items.ids, crates);
     let manager_trait = crates.get_syn_path(cr_idx, &ENGINE_PATHS.manager_trait);
 
     zip_match!(
@@ -109,6 +107,7 @@ pub fn manager_impl(
                 init_systems,
                 mut systems,
                 mut system_events,
+                mut system_docs,
             } = result;
             let EngineGlobalPaths {
                 c_foo: g_c_foo,
@@ -121,8 +120,15 @@ pub fn manager_impl(
             } = global_paths;
             // Add state cleanup systems
             for state in &items.states {
-                let s_label = component_var(state.label);
-                system_events.push(event_variant(state.exit_event));
+                let s_label = component_var(&items.ids, state.label);
+                system_events.push(event_variant(&items.ids, state.exit_event));
+                system_docs.push(source_doc(
+                    crates,
+                    SourceKind::State,
+                    &state.data.path,
+                    state.data.mod_idx,
+                    &state.data.span,
+                ));
                 systems.push(quote!(
                     #gfoo_var.#g_entity_trash.0.extend(#cfoo_var.#s_label.keys());
                 ));
@@ -132,11 +138,12 @@ pub fn manager_impl(
                     #(#component_set_fns)*
 
                     fn add_system(&mut self, e: #event_enum, f: Box<dyn Fn(&mut #components, &mut #globals, &mut #events)>) {
-                        self.#services_var[e as usize].push(f);
+                        self.#services_var.entry(e).or_default().push(f);
                     }
 
                     fn add_systems(&mut self) {
                         #(
+                            #system_docs
                             self.add_system(
                                 #event_enum::#system_events,
                                 Box::new(|#cfoo_var: &mut #components, #gfoo_var: &mut #globals, #efoo_var: &mut #events| {
@@ -168,16 +175,16 @@ pub fn manager_impl(
                                 .last_mut()
                                 .and_then(|queue| queue.front_mut())
                                 .and_then(|(e, i)| {
-                                    let v_s = &self.#services_var[*e as usize];
-                                    v_s.get(*i).map(|_| {
-                                        let vals = (e.clone(), i.clone(), v_s.len());
+                                    let n = self.#services_var.get(e).map_or(0, |v_s| v_s.len());
+                                    (*i < n).then(|| {
+                                        let vals = (e.clone(), i.clone(), n);
                                         *i += 1;
                                         vals
                                     })
                                 })
                             {
                                 self.#gfoo_var.#g_e_foo = #events::new();
-                                if let Some(s) = self.#services_var[e as usize].get(i) {
+                                if let Some(s) = self.#services_var.get(&e).and_then(|v_s| v_s.get(i)) {
                                     (s)(&mut self.#cfoo_var, &mut self.#gfoo_var, &mut self.#efoo_var);
                                 }
                                 if i + 1 >= n {
@@ -239,7 +246,7 @@ pub fn manager_impl(
                             #gfoo_var: #globals::new(),
                             #efoo_var: #events::new(),
                             #stack_var: Vec::new(),
-                            #services_var: std::array::from_fn(|_| Vec::new())
+                            #services_var: std::collections::HashMap::new()
                         };
                         s.init();
                         s.add_systems();
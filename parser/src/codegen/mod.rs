@@ -4,12 +4,14 @@ mod crate_paths;
 mod events;
 mod globals;
 mod manager;
+mod source_map;
 mod traits;
 
-pub use codegen::{codegen, write_codegen};
+pub use codegen::{codegen, format_code, get_codegen_dir, read_codegen, write_codegen};
 pub use components::{component_trait_defs, component_trait_impls, components};
 pub use crate_paths::Crates;
 pub use events::{event_trait_defs, event_trait_impls, events, events_enums};
 pub use globals::globals;
 pub use manager::{manager_def, manager_impl};
+pub use source_map::{source_doc, SourceKind};
 pub use traits::Traits;
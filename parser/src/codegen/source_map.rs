@@ -0,0 +1,142 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use diagnostic::CatchErr;
+use once_cell::sync::Lazy;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use regex::Regex;
+use serde::Serialize;
+
+use shared::syn::error::{CriticalResult, StrToError};
+
+use crate::parse::ItemPath;
+
+use super::Crates;
+
+const SOURCE_MAP_FILE: &str = "hyperfold_source_map.json";
+// Increment when the source map format changes
+const SOURCE_MAP_VERSION: u32 = 1;
+
+// Matches the comments printed for `source_doc` attributes
+static SOURCE_COMMENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^( *)/// (Component|System|State) (.+) at (.+):(\d+):(\d+)$").unwrap()
+});
+
+#[derive(Copy, Clone, Debug)]
+pub enum SourceKind {
+    Component,
+    System,
+    // Cleanup system for a state's exit event
+    State,
+}
+
+impl Display for SourceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SourceKind::Component => "Component",
+            SourceKind::System => "System",
+            SourceKind::State => "State",
+        })
+    }
+}
+
+// Doc attribute naming the item that generated the following code
+// It is printed as a comment by `format_code` and read back into the source map
+pub fn source_doc(
+    crates: &Crates,
+    kind: SourceKind,
+    path: &ItemPath,
+    m_idx: usize,
+    span: &Span,
+) -> TokenStream {
+    let doc = format!(
+        " {kind} {} at {}",
+        crates.get_item_name(path),
+        crates.get_location(path.cr_idx, m_idx, span)
+    );
+    quote!(#[doc = #doc])
+}
+
+// Lines in a generated file that came from a user item, 1-based and inclusive
+#[derive(Serialize)]
+struct SourceBlock {
+    kind: String,
+    item: String,
+    start_line: usize,
+    end_line: usize,
+    file: String,
+    line: usize,
+    column: usize,
+}
+
+#[derive(Serialize)]
+struct SourceMapFile {
+    generated: String,
+    blocks: Vec<SourceBlock>,
+}
+
+// Maps lines in the generated files to the items they came from
+// Lets errors in generated code be traced back to user code
+#[derive(Serialize)]
+struct SourceMap {
+    version: u32,
+    files: Vec<SourceMapFile>,
+}
+
+// Each block is the statement or field after a source comment
+// It ends on the same line or at the next line with the same indent
+fn get_source_blocks(code: &str) -> Vec<SourceBlock> {
+    let lines = code.lines().collect::<Vec<_>>();
+    let indent = |line: &str| line.len() - line.trim_start().len();
+
+    let mut blocks = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let Some(captures) = SOURCE_COMMENT.captures(line) else {
+            continue;
+        };
+        let start = i + 1;
+        let Some(first) = lines.get(start) else {
+            continue;
+        };
+        let end = match first.trim_end().ends_with([';', ',']) {
+            true => start,
+            false => lines[start + 1..]
+                .iter()
+                .position(|l| !l.trim().is_empty() && indent(l) <= indent(first))
+                .map_or(lines.len() - 1, |j| start + 1 + j),
+        };
+        let get = |i: usize| captures.get(i).map_or("", |m| m.as_str());
+        blocks.push(SourceBlock {
+            kind: get(2).to_string(),
+            item: get(3).to_string(),
+            start_line: start + 1,
+            end_line: end + 1,
+            file: get(4).to_string(),
+            line: get(5).parse().unwrap_or_default(),
+            column: get(6).parse().unwrap_or_default(),
+        });
+    }
+    blocks
+}
+
+pub fn write_source_map(out: &Path, files: Vec<(PathBuf, &str)>) -> CriticalResult<()> {
+    let source_map = SourceMap {
+        version: SOURCE_MAP_VERSION,
+        files: files
+            .into_iter()
+            .map(|(file, code)| SourceMapFile {
+                generated: file.display().to_string(),
+                blocks: get_source_blocks(code),
+            })
+            .collect(),
+    };
+
+    let file = out.join(SOURCE_MAP_FILE);
+    let data = serde_json::to_string_pretty(&source_map)
+        .catch_err("Could not serialize source map".trace())?;
+    fs::write(&file, data).catch_err(format!("Could not write to: {}", file.display()).trace())
+}
@@ -37,7 +37,10 @@ pub fn trait_defs<const N: usize>(
             let tr = crates.get_syn_path(cr_idx, tr);
             let paths = items
                 .get_paths()
-                .filter_map_vec_into(|p| (cr_idx == p.cr_idx).then(|| vec_to_path(p.path.to_vec())))
+                .filter_map_vec_into(|p| {
+                    (cr_idx == crates.get_item_owner(p))
+                        .then(|| crates.get_item_syn_path(cr_idx, p))
+                })
                 .combine_results();
             zip_match!((tr, paths) => {
                 paths.map_vec_into(|p| quote!(#tr<#p>))
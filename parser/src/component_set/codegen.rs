@@ -9,6 +9,7 @@ use shared::{
 use crate::{
     codegen::Crates,
     parse::ComponentSymbol,
+    resolve::ItemIds,
     system::ComponentSetFnArg,
     utils::{
         idents::{
@@ -63,6 +64,7 @@ impl ComponentSet {
     pub fn codegen_get_keys_fns(
         cr_idx: usize,
         component_sets: &Vec<Self>,
+        ids: &ItemIds,
         crates: &Crates,
     ) -> CriticalResult<Vec<TokenStream>> {
         let filter_fn = crates.get_syn_path(cr_idx, &ENGINE_PATHS.filter);
@@ -71,13 +73,14 @@ impl ComponentSet {
 
         zip_match!((filter_fn, entity, entity_set) => {
             component_sets
-                .enumer_map_vec(|(i, cs)| cs.codegen_get_keys(i, &filter_fn, &entity, &entity_set))
+                .enumer_map_vec(|(i, cs)| cs.codegen_get_keys(ids, i, &filter_fn, &entity, &entity_set))
         })
     }
 
     // Generates function which get the keys used in a component set
     fn codegen_get_keys(
         &self,
+        ids: &ItemIds,
         cs_idx: usize,
         filter: &syn::Path,
         entity: &syn::Path,
@@ -100,9 +103,9 @@ impl ComponentSet {
             ..
         } = &*CODEGEN_IDENTS;
 
-        let get_keys = component_set_keys_fn(cs_idx, false);
+        let get_keys = component_set_keys_fn(ids, cs_idx, false);
         let get_keys_fn = quote!(#get_keys<'a>(#comps_var: &#components, #eids_var: &'a #entity_set) -> Option<&'a #entity>);
-        let get_keys_vec = component_set_keys_fn(cs_idx, true);
+        let get_keys_vec = component_set_keys_fn(ids, cs_idx, true);
         let get_keys_vec_fn = quote!(#get_keys_vec<'a>(#comps_var: &#components, #eids_var: &'a #entity_set) -> Vec<(&'a #entity, ())>);
 
         // Get labels expression and first/singleton label
@@ -134,12 +137,12 @@ impl ComponentSet {
         let init = match first {
             // Arg or label
             Some(sym) if sym.comp.args.is_singleton => {
-                let var = component_var(sym.comp.idx);
+                let var = component_var(ids, sym.comp.idx);
                 // Option<K>
                 quote!(#comps_var.#var.get_key().and_then(|k| #eids_var.get(k)))
             }
             Some(sym) => {
-                let var = component_var(sym.comp.idx);
+                let var = component_var(ids, sym.comp.idx);
                 // Vec<(K, V)>
                 quote!(#comps_var.#var.keys().filter_map(|k| #eids_var.get(k).map(|k| (k, ()))).collect::<Vec<_>>())
             }
@@ -151,14 +154,14 @@ impl ComponentSet {
         };
 
         // Add filter if labels are present
-        let cs = component_set_var(cs_idx);
+        let cs = component_set_var(ids, cs_idx);
         let init_and_filter = match &labels {
             Some(expr) => {
                 let f = quote!(f);
-                let label_expr = expr.labels.quote(&|comp| component_var(comp.idx));
+                let label_expr = expr.labels.quote(&|comp| component_var(ids, comp.idx));
                 let label_vars = expr
                     .iter_symbols()
-                    .map_vec_into(|sym| component_var(sym.comp.idx));
+                    .map_vec_into(|sym| component_var(ids, sym.comp.idx));
                 let num_labels = label_vars.len();
                 let label_fn = quote!(|[#(#label_vars),*]: [bool; #num_labels]| #label_expr);
                 let eval_labels = quote!(#f([#(#comps_var.#label_vars.contains_key(k)),*]));
@@ -211,6 +214,7 @@ impl ComponentSet {
     // Generates inline code to construct values from keys
     fn codegen_get_vals(
         &self,
+        ids: &ItemIds,
         arg: &ComponentSetFnArg,
         v: syn::Ident,
         ty: &syn::Path,
@@ -238,8 +242,9 @@ impl ComponentSet {
                 if sym.comp.args.is_singleton && !arg.is_vec =>
             {
                 // Unique args
-                let var = args
-                    .filter_map_vec(|item| item.is_opt.then_none(component_var(item.sym.comp.idx)));
+                let var = args.filter_map_vec(|item| {
+                    item.is_opt.then_none(component_var(ids, item.sym.comp.idx))
+                });
 
                 let (mut arg_name, mut arg_var) = (Vec::new(), Vec::new());
                 let (mut opt_arg_name, mut opt_arg_var, mut opt_arg_get) =
@@ -253,7 +258,7 @@ impl ComponentSet {
                         false => (&mut arg_name, &mut arg_var),
                     };
                     names.push(format_ident!("{}", item.var));
-                    vars.push(component_var(item.sym.comp.idx));
+                    vars.push(component_var(ids, item.sym.comp.idx));
                 }
 
                 // Expression to assign vars
@@ -285,7 +290,7 @@ impl ComponentSet {
                 // Unique args
                 let (mut var, mut var_intersect) = (Vec::new(), Vec::new());
                 for item in &args {
-                    var.push(component_var(item.sym.comp.idx));
+                    var.push(component_var(ids, item.sym.comp.idx));
                     var_intersect.push(match item.is_opt {
                         true => intersect_opt,
                         false => intersect,
@@ -295,7 +300,7 @@ impl ComponentSet {
                 let (mut arg_name, mut arg_var) = (Vec::new(), Vec::new());
                 for item in &self.args {
                     arg_name.push(format_ident!("{}", item.var));
-                    arg_var.push(component_var(item.sym.comp.idx));
+                    arg_var.push(component_var(ids, item.sym.comp.idx));
                 }
 
                 // Expression to assign vars
@@ -358,6 +363,7 @@ impl ComponentSet {
     // Param 1: Vec<(cs function arg, cs, cs type)>
     pub fn codegen_build_sets(
         component_sets: &Vec<BuildSetsArg>,
+        ids: &ItemIds,
         intersect: &syn::Path,
         intersect_opt: &syn::Path,
     ) -> BuildSetsResult {
@@ -375,15 +381,15 @@ impl ComponentSet {
         // Now we have immutable references to eids
         let (var, get_keys_fn) = c_sets.unzip_vec(|cs| {
             (
-                component_set_var(cs.fn_arg.idx),
-                component_set_keys_fn(cs.fn_arg.idx, cs.fn_arg.is_vec),
+                component_set_var(ids, cs.fn_arg.idx),
+                component_set_keys_fn(ids, cs.fn_arg.idx, cs.fn_arg.is_vec),
             )
         });
 
         // Build sets
         let get_vals = c_sets.iter().zip(var.iter()).map_vec_into(|(cs, v)| {
             cs.cs
-                .codegen_get_vals(&cs.fn_arg, v.clone(), &cs.ty, intersect, intersect_opt)
+                .codegen_get_vals(ids, &cs.fn_arg, v.clone(), &cs.ty, intersect, intersect_opt)
         });
 
         BuildSetsResult {
@@ -392,7 +398,7 @@ impl ComponentSet {
                 #(#get_vals)*
             ),
             func_args: component_sets.map_vec(|cs| {
-                let var = component_set_var(cs.fn_arg.idx);
+                let var = component_set_var(ids, cs.fn_arg.idx);
                 match component_sets
                     .iter()
                     .rev()
@@ -417,7 +423,7 @@ impl ComponentSet {
             }),
             singletons: c_sets.filter_map_vec(|cs| {
                 match cs.cs.has_singleton() && !cs.fn_arg.is_vec {
-                    true => Some(component_set_var(cs.fn_arg.idx).quote()),
+                    true => Some(component_set_var(ids, cs.fn_arg.idx).quote()),
                     false => None,
                 }
             }),
@@ -4,8 +4,9 @@ mod parse;
 mod resolve;
 
 pub use codegen::{BuildSetsArg, BuildSetsResult};
-pub use labels::ComponentSetLabels;
-pub use resolve::ComponentSet;
+pub use labels::{ComponentSetLabels, LabelSymbol, LabelsExpression};
+pub use parse::AstComponentSet;
+pub use resolve::{ComponentSet, ComponentSetItem, LabelItem};
 
 /*
 * Pass 1: Parse into expressions
@@ -7,9 +7,10 @@ use syn::{
     Error, Token,
 };
 
-use crate::parse::ItemPath;
+use crate::{parse::ItemPath, resolve::get_type_args};
 
 use shared::{
+    error_codes::ErrorCode,
     syn::{
         error::{CatchSynError, CriticalResult, ToError},
         get_type_generics, parse_tokens, use_path_from_syn, Parse, StreamParse,
@@ -17,7 +18,7 @@ use shared::{
     traits::{CollectVec, PushInto},
 };
 
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
 pub enum LabelOp {
     And,
     Or,
@@ -248,6 +249,7 @@ impl std::fmt::Display for AstLabelItem {
 pub struct AstComponentSetItem {
     pub var: String,
     pub ty: ItemPath,
+    pub generics: Vec<syn::Type>,
     pub ref_cnt: usize,
     pub is_mut: bool,
     pub is_opt: bool,
@@ -262,26 +264,39 @@ impl AstComponentSetItem {
                 let mut path = use_path_from_syn(&Vec::new(), &ty.path);
                 match path.join("::").as_str() {
                     "Option" => {
-                        match get_type_generics(&ty)
-                            .ok_or(ty.error("Missing generics after Option<>").as_vec())?[..]
+                        match get_type_generics(&ty).ok_or(
+                            ty.error("Missing generics after Option<>")
+                                .with_code(ErrorCode::InvalidOptional)
+                                .as_vec(),
+                        )?[..]
                         {
                             [arg] => match arg {
                                 syn::GenericArgument::Type(ty) => {
                                     let mut i = Self::from(var, ty)?;
                                     if i.is_opt {
-                                        return ty.error("Cannot nest Option<>").as_err();
+                                        return ty
+                                            .error("Cannot nest Option<>")
+                                            .with_code(ErrorCode::InvalidOptional)
+                                            .as_err();
                                     }
                                     i.is_opt = true;
                                     Ok(i)
                                 }
-                                _ => arg.error("Expected type generic").as_err(),
+                                _ => arg
+                                    .error("Expected type generic")
+                                    .with_code(ErrorCode::InvalidOptional)
+                                    .as_err(),
                             },
-                            _ => ty.error("Multiple generics after Option<>").as_err(),
+                            _ => ty
+                                .error("Multiple generics after Option<>")
+                                .with_code(ErrorCode::InvalidOptional)
+                                .as_err(),
                         }
                     }
                     _ => Ok(Self {
                         var,
-                        ty: ItemPath { cr_idx: 0, path },
+                        ty: ItemPath::new(0, path),
+                        generics: get_type_args(ty),
                         ref_cnt: 0,
                         is_mut: false,
                         is_opt: false,
@@ -293,6 +308,7 @@ impl AstComponentSetItem {
                 if i.is_opt {
                     return ty
                         .error("Optional arguments may not be taken by reference")
+                        .with_code(ErrorCode::InvalidOptional)
                         .as_err();
                 }
                 i.ref_cnt += 1;
@@ -8,11 +8,15 @@ use super::{
     labels::{ComponentSetLabels, LabelSymbol, LabelsExpression},
     parse::{AstComponentSet, AstComponentSetItem, AstLabelItem, LabelOp},
 };
-use crate::parse::{
-    resolve_path, ComponentSymbol, DiscardSymbol, ItemPath, ItemSpan, MatchSymbol, ModInfo,
-    ModInfoMut, WithItemSpan,
+use crate::{
+    parse::{
+        resolve_path, ComponentSymbol, DiscardSymbol, ItemPath, ItemSpan, MatchSymbol, ModInfo,
+        ModInfoMut, WithItemSpan,
+    },
+    resolve::Items,
 };
 use shared::{
+    error_codes::ErrorCode,
     syn::{
         error::{CriticalResult, MutateResults, Result, ToError},
         get_fn_name, parse_tokens, ToRange,
@@ -99,15 +103,18 @@ impl ComponentSetItem {
         AstComponentSetItem {
             var,
             ty,
+            generics,
             ref_cnt,
             is_mut,
             is_opt,
             span,
         }: AstComponentSetItem,
+        items: &Items,
         (m, cr, crates): ModInfo,
     ) -> CriticalResult<Self> {
         let span = ItemSpan::new(cr, m, span);
         resolve_path(ty.path.to_vec(), (m, cr, crates))
+            .and_then(|sym| items.resolve_instance(sym, &generics, (m, cr, crates)))
             .expect_component()
             .discard_symbol()
             .with_item_span(&span)
@@ -182,9 +189,9 @@ impl ComponentSet {
             .is_some_and(|sym| sym.comp.args.is_singleton)
     }
 
-    pub fn parse(tokens: TokenStream, (m, cr, crates): ModInfo) -> Result<Self> {
+    pub fn parse(tokens: TokenStream, items: &Items, (m, cr, crates): ModInfo) -> Result<Self> {
         let span = tokens.span();
-        parse_tokens(tokens).and_then(|cs| Self::resolve(cs, (m, cr, crates)))
+        parse_tokens(tokens).and_then(|cs| Self::resolve(cs, items, (m, cr, crates)))
     }
 
     fn resolve(
@@ -194,10 +201,11 @@ impl ComponentSet {
             labels,
             label_ident,
         }: AstComponentSet,
+        items: &Items,
         (m, cr, crates): ModInfo,
     ) -> Result<Self> {
         args.into_iter()
-            .map_vec_into(|arg| ComponentSetItem::resolve(arg, (m, cr, crates)))
+            .map_vec_into(|arg| ComponentSetItem::resolve(arg, items, (m, cr, crates)))
             .combine_results()
             .zip(labels.map_or(Ok(None), |l| {
                 LabelItem::resolve(l, (m, cr, crates)).map(|t| Some(t))
@@ -222,7 +230,8 @@ impl ComponentSet {
                                     .warning(format!(
                                         "Label expression is {} true",
                                         val.map_or("always", "never")
-                                    )),
+                                    ))
+                                    .with_code(ErrorCode::ConstantLabels),
                                 );
                             }
                             _ => (),
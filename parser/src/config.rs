@@ -0,0 +1,200 @@
+use std::{env, fs, path::PathBuf};
+
+use diagnostic::{CatchErr, MessageFormat, ToErr};
+use shared::{
+    error_codes::EXPLAIN_ENV,
+    syn::error::{CriticalResult, StrToError},
+};
+
+use crate::{parse::read_cargo_toml, profile::ProfileMode};
+
+// Directory of the main crate, Cargo doesn't tell the engine's build script which crate depends on it
+// E.g. in the main crate's .cargo/config.toml:
+// [env]
+// HYPERFOLD_GAME_DIR = { value = ".", relative = true }
+pub const GAME_DIR_ENV: &str = "HYPERFOLD_GAME_DIR";
+// Path to write a Graphviz graph of systems to, relative to the main crate directory
+pub const GRAPH_ENV: &str = "HYPERFOLD_GRAPH";
+// "json" writes rustc-style JSON diagnostics to hyperfold_diagnostics.json in OUT_DIR
+// instead of printing rendered text, only the file's path is printed
+pub const MESSAGE_FORMAT_ENV: &str = "HYPERFOLD_MESSAGE_FORMAT";
+// Features enabled in the main crate besides its default features, e.g. "debug,hyperfold_engine/audio"
+// Like HYPERFOLD_GAME_DIR, it must be set for the engine's build script, e.g. in .cargo/config.toml
+pub const FEATURES_ENV: &str = "HYPERFOLD_FEATURES";
+// Any value writes hyperfold_profile.json to OUT_DIR, "warn" also prints a summary
+pub const PROFILE_ENV: &str = "HYPERFOLD_PROFILE";
+// Everything `parse()` reads from the environment, build scripts rerun when these change
+pub const BUILD_ENVS: [&str; 6] = [
+    GAME_DIR_ENV,
+    FEATURES_ENV,
+    GRAPH_ENV,
+    MESSAGE_FORMAT_ENV,
+    PROFILE_ENV,
+    EXPLAIN_ENV,
+];
+// Set by Cargo to the directory of the crate running the build script (the engine)
+const MANIFEST_DIR_ENV: &str = "CARGO_MANIFEST_DIR";
+
+// Name of the engine in the main crate's dependencies
+const ENGINE_CRATE: &str = "hyperfold_engine";
+
+// Defaults, relative to their crate directories
+const MAIN_FILE: &str = "src/main.rs";
+const MACROS_DIR: &str = "macros";
+
+// Locations of the main, engine, and macros crates
+// Can be overridden in the main crate's Cargo.toml:
+// [package.metadata.hyperfold]
+// main = "src/main.rs"
+// engine = "../hyperfold-engine"
+// macros = "../hyperfold-engine/macros"
+// manifest = true  # Write hyperfold_manifest.json next to the codegen
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub entry: PathBuf,
+    pub main: PathBuf,
+    pub engine_dir: PathBuf,
+    pub macros_dir: PathBuf,
+    pub manifest: bool,
+    pub graph: Option<PathBuf>,
+    // Enabled features of the main crate besides its default features
+    pub features: Vec<String>,
+}
+
+impl Config {
+    // Reads the main crate directory and overrides from the build script environment
+    pub fn new() -> CriticalResult<Self> {
+        let entry = env::var(GAME_DIR_ENV).map(PathBuf::from).catch_err(
+            format!(
+                "{GAME_DIR_ENV} is not set, it must be the directory of the game crate. Set it in the game's .cargo/config.toml:\n[env]\n{GAME_DIR_ENV} = {{ value = \".\", relative = true }}"
+            )
+            .error(),
+        )?;
+        let engine_dir = env::var(MANIFEST_DIR_ENV).ok().map(PathBuf::from);
+        let config = Self::load(entry, engine_dir, None)?;
+        let graph = env::var(GRAPH_ENV).ok().map(|file| config.entry.join(file));
+        let features = env::var(FEATURES_ENV).map_or(Vec::new(), |f| parse_features(&f));
+        Ok(Self {
+            graph,
+            features,
+            ..config
+        })
+    }
+
+    // `engine_dir` and `macros_dir` are defaults for paths not set in the main crate's Cargo.toml
+    pub fn load(
+        entry: PathBuf,
+        engine_dir: Option<PathBuf>,
+        macros_dir: Option<PathBuf>,
+    ) -> CriticalResult<Self> {
+        let entry = canonicalize(entry, "main crate")?;
+
+        let cargo_toml = read_cargo_toml(&entry)?;
+        let metadata = cargo_toml
+            .get("package")
+            .and_then(|p| p.get("metadata"))
+            .and_then(|m| m.get("hyperfold"));
+        let get_path = |key: &str| {
+            metadata
+                .and_then(|m| m.get(key))
+                .and_then(|v| v.as_str())
+                .map(|p| entry.join(p))
+        };
+
+        // Explicit path, else the first binary target, else src/main.rs
+        let main = get_path("main")
+            .or_else(|| {
+                cargo_toml
+                    .get("bin")
+                    .and_then(|b| b.as_array())
+                    .and_then(|b| b.first())
+                    .and_then(|b| b.get("path"))
+                    .and_then(|p| p.as_str())
+                    .map(|p| entry.join(p))
+            })
+            .unwrap_or_else(|| entry.join(MAIN_FILE));
+        if !main.is_file() {
+            return format!("Main crate root file does not exist: {}", main.display())
+                .error()
+                .as_err();
+        }
+
+        // Without a default, use the engine's path if it is a path dependency
+        let engine_dir = canonicalize(
+            get_path("engine")
+                .or(engine_dir)
+                .or_else(|| {
+                    cargo_toml
+                        .get("dependencies")
+                        .and_then(|d| d.get(ENGINE_CRATE))
+                        .and_then(|e| e.get("path"))
+                        .and_then(|p| p.as_str())
+                        .map(|p| entry.join(p))
+                })
+                .unwrap_or_else(|| PathBuf::from(".")),
+            "engine crate",
+        )?;
+        let macros_dir = canonicalize(
+            get_path("macros")
+                .or(macros_dir)
+                .unwrap_or_else(|| engine_dir.join(MACROS_DIR)),
+            "macros crate",
+        )?;
+
+        let manifest = metadata
+            .and_then(|m| m.get("manifest"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        Ok(Self {
+            entry,
+            main,
+            engine_dir,
+            macros_dir,
+            manifest,
+            graph: None,
+            features: Vec::new(),
+        })
+    }
+
+    pub fn get_main_file(&self) -> String {
+        self.main.display().to_string()
+    }
+}
+
+// Same format as `cargo build --features`, separated by commas or spaces
+pub fn parse_features(features: &str) -> Vec<String> {
+    features
+        .split([',', ' '])
+        .filter(|f| !f.is_empty())
+        .map(|f| f.to_string())
+        .collect()
+}
+
+// Read separately from `Config` so config errors use the same format
+pub fn get_message_format() -> MessageFormat {
+    match env::var(MESSAGE_FORMAT_ENV).as_deref() {
+        Ok("json") => MessageFormat::Json,
+        _ => MessageFormat::Human,
+    }
+}
+
+// Read before `Config` so parsing the config is timed
+pub fn get_profile_mode() -> Option<ProfileMode> {
+    match env::var(PROFILE_ENV).as_deref() {
+        Ok("warn") => Some(ProfileMode::Warn),
+        Ok("" | "0" | "false") | Err(_) => None,
+        Ok(_) => Some(ProfileMode::Report),
+    }
+}
+
+fn canonicalize(dir: PathBuf, name: &str) -> CriticalResult<PathBuf> {
+    fs::canonicalize(&dir).catch_err(
+        format!(
+            "Could not canonicalize {name} path: '{}' relative to: {:#?}",
+            dir.display(),
+            env::current_dir()
+        )
+        .error(),
+    )
+}
@@ -0,0 +1,504 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use diagnostic::CatchErr;
+use proc_macro2::Span;
+
+use shared::{
+    parsing::SystemMacroArgs,
+    syn::error::{CriticalResult, StrToError},
+    traits::{CollectVec, CollectVecInto},
+};
+
+use crate::{
+    codegen::Crates,
+    component_set::{ComponentSet, ComponentSetLabels, LabelItem},
+    parse::ItemPath,
+    resolve::{ItemData, Items},
+    system::{FnArgType, ItemSystem},
+    utils::paths::Crate,
+};
+
+const INDEX_FILE: &str = "index.md";
+const NONE: &str = "_None_";
+
+#[derive(Copy, Clone)]
+enum Page {
+    Component(usize),
+    Global(usize),
+    Event(usize),
+    State(usize),
+    System(usize),
+}
+
+impl Page {
+    fn dir(&self) -> &'static str {
+        match self {
+            Page::Component(_) => "components",
+            Page::Global(_) => "globals",
+            Page::Event(_) => "events",
+            Page::State(_) => "states",
+            Page::System(_) => "systems",
+        }
+    }
+}
+
+// Systems that use an item, by system index
+#[derive(Default)]
+struct Usage {
+    reads: Vec<usize>,
+    writes: Vec<usize>,
+    // Components that appear in a component set's labels
+    labels: Vec<usize>,
+}
+
+struct Docs<'a> {
+    items: &'a Items,
+    crates: &'a Crates,
+    engine_cr_idx: usize,
+    engine: bool,
+    components: Vec<Usage>,
+    globals: Vec<Usage>,
+    // Systems run by each event
+    triggers: Vec<Vec<usize>>,
+}
+
+// Writes a Markdown page for every component, global, event, state, and system,
+// plus an index linking to them, and returns the number of pages written
+// Items from the engine crate are skipped unless `engine` is set
+pub fn write_docs(
+    items: &Items,
+    crates: &Crates,
+    dir: &Path,
+    engine: bool,
+) -> CriticalResult<usize> {
+    let docs = Docs::new(items, crates, engine);
+    let pages = docs.pages();
+
+    let write = |file: PathBuf, text: String| {
+        fs::write(&file, text).catch_err(format!("Could not write to: {}", file.display()).trace())
+    };
+    for sub_dir in ["components", "globals", "events", "states", "systems"] {
+        let sub_dir = dir.join(sub_dir);
+        fs::create_dir_all(&sub_dir)
+            .catch_err(format!("Could not create directory: {}", sub_dir.display()).trace())?;
+    }
+    for page in &pages {
+        write(
+            dir.join(page.dir()).join(docs.file(*page)),
+            docs.page(*page),
+        )?;
+    }
+    write(dir.join(INDEX_FILE), docs.index(&pages))?;
+    Ok(pages.len() + 1)
+}
+
+impl<'a> Docs<'a> {
+    fn new(items: &'a Items, crates: &'a Crates, engine: bool) -> Self {
+        let mut docs = Self {
+            items,
+            crates,
+            engine_cr_idx: crates.get_crate_index(Crate::Engine),
+            engine,
+            components: items.components.map_vec(|_| Usage::default()),
+            globals: items.globals.map_vec(|_| Usage::default()),
+            triggers: items.events.map_vec(|_| Vec::new()),
+        };
+
+        for (i, sys) in items.systems.iter().enumerate() {
+            for arg in &sys.args {
+                match arg.ty {
+                    FnArgType::Event(e_idx) => {
+                        if let Some(systems) = docs.triggers.get_mut(e_idx) {
+                            systems.push(i);
+                        }
+                    }
+                    FnArgType::Global(g_idx) => {
+                        if let Some(usage) = docs.globals.get_mut(g_idx) {
+                            match arg.is_mut {
+                                true => usage.writes.push(i),
+                                false => usage.reads.push(i),
+                            }
+                        }
+                    }
+                    FnArgType::Entities { idx, .. } => {
+                        let Some(cs) = items.component_sets.get(idx) else {
+                            continue;
+                        };
+                        for cs_arg in &cs.args {
+                            if let Some(usage) = docs.components.get_mut(cs_arg.sym.comp.idx) {
+                                match cs_arg.is_mut {
+                                    true => usage.writes.push(i),
+                                    false => usage.reads.push(i),
+                                }
+                            }
+                        }
+                        if let Some(ComponentSetLabels::Expression(expr)) = &cs.labels {
+                            for sym in expr.iter_symbols() {
+                                if let Some(usage) = docs.components.get_mut(sym.comp.idx) {
+                                    usage.labels.push(i);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // A system may use the same item through several arguments
+        for usage in docs.components.iter_mut().chain(docs.globals.iter_mut()) {
+            for systems in [&mut usage.reads, &mut usage.writes, &mut usage.labels] {
+                systems.dedup();
+            }
+        }
+        for systems in &mut docs.triggers {
+            systems.dedup();
+        }
+
+        docs
+    }
+
+    fn path(&self, page: Page) -> Option<&'a ItemPath> {
+        let items = self.items;
+        match page {
+            Page::Component(i) => items.components.get(i).map(|c| &c.data.path),
+            Page::Global(i) => items.globals.get(i).map(|g| &g.data.path),
+            Page::Event(i) => items.events.get(i).map(|e| &e.data.path),
+            Page::State(i) => items.states.get(i).map(|s| &s.data.path),
+            Page::System(i) => items.systems.get(i).map(|s| &s.path),
+        }
+    }
+
+    fn name(&self, page: Page) -> String {
+        self.path(page)
+            .map_or_else(String::new, |path| self.crates.get_item_name(path))
+    }
+
+    fn is_documented(&self, page: Page) -> bool {
+        self.path(page)
+            .is_some_and(|path| self.engine || path.cr_idx != self.engine_cr_idx)
+    }
+
+    fn pages(&self) -> Vec<Page> {
+        let items = self.items;
+        (0..items.components.len())
+            .map(Page::Component)
+            .chain((0..items.globals.len()).map(Page::Global))
+            .chain((0..items.events.len()).map(Page::Event))
+            .chain((0..items.states.len()).map(Page::State))
+            .chain((0..items.systems.len()).map(Page::System))
+            .filter(|page| self.is_documented(*page))
+            .collect()
+    }
+
+    // Item names are unique within each kind of item
+    fn file(&self, page: Page) -> String {
+        let name = self.name(page).replace("::", ".");
+        format!(
+            "{}.md",
+            name.chars()
+                .map(
+                    |c| match c.is_ascii_alphanumeric() || c == '.' || c == '_' {
+                        true => c,
+                        false => '_',
+                    }
+                )
+                .collect::<String>()
+        )
+    }
+
+    // Links are relative to the item pages, items without a page are not linked
+    fn link(&self, page: Page) -> String {
+        let name = self.name(page);
+        match self.is_documented(page) {
+            true => format!("[`{name}`](../{}/{})", page.dir(), self.file(page)),
+            false => format!("`{name}`"),
+        }
+    }
+
+    // File relative to its crate's directory
+    fn location(&self, cr_idx: usize, m_idx: usize, span: &Span) -> String {
+        let start = span.start();
+        let file = self.crates.get_mod(cr_idx, m_idx).map_or_else(
+            |_| "<unknown>".to_string(),
+            |m| match self.crates.get_crates().get(cr_idx) {
+                Some(cr) => m
+                    .file
+                    .strip_prefix(&cr.dir)
+                    .map_or_else(|_| m.get_file(), |f| format!("{}/{}", cr.name, f.display())),
+                None => m.get_file(),
+            },
+        );
+        format!("{file}:{}", start.line)
+    }
+
+    fn data_location(&self, data: &ItemData) -> String {
+        self.location(data.path.cr_idx, data.mod_idx, &data.span)
+    }
+
+    fn systems_list(&self, systems: &Vec<usize>) -> String {
+        match systems.is_empty() {
+            true => NONE.to_string(),
+            false => systems
+                .map_vec(|i| format!("- {}", self.link(Page::System(*i))))
+                .join("\n"),
+        }
+    }
+
+    fn page(&self, page: Page) -> String {
+        let items = self.items;
+        let (kind, location, docs, sections) = match page {
+            Page::Component(i) => {
+                let c = &items.components[i];
+                let usage = &self.components[i];
+                (
+                    "Component",
+                    self.data_location(&c.data),
+                    &c.data.docs,
+                    [
+                        ("Written by", self.systems_list(&usage.writes)),
+                        ("Read by", self.systems_list(&usage.reads)),
+                        ("Used in labels by", self.systems_list(&usage.labels)),
+                    ]
+                    .to_vec(),
+                )
+            }
+            Page::Global(i) => {
+                let g = &items.globals[i];
+                let usage = &self.globals[i];
+                (
+                    "Global",
+                    self.data_location(&g.data),
+                    &g.data.docs,
+                    [
+                        ("Written by", self.systems_list(&usage.writes)),
+                        ("Read by", self.systems_list(&usage.reads)),
+                    ]
+                    .to_vec(),
+                )
+            }
+            Page::Event(i) => {
+                let e = &items.events[i];
+                let mut sections = Vec::new();
+                if let Some(s) = e.state.and_then(|s_idx| items.states.get(s_idx)) {
+                    sections.push((
+                        "State",
+                        format!(
+                            "Sent when {} {}",
+                            match s.enter_event == i {
+                                true => "entering",
+                                false => "exiting",
+                            },
+                            self.link(Page::State(e.state.unwrap_or_default()))
+                        ),
+                    ));
+                }
+                sections.push(("Triggers", self.systems_list(&self.triggers[i])));
+                ("Event", self.data_location(&e.data), &e.data.docs, sections)
+            }
+            Page::State(i) => {
+                let s = &items.states[i];
+                (
+                    "State",
+                    self.data_location(&s.data),
+                    &s.data.docs,
+                    [
+                        (
+                            "Items",
+                            [
+                                format!("- Data: `{}`", self.crates.get_item_name(&s.data_path)),
+                                format!("- Enter event: {}", self.link(Page::Event(s.enter_event))),
+                                format!("- Exit event: {}", self.link(Page::Event(s.exit_event))),
+                                format!("- Label: {}", self.link(Page::Component(s.label))),
+                            ]
+                            .join("\n"),
+                        ),
+                        (
+                            "Systems run on enter",
+                            self.systems_list(&self.triggers[s.enter_event]),
+                        ),
+                        (
+                            "Systems run on exit",
+                            self.systems_list(&self.triggers[s.exit_event]),
+                        ),
+                    ]
+                    .to_vec(),
+                )
+            }
+            Page::System(i) => {
+                let sys = &items.systems[i];
+                (
+                    "System",
+                    self.location(sys.path.cr_idx, sys.span.m_idx, &sys.span.span),
+                    &sys.docs,
+                    self.system_sections(sys),
+                )
+            }
+        };
+
+        let mut lines = vec![
+            format!("# {kind} `{}`", self.name(page)),
+            String::new(),
+            format!("Defined in `{location}`"),
+        ];
+        if !docs.is_empty() {
+            lines.extend([String::new(), docs.to_string()]);
+        }
+        for (title, text) in sections {
+            lines.extend([String::new(), format!("## {title}"), String::new(), text]);
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    fn system_sections(&self, sys: &ItemSystem) -> Vec<(&'static str, String)> {
+        let mut sections = Vec::new();
+        let trigger = match &sys.attr_args {
+            SystemMacroArgs::Init() => Some("Runs once when the game starts".to_string()),
+            SystemMacroArgs::System { states, .. } => {
+                if !states.is_empty() {
+                    sections.push((
+                        "States",
+                        states
+                            .map_vec(|(path, _)| format!("- `{}`", path.join("::")))
+                            .join("\n"),
+                    ));
+                }
+                sys.args.iter().find_map(|arg| match arg.ty {
+                    FnArgType::Event(e_idx) => {
+                        Some(format!("Runs on {}", self.link(Page::Event(e_idx))))
+                    }
+                    _ => None,
+                })
+            }
+        };
+        sections.insert(0, ("Trigger", trigger.unwrap_or(NONE.to_string())));
+
+        let args = sys.args.filter_map_vec(|arg| match arg.ty {
+            FnArgType::Event(_) => None,
+            FnArgType::Global(g_idx) => Some(format!(
+                "- Global {} ({})",
+                self.link(Page::Global(g_idx)),
+                access(arg.is_mut)
+            )),
+            FnArgType::Entities { idx, is_vec } => self
+                .items
+                .component_sets
+                .get(idx)
+                .map(|cs| self.component_set(cs, is_vec)),
+        });
+        sections.push((
+            "Arguments",
+            match args.is_empty() {
+                true => NONE.to_string(),
+                false => args.join("\n"),
+            },
+        ));
+        sections
+    }
+
+    fn component_set(&self, cs: &ComponentSet, is_vec: bool) -> String {
+        let mut lines = vec![format!(
+            "- {} `{}`",
+            match is_vec {
+                true => "Every entity in",
+                false => "Each entity in",
+            },
+            self.crates.get_item_name(&cs.path)
+        )];
+        lines.extend(cs.args.iter().map(|arg| {
+            format!(
+                "    - `{}`: {} ({}{})",
+                arg.var,
+                self.link(Page::Component(arg.sym.comp.idx)),
+                access(arg.is_mut),
+                if arg.is_opt { ", optional" } else { "" }
+            )
+        }));
+        if let Some(labels) = &cs.labels {
+            lines.push(format!("    - Labels: `{}`", self.labels(labels)));
+        }
+        lines.join("\n")
+    }
+
+    // Label expressions with component names instead of indices
+    fn labels(&self, labels: &ComponentSetLabels) -> String {
+        match labels {
+            ComponentSetLabels::Constant(v) => v.to_string(),
+            ComponentSetLabels::Expression(expr) => match &expr.labels {
+                LabelItem::Expression { op, items, .. } => items
+                    .map_vec(|item| self.label_item(item))
+                    .join(&format!(" {op} ")),
+                item => self.label_item(item),
+            },
+        }
+    }
+
+    fn label_item(&self, item: &LabelItem) -> String {
+        match item {
+            LabelItem::Item { not, sym } => format!(
+                "{}{}",
+                if *not { "!" } else { "" },
+                self.name(Page::Component(sym.comp.idx))
+            ),
+            LabelItem::Expression { op, items, .. } => format!(
+                "({})",
+                items
+                    .map_vec(|item| self.label_item(item))
+                    .join(&format!(" {op} "))
+            ),
+        }
+    }
+
+    fn index(&self, pages: &[Page]) -> String {
+        let mut lines = vec!["# ECS Reference".to_string()];
+        for (title, dir) in [
+            ("Components", "components"),
+            ("Globals", "globals"),
+            ("Events", "events"),
+            ("States", "states"),
+            ("Systems", "systems"),
+        ] {
+            let entries = pages
+                .iter()
+                .filter(|page| page.dir() == dir)
+                .map_vec_into(|page| {
+                    let link = format!("- [`{}`]({dir}/{})", self.name(*page), self.file(*page));
+                    match self.summary(*page) {
+                        Some(summary) => format!("{link}: {summary}"),
+                        None => link,
+                    }
+                });
+            lines.extend([String::new(), format!("## {title}"), String::new()]);
+            match entries.is_empty() {
+                true => lines.push(NONE.to_string()),
+                false => lines.extend(entries),
+            }
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    // First line of the item's doc comment
+    fn summary(&self, page: Page) -> Option<&'a str> {
+        let items = self.items;
+        match page {
+            Page::Component(i) => items.components.get(i).map(|c| &c.data.docs),
+            Page::Global(i) => items.globals.get(i).map(|g| &g.data.docs),
+            Page::Event(i) => items.events.get(i).map(|e| &e.data.docs),
+            Page::State(i) => items.states.get(i).map(|s| &s.data.docs),
+            Page::System(i) => items.systems.get(i).map(|s| &s.docs),
+        }
+        .and_then(|docs| docs.lines().next())
+        .filter(|line| !line.is_empty())
+    }
+}
+
+fn access(is_mut: bool) -> &'static str {
+    match is_mut {
+        true => "mutable",
+        false => "immutable",
+    }
+}
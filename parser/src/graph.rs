@@ -0,0 +1,124 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::PathBuf,
+};
+
+use diagnostic::CatchErr;
+
+use shared::syn::error::{CriticalResult, StrToError};
+
+use crate::{
+    codegen::Crates,
+    parse::ItemPath,
+    resolve::Items,
+    system::{FnArgs, GlobalFnArg},
+};
+
+const INIT_NODE: &str = "init";
+
+// Writes a Graphviz graph of which events trigger each system
+// and which components and globals each system reads or writes
+pub fn write_graph(items: &Items, crates: &Crates, file: &PathBuf) -> CriticalResult<()> {
+    // Node id -> (label, shape)
+    let mut nodes = BTreeMap::new();
+    // Systems can access the same item through several arguments
+    let mut edges = BTreeSet::new();
+
+    // Replace 'crate' with the crate name
+    let get_label = |path: &ItemPath| {
+        let name = crates
+            .get_crates()
+            .get(path.cr_idx)
+            .map_or(String::new(), |cr| cr.name.to_string());
+        [name]
+            .into_iter()
+            .chain(path.path.iter().skip(1).cloned())
+            .collect::<Vec<_>>()
+            .join("::")
+            + &path.generics_string()
+    };
+    let add_node = |nodes: &mut BTreeMap<_, _>, id: String, path: &ItemPath, shape: &str| {
+        nodes.insert(id.to_string(), (get_label(path), shape.to_string()));
+        id
+    };
+
+    let add_access = |edges: &mut BTreeSet<String>, sys: &str, item: String, is_mut: bool| {
+        edges.insert(match is_mut {
+            true => format!("{sys} -> {item} [label=\"writes\"]"),
+            false => format!("{item} -> {sys} [label=\"reads\"]"),
+        });
+    };
+
+    for (i, sys) in items.systems.iter().enumerate() {
+        // Systems with invalid arguments are reported during codegen
+        let Ok(args) = sys.validate(items) else {
+            continue;
+        };
+        let s_id = add_node(&mut nodes, format!("s{i}"), &sys.path, "box");
+
+        let globals = match &args {
+            FnArgs::Init { globals } => {
+                nodes.insert(
+                    INIT_NODE.to_string(),
+                    ("Init".to_string(), "doublecircle".to_string()),
+                );
+                edges.insert(format!("{INIT_NODE} -> {s_id} [label=\"triggered by\"]"));
+                globals
+            }
+            FnArgs::System {
+                event,
+                globals,
+                component_sets,
+            } => {
+                if let Some(e) = items.events.get(event.idx) {
+                    let e_id = add_node(
+                        &mut nodes,
+                        format!("e{}", event.idx),
+                        &e.data.path,
+                        "ellipse",
+                    );
+                    edges.insert(format!("{e_id} -> {s_id} [label=\"triggered by\"]"));
+                }
+                for cs_arg in component_sets {
+                    let Some(cs) = items.component_sets.get(cs_arg.idx) else {
+                        continue;
+                    };
+                    for arg in &cs.args {
+                        let c_idx = arg.sym.comp.idx;
+                        if let Some(c) = items.components.get(c_idx) {
+                            let c_id =
+                                add_node(&mut nodes, format!("c{c_idx}"), &c.data.path, "note");
+                            add_access(&mut edges, &s_id, c_id, arg.is_mut);
+                        }
+                    }
+                }
+                globals
+            }
+        };
+
+        for GlobalFnArg { idx, is_mut, .. } in globals {
+            if let Some(g) = items.globals.get(*idx) {
+                let g_id = add_node(&mut nodes, format!("g{idx}"), &g.data.path, "hexagon");
+                add_access(&mut edges, &s_id, g_id, *is_mut);
+            }
+        }
+    }
+
+    let mut lines = vec![
+        "digraph hyperfold {".to_string(),
+        "  rankdir=LR;".to_string(),
+        "  node [fontname=\"monospace\"];".to_string(),
+    ];
+    lines.extend(nodes.into_iter().map(|(id, (label, shape))| {
+        format!(
+            "  {id} [label=\"{}\", shape={shape}];",
+            label.replace('"', "\\\"")
+        )
+    }));
+    lines.extend(edges.into_iter().map(|e| format!("  {e};")));
+    lines.push("}".to_string());
+
+    fs::write(file, lines.join("\n"))
+        .catch_err(format!("Could not write to: {}", file.display()).trace())
+}
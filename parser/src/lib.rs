@@ -3,53 +3,60 @@
 #![feature(lazy_cell)]
 #![allow(unused)]
 
+mod analysis;
+mod cache;
 mod codegen;
 mod component_set;
+mod config;
+mod docs;
+mod graph;
+mod lint;
+mod list;
+mod manifest;
 mod parse;
+mod profile;
 mod resolve;
 mod system;
+#[cfg(test)]
+mod test;
 mod utils;
 
-use std::{collections::HashSet, fmt::Display, io::Write, path::PathBuf, time::Instant};
+pub use analysis::{parse_crate, Analysis, ParseOptions};
+pub use codegen::{format_code, Crates};
+pub use component_set::ComponentSet;
+pub use config::{parse_features, Config, BUILD_ENVS};
+pub use docs::write_docs;
+pub use list::list_items;
+pub use resolve::{ItemComponent, ItemData, ItemEvent, ItemGlobal, ItemIds, ItemState, Items};
+pub use system::ItemSystem;
+pub use utils::paths::Crate;
 
-use codegen::write_codegen;
-use diagnostic::{Diagnostic, DiagnosticLevel, ErrorSpan, ResultsTrait};
-use resolve::Items;
+use std::{collections::HashSet, env, fs, io::Write, path::PathBuf};
+
+use cache::{hash_items, ParseCache};
+use codegen::{get_codegen_dir, read_codegen, write_codegen};
+use diagnostic::{CatchErr, Diagnostic, DiagnosticLevel, ErrorSpan, MessageFormat, ResultsTrait};
 
 use component_set::ComponentSetLabels;
+use config::{get_message_format, get_profile_mode};
+use graph::write_graph;
+use lint::lint;
+use manifest::write_manifest;
 use parse::{AstCrate, ComponentSymbol};
+use profile::Profiler;
 use shared::{
-    syn::error::{MutateResults, Renderer},
+    error_codes::{ErrorCode, EXPLAIN_ENV},
+    syn::error::{CriticalResult, Error, MutateResults, Renderer, StrToError},
     traits::{Call, CollectVec, CollectVecInto, ExtendInto, GetSlice},
 };
-use utils::paths::Crate;
-
-struct Timer {
-    i: Instant,
-    i2: Instant,
-}
 
-impl Timer {
-    pub fn new() -> Self {
-        Self {
-            i: Instant::now(),
-            i2: Instant::now(),
-        }
-    }
+const DIAGNOSTICS_FILE: &str = "hyperfold_diagnostics.json";
 
-    pub fn step(&mut self, msg: impl Display) {
-        eprintln!("{msg}: {:#?}", self.i2.elapsed());
-        self.i2 = Instant::now();
-    }
-
-    pub fn finish(&self, msg: impl Display) {
-        eprintln!("{msg}: {:#?}", self.i.elapsed());
-    }
+// Long form explanation of an error code, e.g. "HF0001"
+pub fn explain(code: &str) -> Option<&'static str> {
+    ErrorCode::from_code(code).map(|code| code.explanation())
 }
 
-// TODO: no hardcode
-const MAIN: &str = "c:\\Users\\aaore\\repos\\hyperfold-games-library\\src\\main.rs";
-
 // Process:
 // 1) Parse AST, get mod/crate structure, use statements, and important syntax items
 // 2) Populate with macro symbols
@@ -57,24 +64,77 @@ const MAIN: &str = "c:\\Users\\aaore\\repos\\hyperfold-games-library\\src\\main.
 // 4) Parse component sets; Validate labels; insert symbols
 // 5) Parse systems; Validate arguments; insert symbols
 // 6) Codegen
-pub fn parse(entry: PathBuf) {
-    let mut t = Timer::new();
+// The main crate is found through `config::GAME_DIR_ENV`
+pub fn parse() {
+    let t = Profiler::new(get_profile_mode());
+
+    if let Ok(code) = env::var(EXPLAIN_ENV) {
+        match explain(&code) {
+            Some(text) => text
+                .lines()
+                .for_each(|line| println!("cargo:warning={line}")),
+            None => println!("cargo:warning=Unknown error code: {code}"),
+        }
+    }
+
+    let config = Config::new();
+    let main_file = config
+        .as_ref()
+        .map_or_else(|_| String::new(), |config| config.get_main_file());
+    let manifest = config.as_ref().is_ok_and(|config| config.manifest);
+    let graph = config
+        .as_ref()
+        .ok()
+        .and_then(|config| config.graph.to_owned());
+
+    let cache = ParseCache::load();
 
-    let (renderer, errors) = match AstCrate::parse(entry) {
+    let crates = config.and_then(|config| AstCrate::parse(&config, &cache, true));
+    let (renderer, mut errors) = match crates {
         Ok(res) => {
-            t.step("Parsed Crates (Success)");
+            t.step(format!(
+                "Parsed Crates (Success, {} Files Cached)",
+                cache.num_restored()
+            ));
 
             let (mut crates, mut errors) = res.take();
-            let (items, warnings) = Items::resolve(&mut crates).record_errs(&mut errors).take();
+            let (items, mut warnings) = Items::resolve(&mut crates, &t)
+                .record_errs(&mut errors)
+                .take();
             t.step("Resolved Items");
+            t.add_crates(&crates);
+
+            lint(&items, &crates).record_errs(&mut warnings);
+            t.step("Linted Items");
+
+            if manifest {
+                write_manifest(&items, &crates).record_errs(&mut errors);
+                t.step("Wrote Manifest");
+            }
+
+            if let Some(file) = &graph {
+                write_graph(&items, &crates, file).record_errs(&mut errors);
+                t.step("Wrote Graph");
+            }
 
             let macro_cr_idx = crates.get_crate_index(Crate::Macros);
 
-            let code = match codegen::codegen(&mut crates, &items).record_errs(&mut errors) {
+            // Reuse the previous codegen if nothing it depends on has changed
+            let items_hash = hash_items(&items, &crates);
+            let num_crates = crates.iter_except([macro_cr_idx]).count();
+            let code = match cache
+                .is_codegen_current(items_hash)
+                .then(|| read_codegen(num_crates).ok())
+                .flatten()
+                .or_else(|| {
+                    codegen::codegen(&mut crates, &items, &t)
+                        .record_errs(&mut errors)
+                        .map(|code| code.map_vec(format_code))
+                }) {
                 Some(code) => crates
                     .iter_except([macro_cr_idx])
                     .zip(code)
-                    .map_vec_into(|(cr, c)| (cr, c.to_string())),
+                    .map_vec_into(|(cr, c)| (cr, c)),
                 _ => {
                     let engine_cr_idx = crates.get_crate_index(Crate::Engine);
                     crates
@@ -97,44 +157,90 @@ pub fn parse(entry: PathBuf) {
             };
             t.step("Finished Codegen");
 
+            for (cr, code) in &code {
+                t.codegen_file(&cr.name, code);
+            }
+
             write_codegen(code).record_errs(&mut errors);
             t.step("Wrote Codegen");
 
+            if errors.is_empty() {
+                cache.set_codegen(items_hash);
+            }
+
             // Add in warnings
             errors.extend(warnings);
 
-            let mut renderer = Renderer::new(
-                MAIN,
-                crates
-                    .get_main_mod(crates.get_crate_index(Crate::Main))
-                    .map(|m| ErrorSpan::from(&m.span))
-                    .unwrap_or_default(),
-            );
-            for (cr_idx, m_idx) in errors
-                .iter()
-                .fold(HashSet::new(), |mut hs, e| hs.extend_into(e.get_files()))
-            {
-                let (file, span) = crates
-                    .get_mod(cr_idx, m_idx)
-                    .map(|m| (m.get_file(), (&m.span).into()))
-                    .unwrap_or((MAIN.to_string(), ErrorSpan::default()));
-                renderer.add_file(cr_idx, m_idx, file, span);
-            }
-
-            (renderer, errors)
+            (get_renderer(&main_file, &crates, &errors), errors)
         }
         Err(errs) => {
             t.step("Parsed Crates (Failed)");
-            (Renderer::new(MAIN, ErrorSpan::default()), errs)
+            (Renderer::new(&main_file, ErrorSpan::default()), errs)
         }
     };
 
-    let mut i = 0;
-    for err in errors {
-        err.render(&renderer).emit().unwrap();
-        i += 1;
+    cache.save();
+
+    // Written before emitting diagnostics so its errors are reported
+    t.write_report().record_errs(&mut errors);
+
+    let diagnostics = errors.map_vec(|err| err.render(&renderer));
+    match get_message_format() {
+        MessageFormat::Human => diagnostics.iter().for_each(Diagnostic::emit),
+        MessageFormat::Json => match write_diagnostics(&diagnostics) {
+            Ok(file) => {
+                if !diagnostics.is_empty() {
+                    println!(
+                        "cargo:warning=Wrote {} diagnostics to {}",
+                        diagnostics.len(),
+                        file.display()
+                    );
+                }
+            }
+            // Fall back to rendered text so the diagnostics aren't lost
+            Err(errs) => errs
+                .map_vec(|err| err.render(&renderer))
+                .iter()
+                .chain(&diagnostics)
+                .for_each(Diagnostic::emit),
+        },
     }
-    t.step(format!("Emitted {i} Diagnostics"));
+    t.step(format!("Emitted {} Diagnostics", diagnostics.len()));
 
     t.finish("Build Time");
 }
+
+// Writes one JSON diagnostic per line to OUT_DIR, replacing those of the previous build
+fn write_diagnostics(diagnostics: &[Diagnostic]) -> CriticalResult<PathBuf> {
+    let file = get_codegen_dir()?.join(DIAGNOSTICS_FILE);
+    let lines = diagnostics
+        .iter()
+        .map(|d| d.to_json())
+        .collect::<serde_json::Result<Vec<_>>>()
+        .catch_err("Could not serialize diagnostics".trace())?;
+    fs::write(&file, lines.join("\n"))
+        .catch_err(format!("Could not write to: {}", file.display()).trace())?;
+    Ok(file)
+}
+
+// Loads every file referenced by the errors
+fn get_renderer(main_file: &str, crates: &Crates, errors: &[Error]) -> Renderer {
+    let mut renderer = Renderer::new(
+        main_file,
+        crates
+            .get_main_mod(crates.get_crate_index(Crate::Main))
+            .map(|m| ErrorSpan::from(&m.span))
+            .unwrap_or_default(),
+    );
+    for (cr_idx, m_idx) in errors
+        .iter()
+        .fold(HashSet::new(), |mut hs, e| hs.extend_into(e.get_files()))
+    {
+        let (file, span) = crates
+            .get_mod(cr_idx, m_idx)
+            .map(|m| (m.get_file(), (&m.span).into()))
+            .unwrap_or((main_file.to_string(), ErrorSpan::default()));
+        renderer.add_file(cr_idx, m_idx, file, span);
+    }
+    renderer
+}
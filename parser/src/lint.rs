@@ -0,0 +1,212 @@
+use std::collections::HashSet;
+
+use diagnostic::err;
+use proc_macro2::Span;
+
+use shared::{
+    error_codes::ErrorCode,
+    parsing::SystemMacroArgs,
+    syn::error::{StrToError, ToError, WarningResult},
+    traits::GetSlice,
+};
+
+use crate::{
+    codegen::Crates,
+    component_set::ComponentSetLabels,
+    parse::{resolve_path, EngineCallKind, ItemSpan, Symbol, SymbolType},
+    resolve::{ItemData, Items},
+    system::{FnArgType, ItemSystem},
+    utils::paths::Crate,
+};
+
+// Warns about components, events, and systems that are never used
+// Items from the engine are not checked, but usages are collected from every crate
+pub fn lint(items: &Items, crates: &Crates) -> WarningResult<()> {
+    let engine_cr_idx = crates.get_crate_index(Crate::Engine);
+    let is_linted = |data: &ItemData| data.path.cr_idx != engine_cr_idx;
+    let get_span = |data: &ItemData| ItemSpan {
+        span: data.span,
+        m_idx: data.mod_idx,
+        cr_idx: data.path.cr_idx,
+    };
+    let get_name = |data: &ItemData| {
+        data.path
+            .path
+            .last()
+            .map_or(String::new(), |s| s.to_string())
+            + &data.path.generics_string()
+    };
+
+    let (added_components, new_events, unknown_event) = get_engine_calls(items, crates);
+
+    let mut used_components = added_components;
+    for cs in &items.component_sets {
+        used_components.extend(cs.args.iter().map(|arg| arg.sym.comp.idx));
+        if let Some(ComponentSetLabels::Expression(e)) = &cs.labels {
+            used_components.extend(e.iter_symbols().map(|sym| sym.comp.idx));
+        }
+    }
+    used_components.extend(items.states.iter().map(|s| s.label));
+
+    let handled_events = items
+        .systems
+        .iter()
+        .filter_map(|sys| get_event(sys).map(|(i, _)| i))
+        .collect::<HashSet<_>>();
+
+    let mut warnings = Vec::new();
+
+    for (i, c) in items.components.iter().enumerate() {
+        if is_linted(&c.data)
+            && !c.args.is_dummy
+            && !c.args.allow_unused
+            && !used_components.contains(&i)
+        {
+            warnings.push(
+                get_span(&c.data)
+                    .warning(format!("Component '{}' is never used", get_name(&c.data)))
+                    .with_code(ErrorCode::UnusedComponent)
+                    .with_note("Allow this with #[component(AllowUnused)]".note()),
+            );
+        }
+    }
+
+    for (i, e) in items.events.iter().enumerate() {
+        if is_linted(&e.data)
+            && e.state.is_none()
+            && !e.args.allow_unused
+            && !handled_events.contains(&i)
+        {
+            warnings.push(
+                get_span(&e.data)
+                    .warning(format!(
+                        "Event '{}' is never handled by a system",
+                        get_name(&e.data)
+                    ))
+                    .with_code(ErrorCode::UnhandledEvent)
+                    .with_note("Allow this with #[event(AllowUnused)]".note()),
+            );
+        }
+    }
+
+    for sys in &items.systems {
+        if let SystemMacroArgs::System {
+            allow_unreachable: false,
+            ..
+        } = sys.attr_args
+        {
+            let Some((i, span)) = get_event(sys) else {
+                continue;
+            };
+            match items.events.get(i) {
+                Some(e) if is_linted(&e.data) && e.state.is_none() && !new_events.contains(&i) => {
+                    let sys_name = sys.path.path.last().map_or("", |s| s.as_str());
+                    let e_name = get_name(&e.data);
+                    // An event whose type isn't known could be this one
+                    let msg = match unknown_event {
+                        Some(_) => format!(
+                            "System '{sys_name}' may never run, no code is known to send '{e_name}'"
+                        ),
+                        None => {
+                            format!("System '{sys_name}' is never run, no code sends '{e_name}'")
+                        }
+                    };
+                    warnings.push(
+                        ItemSpan { span, ..sys.span }
+                            .warning(msg)
+                            .with_code(ErrorCode::UnreachableSystem)
+                            .with_note(get_span(&e.data).note("Event declared here"))
+                            .with_notes(
+                                unknown_event
+                                    .map(|span| span.note("The type of this event isn't known")),
+                            )
+                            .with_note("Allow this with #[system(AllowUnreachable)]".note()),
+                    )
+                }
+                _ => (),
+            }
+        }
+    }
+
+    err((), warnings)
+}
+
+fn get_event(sys: &ItemSystem) -> Option<(usize, Span)> {
+    sys.args.iter().find_map(|arg| match arg.ty {
+        FnArgType::Event(i) => Some((i, arg.span)),
+        _ => None,
+    })
+}
+
+// Returns the indices of components passed to `add_component` and events passed to `new_event`
+// and the first event passed to `new_event` whose type isn't known
+fn get_engine_calls(
+    items: &Items,
+    crates: &Crates,
+) -> (HashSet<usize>, HashSet<usize>, Option<ItemSpan>) {
+    let mut components = HashSet::new();
+    let mut events = HashSet::new();
+    let mut unknown_event = None;
+
+    let macro_cr_idx = crates.get_crate_index(Crate::Macros);
+    let all_crates = crates.get_crates();
+    for cr in crates.iter_except([macro_cr_idx]) {
+        for m in cr.iter_mods() {
+            for call in &m.items.engine_calls {
+                // Enum variants name their type without the last segment
+                let sym = match call.path.is_empty() {
+                    true => None,
+                    false => [call.path.to_vec(), call.path.slice_to(-1).to_vec()]
+                        .into_iter()
+                        .find_map(|path| {
+                            resolve_path(path, (m, cr, all_crates)).into_result().ok()
+                        }),
+                };
+                // Unresolved paths may be functions returning the event
+                let Some(sym) = sym else {
+                    if call.kind == EngineCallKind::NewEvent && unknown_event.is_none() {
+                        unknown_event = Some(ItemSpan {
+                            span: call.span,
+                            m_idx: m.idx,
+                            cr_idx: cr.idx,
+                        });
+                    }
+                    continue;
+                };
+                match call.kind {
+                    EngineCallKind::AddComponent => {
+                        components.extend(get_instances(sym, items, |kind| match kind {
+                            SymbolType::Component(c_sym) => Some(c_sym.idx),
+                            _ => None,
+                        }))
+                    }
+                    EngineCallKind::NewEvent => {
+                        events.extend(get_instances(sym, items, |kind| match kind {
+                            SymbolType::Event(i) => Some(*i),
+                            _ => None,
+                        }))
+                    }
+                }
+            }
+        }
+    }
+
+    (components, events, unknown_event)
+}
+
+// Generic items are matched to all of their instances
+fn get_instances(
+    sym: &Symbol,
+    items: &Items,
+    get_idx: impl Fn(&SymbolType) -> Option<usize>,
+) -> Vec<usize> {
+    match sym.kind {
+        SymbolType::Generic(g_idx) => items
+            .instances
+            .iter()
+            .filter(|inst| inst.generic == g_idx)
+            .filter_map(|inst| get_idx(&inst.symbol.kind))
+            .collect(),
+        kind => get_idx(&kind).into_iter().collect(),
+    }
+}
@@ -0,0 +1,100 @@
+use shared::{
+    parsing::SystemMacroArgs,
+    traits::{CollectVec, CollectVecInto},
+};
+
+use crate::{
+    codegen::Crates,
+    resolve::{ItemData, Items},
+    system::{FnArgType, ItemSystem},
+    utils::paths::Crate,
+};
+
+// Lists components, events, systems, and states with their locations
+// Items from the engine crate are skipped unless `engine` is set
+pub fn list_items(items: &Items, crates: &Crates, engine: bool) -> String {
+    let engine_cr_idx = crates.get_crate_index(Crate::Engine);
+    let is_listed = |data: &ItemData| engine || data.path.cr_idx != engine_cr_idx;
+    let entry = |data: &ItemData| {
+        (
+            crates.get_item_name(&data.path),
+            crates.get_location(data.path.cr_idx, data.mod_idx, &data.span),
+        )
+    };
+
+    let sections = [
+        (
+            "Components",
+            items
+                .components
+                .iter()
+                .filter(|c| is_listed(&c.data))
+                .map_vec_into(|c| entry(&c.data)),
+        ),
+        (
+            "Events",
+            items
+                .events
+                .iter()
+                .filter(|e| is_listed(&e.data))
+                .map_vec_into(|e| entry(&e.data)),
+        ),
+        (
+            "Systems",
+            items
+                .systems
+                .iter()
+                .filter(|sys| engine || sys.path.cr_idx != engine_cr_idx)
+                .map_vec_into(|sys| system_entry(items, crates, sys)),
+        ),
+        (
+            "States",
+            items
+                .states
+                .iter()
+                .filter(|s| is_listed(&s.data))
+                .map_vec_into(|s| entry(&s.data)),
+        ),
+    ];
+
+    sections
+        .map_vec(|(title, entries)| {
+            let width = entries
+                .iter()
+                .map(|(name, _)| name.len())
+                .max()
+                .unwrap_or(0);
+            [format!("{title} ({}):", entries.len())]
+                .into_iter()
+                .chain(
+                    entries
+                        .iter()
+                        .map(|(name, loc)| format!("    {name:width$}  {loc}")),
+                )
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .join("\n\n")
+}
+
+// Systems are listed with the event that runs them
+fn system_entry(items: &Items, crates: &Crates, sys: &ItemSystem) -> (String, String) {
+    let trigger = match sys.attr_args {
+        SystemMacroArgs::Init() => Some("init".to_string()),
+        SystemMacroArgs::System { .. } => sys.args.iter().find_map(|arg| match arg.ty {
+            FnArgType::Event(i) => items
+                .events
+                .get(i)
+                .map(|e| crates.get_item_name(&e.data.path)),
+            _ => None,
+        }),
+    };
+    let path = crates.get_item_name(&sys.path);
+    (
+        match trigger {
+            Some(trigger) => format!("{path} ({trigger})"),
+            None => path,
+        },
+        crates.get_location(sys.path.cr_idx, sys.span.m_idx, &sys.span.span),
+    )
+}
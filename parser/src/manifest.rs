@@ -0,0 +1,217 @@
+use std::fs;
+
+use diagnostic::CatchErr;
+use proc_macro2::Span;
+use serde::Serialize;
+
+use shared::{
+    parsing::SystemMacroArgs,
+    syn::error::{CriticalResult, StrToError},
+    traits::{CollectVec, CollectVecInto, GetSlice},
+};
+
+use crate::{
+    codegen::{get_codegen_dir, Crates},
+    component_set::ComponentSet,
+    parse::ItemPath,
+    resolve::{ItemData, Items},
+    system::{FnArgType, ItemSystem},
+};
+
+const MANIFEST_FILE: &str = "hyperfold_manifest.json";
+// Increment when the manifest format changes
+const MANIFEST_VERSION: u32 = 1;
+
+// Lists every resolved ECS item for external tools
+// Item indices refer to positions in the corresponding lists
+#[derive(Serialize)]
+struct Manifest {
+    version: u32,
+    crates: Vec<CrateEntry>,
+    components: Vec<ComponentEntry>,
+    globals: Vec<GlobalEntry>,
+    events: Vec<EventEntry>,
+    states: Vec<StateEntry>,
+    component_sets: Vec<ComponentSetEntry>,
+    systems: Vec<SystemEntry>,
+}
+
+#[derive(Serialize)]
+struct CrateEntry {
+    name: String,
+    dir: String,
+}
+
+#[derive(Serialize)]
+struct Location {
+    #[serde(rename = "crate")]
+    cr_idx: usize,
+    path: String,
+    file: Option<String>,
+    // 1-based line, 0-based column
+    line: usize,
+    column: usize,
+}
+
+#[derive(Serialize)]
+struct ComponentEntry {
+    #[serde(flatten)]
+    loc: Location,
+    singleton: bool,
+    dummy: bool,
+}
+
+#[derive(Serialize)]
+struct GlobalEntry {
+    #[serde(flatten)]
+    loc: Location,
+    #[serde(rename = "const")]
+    is_const: bool,
+    dummy: bool,
+}
+
+#[derive(Serialize)]
+struct EventEntry {
+    #[serde(flatten)]
+    loc: Location,
+    state: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct StateEntry {
+    #[serde(flatten)]
+    loc: Location,
+    data: String,
+    enter_event: usize,
+    exit_event: usize,
+    label: usize,
+}
+
+#[derive(Serialize)]
+struct ComponentSetArg {
+    var: String,
+    component: usize,
+    refs: usize,
+    mutable: bool,
+    optional: bool,
+}
+
+#[derive(Serialize)]
+struct ComponentSetEntry {
+    #[serde(flatten)]
+    loc: Location,
+    args: Vec<ComponentSetArg>,
+    labels: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SystemArg {
+    kind: String,
+    index: usize,
+    refs: usize,
+    mutable: bool,
+}
+
+#[derive(Serialize)]
+struct SystemEntry {
+    #[serde(flatten)]
+    loc: Location,
+    init: bool,
+    states: Vec<String>,
+    args: Vec<SystemArg>,
+}
+
+fn location(crates: &Crates, path: &ItemPath, m_idx: Option<usize>, span: &Span) -> Location {
+    let m = match m_idx {
+        Some(m_idx) => crates.get_mod(path.cr_idx, m_idx).ok(),
+        None => crates
+            .get_crates()
+            .get(path.cr_idx)
+            .and_then(|cr| cr.find_mod(path.path.slice_to(-1)).ok()),
+    };
+    let start = span.start();
+    Location {
+        cr_idx: path.cr_idx,
+        path: path.to_string(),
+        file: m.map(|m| m.get_file()),
+        line: start.line,
+        column: start.column,
+    }
+}
+
+fn data_location(crates: &Crates, data: &ItemData) -> Location {
+    location(crates, &data.path, Some(data.mod_idx), &data.span)
+}
+
+fn component_set(crates: &Crates, cs: &ComponentSet) -> ComponentSetEntry {
+    ComponentSetEntry {
+        loc: location(crates, &cs.path, None, &cs.span),
+        args: cs.args.map_vec(|arg| ComponentSetArg {
+            var: arg.var.to_string(),
+            component: arg.sym.comp.idx,
+            refs: arg.ref_cnt,
+            mutable: arg.is_mut,
+            optional: arg.is_opt,
+        }),
+        labels: cs.labels.as_ref().map(|l| l.to_string()),
+    }
+}
+
+fn system(crates: &Crates, sys: &ItemSystem) -> SystemEntry {
+    let (init, states) = match &sys.attr_args {
+        SystemMacroArgs::Init() => (true, Vec::new()),
+        SystemMacroArgs::System { states, .. } => (false, states.map_vec(|(p, _)| p.join("::"))),
+    };
+    SystemEntry {
+        loc: location(crates, &sys.path, Some(sys.span.m_idx), &sys.span.span),
+        init,
+        states,
+        args: sys.args.map_vec(|arg| SystemArg {
+            kind: arg.ty.to_string(),
+            index: match arg.ty {
+                FnArgType::Event(i) | FnArgType::Global(i) => i,
+                FnArgType::Entities { idx, .. } => idx,
+            },
+            refs: arg.ref_cnt,
+            mutable: arg.is_mut,
+        }),
+    }
+}
+
+pub fn write_manifest(items: &Items, crates: &Crates) -> CriticalResult<()> {
+    let manifest = Manifest {
+        version: MANIFEST_VERSION,
+        crates: crates.iter().map_vec_into(|cr| CrateEntry {
+            name: cr.name.to_string(),
+            dir: cr.dir.display().to_string(),
+        }),
+        components: items.components.map_vec(|c| ComponentEntry {
+            loc: data_location(crates, &c.data),
+            singleton: c.args.is_singleton,
+            dummy: c.args.is_dummy,
+        }),
+        globals: items.globals.map_vec(|g| GlobalEntry {
+            loc: data_location(crates, &g.data),
+            is_const: g.args.is_const,
+            dummy: g.args.is_dummy,
+        }),
+        events: items.events.map_vec(|e| EventEntry {
+            loc: data_location(crates, &e.data),
+            state: e.state,
+        }),
+        states: items.states.map_vec(|s| StateEntry {
+            loc: data_location(crates, &s.data),
+            data: s.data_path.to_string(),
+            enter_event: s.enter_event,
+            exit_event: s.exit_event,
+            label: s.label,
+        }),
+        component_sets: items.component_sets.map_vec(|cs| component_set(crates, cs)),
+        systems: items.systems.map_vec(|s| system(crates, s)),
+    };
+
+    let file = get_codegen_dir()?.join(MANIFEST_FILE);
+    let data = serde_json::to_string_pretty(&manifest)
+        .catch_err("Could not serialize manifest".trace())?;
+    fs::write(&file, data).catch_err(format!("Could not write to: {}", file.display()).trace())
+}
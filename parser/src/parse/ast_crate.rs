@@ -8,10 +8,12 @@ use std::{
     path::PathBuf,
 };
 
-use diagnostic::{err, CatchErr, CombineWarnings, ErrForEach, ErrorTrait, ToErr};
+use diagnostic::{err, CatchErr, CombineWarnings, ErrForEach, ErrorTrait, ResultsTrait, ToErr};
+use proc_macro2::Span;
 use shared::{
     syn::error::{
-        CriticalResult, GetVec, MutateResults, Result, SpanTrait, StrToError, WarningResult,
+        CriticalResult, Error, GetVec, MutateResults, Result, SpanTrait, StrToError, ToError,
+        WarningResult,
     },
     traits::{Call, Catch, CollectVec, CollectVecInto, ExpandEnum, GetSlice, PushInto},
 };
@@ -19,10 +21,16 @@ use shared::{
 use super::{
     ast_file::DirType,
     ast_mod::{AstMod, AstModType},
-    AstItems, HardcodedSymbol, NewMod, Symbol, SymbolType,
+    cargo_metadata::read_cargo_metadata,
+    cargo_toml::{read_path_dependencies, resolve_features, CargoToml},
+    cfg::CfgEnv,
+    find_path::STD_CRATES,
+    AstItems, AstUse, HardcodedSymbol, NewMod, Symbol, SymbolType, Visibility,
 };
 use crate::{
+    cache::ParseCache,
     codegen::Crates,
+    config::Config,
     parse::ItemPath,
     utils::{
         constants::NAMESPACE,
@@ -31,24 +39,6 @@ use crate::{
     },
 };
 
-// TODO: hardcoded
-const ENGINE: &str = ".";
-const MACROS: &str = "macros";
-
-fn get_engine_dir() -> CriticalResult<PathBuf> {
-    fs::canonicalize(PathBuf::from(ENGINE)).catch_err(
-        format!(
-            "Could not canonicalize engine crate path relative to: {:#?}",
-            env::current_dir()
-        )
-        .trace(),
-    )
-}
-
-fn get_macros_dir() -> CriticalResult<PathBuf> {
-    Ok(get_engine_dir()?.join("macros"))
-}
-
 #[derive(Debug)]
 pub struct AstCrate {
     pub idx: usize,
@@ -60,26 +50,28 @@ pub struct AstCrate {
 }
 
 impl AstCrate {
-    pub fn new(dir: PathBuf, idx: usize, is_entry: bool) -> Result<Self> {
-        let rel_dir = dir.to_owned();
-        let dir: PathBuf = fs::canonicalize(dir)
-            .catch_err(format!("Could not canonicalize path: {}", rel_dir.display()).trace())?;
-
+    // If given, `root` is the crate's main file, otherwise it is a library
+    pub fn new(
+        dir: PathBuf,
+        idx: usize,
+        root: Option<&PathBuf>,
+        cfg: &CfgEnv,
+        cache: &ParseCache,
+    ) -> Result<Self> {
         let mut errs = Vec::new();
-        let mods = AstMod::parse_dir(
-            dir.join("src"),
-            &vec!["crate".to_string()],
-            if is_entry {
-                DirType::Main
-            } else {
-                DirType::Lib
-            },
-        )?
+        let mod_path = vec!["crate".to_string()];
+        let mods = match root {
+            Some(file) => {
+                AstMod::parse_file(file.to_owned(), &mod_path, AstModType::Main, cfg, cache)
+            }
+            None => AstMod::parse_dir(dir.join("src"), &mod_path, DirType::Lib, cfg, cache),
+        }?
         .flatten()
         .enumer_map_vec_into(|(i, (WarningResult { mut value, errors }, idxs))| {
             errs.extend(errors.into_iter().map(|e| e.with_mod(idx, i)));
             value.mods = idxs;
             value.idx = i;
+            value.add_type_alias_symbols(idx);
             value
         });
         let main = mods.len() - 1;
@@ -89,7 +81,7 @@ impl AstCrate {
                 idx,
                 name: dir
                     .file_name()
-                    .catch_err(format!("Could not parse file name: {}", rel_dir.display()).trace())?
+                    .catch_err(format!("Could not parse file name: {}", dir.display()).trace())?
                     .to_string_lossy()
                     .to_string(),
                 dir: dir.to_owned(),
@@ -101,38 +93,67 @@ impl AstCrate {
         ))
     }
 
-    pub fn parse(mut dir: PathBuf) -> Result<Crates> {
-        let engine_dir = get_engine_dir()?;
-        let macros_dir = get_macros_dir()?;
+    pub fn parse(config: &Config, cache: &ParseCache, cargo_metadata: bool) -> Result<Crates> {
+        let engine_dir = &config.engine_dir;
+        let macros_dir = &config.macros_dir;
 
-        let mut crates = vec![AstCrate::new(dir.to_owned(), 0, true)?];
+        // Find all crates before parsing so features can be resolved
+        // Without cargo metadata, only path dependencies are found
+        let mut metadata_errs = Vec::new();
+        let metadata = match cargo_metadata {
+            true => read_cargo_metadata(&config.entry, engine_dir, macros_dir)?
+                .record_errs(&mut metadata_errs),
+            false => None,
+        };
+        let mut manifests = match metadata {
+            Some(manifests) => manifests,
+            None => read_path_dependencies(&config.entry, engine_dir, macros_dir)?,
+        };
+        let mut deps = Vec::new();
         let mut i = 0;
-        while i < crates.len() {
-            let cr_dir = crates.try_get(i)?.value.dir.to_owned();
-            let (deps, new_deps) = Self::get_crate_dependencies(cr_dir.to_owned(), &crates)?;
-            {
-                crates.try_get_mut(i)?.value.deps = deps.into_iter().collect();
-            }
-            for path in new_deps {
-                crates.push(AstCrate::new(cr_dir.join(path), crates.len(), false)?);
-            }
+        while i < manifests.len() {
+            let cr_deps = Self::get_crate_dependencies(&mut manifests, i)?;
+            deps.push(cr_deps);
             i += 1;
         }
-        let crates = crates.combine_results();
+        let features = resolve_features(&manifests, &deps, &config.features);
+        let cfg = CfgEnv::from_env();
+
+        let mut crates = Vec::new();
+        for ((m, cr_deps), features) in manifests.into_iter().zip(deps).zip(features) {
+            let idx = crates.len();
+            let mut cr = AstCrate::new(
+                m.dir,
+                idx,
+                (idx == 0).then_some(&config.main),
+                &cfg.with_features(features),
+                cache,
+            )?;
+            cr.value.deps = cr_deps
+                .into_iter()
+                .zip(m.deps)
+                .map(|(i, dep)| (i, dep.name.replace('-', "_")))
+                .collect();
+            let mut errs = cr.value.resolve_uses();
+            cr.errors.append(&mut errs);
+            crates.push(cr);
+        }
+        let mut crates = crates.combine_results();
+        crates.errors.extend(metadata_errs);
 
         let mut crate_idxs = [0; Crate::LEN];
         crate_idxs[Crate::Main as usize] = 0;
         crate_idxs[Crate::Engine as usize] = crates
             .value
             .iter()
-            .find_map(|cr| (cr.dir == engine_dir).then_some(cr.idx))
+            .find_map(|cr| (&cr.dir == engine_dir).then_some(cr.idx))
             .catch_err(
                 format!("Could not find engine crate: '{}'", engine_dir.display()).trace(),
             )?;
         crate_idxs[Crate::Macros as usize] = crates
             .value
             .iter()
-            .find_map(|cr| (cr.dir == macros_dir).then_some(cr.idx))
+            .find_map(|cr| (&cr.dir == macros_dir).then_some(cr.idx))
             .catch_err(
                 format!("Could not find macros crate: '{}'", macros_dir.display()).trace(),
             )?;
@@ -140,15 +161,19 @@ impl AstCrate {
         crates.try_map(|crates| Crates::new(crates, crate_idxs))
     }
 
+    // Returns the crate index of each dependency, adding new crates to the list
     fn get_crate_dependencies(
-        cr_dir: PathBuf,
-        crates: &Vec<WarningResult<AstCrate>>,
-    ) -> CriticalResult<(Vec<(usize, String)>, Vec<String>)> {
-        let deps = AstCrate::parse_cargo_toml(cr_dir.to_owned())?;
-        let mut new_deps = Vec::new();
+        manifests: &mut Vec<CargoToml>,
+        cr_idx: usize,
+    ) -> CriticalResult<Vec<usize>> {
+        let cr_dir = manifests.try_get(cr_idx)?.dir.to_owned();
+        let paths = manifests
+            .try_get(cr_idx)?
+            .deps
+            .map_vec(|dep| (dep.name.to_string(), dep.path.to_string()));
         let mut cr_deps = Vec::new();
-        for (name, path) in deps {
-            let dep_dir = fs::canonicalize(cr_dir.join(path.to_string())).catch_err(
+        for (name, path) in paths {
+            let dep_dir = fs::canonicalize(cr_dir.join(&path)).catch_err(
                 format!(
                     "Could not canonicalize dependency path: {}: {}/{}",
                     name,
@@ -157,51 +182,15 @@ impl AstCrate {
                 )
                 .trace(),
             )?;
-            cr_deps.push(match crates.iter().position(|cr| cr.value.dir == dep_dir) {
-                Some(i) => (i, name),
+            cr_deps.push(match manifests.iter().position(|m| m.dir == dep_dir) {
+                Some(i) => i,
                 None => {
-                    new_deps.push(path);
-                    (crates.len() + new_deps.len() - 1, name)
+                    manifests.push(CargoToml::parse(dep_dir)?);
+                    manifests.len() - 1
                 }
             });
         }
-        Ok((cr_deps, new_deps))
-    }
-
-    fn parse_cargo_toml(dir: PathBuf) -> CriticalResult<HashMap<String, String>> {
-        // Get the path to the `Cargo.toml` file
-        let cargo_toml_path = dir.join("Cargo.toml");
-
-        // Read the `Cargo.toml` file into a string
-        let mut cargo_toml = String::new();
-        let mut file = File::open(&cargo_toml_path).catch_err(
-            format!("Could not open Cargo.toml: {}", cargo_toml_path.display()).trace(),
-        )?;
-        file.read_to_string(&mut cargo_toml).catch_err(
-            format!("Could not read Cargo.toml: {}", cargo_toml_path.display()).trace(),
-        )?;
-
-        // Parse the `Cargo.toml` file as TOML
-        let cargo_toml = cargo_toml.parse::<toml::Value>().catch_err(
-            format!("Could not parse Cargo.toml: {}", cargo_toml_path.display()).trace(),
-        )?;
-
-        // Extract the list of dependencies from the `Cargo.toml` file
-        let deps = cargo_toml
-            .get("dependencies")
-            .catch_err("Could not find 'dependencies' section in Cargo.toml".trace())?
-            .as_table()
-            .catch_err("Could not convert 'dependencies' section to a table".trace())?;
-        Ok(deps
-            .into_iter()
-            .filter_map(|(k, v)| match v {
-                toml::Value::Table(t) => match (t.get("path"), t.get("dependency")) {
-                    (Some(toml::Value::String(p)), Some(_)) => Some((k.to_string(), p.to_string())),
-                    _ => None,
-                },
-                _ => None,
-            })
-            .collect())
+        Ok(cr_deps)
     }
 
     // Insert things into crates
@@ -215,12 +204,125 @@ impl AstCrate {
         crates.get_crate_mut(path.cr)?.add_symbol(Symbol {
             kind: SymbolType::Hardcoded(sym),
             path: path.full_path(),
-            public: true,
+            vis: Visibility::Public,
         });
         Ok(())
     }
 }
 
+// Bounds chains of imports, e.g. use a::b; use b::c;
+const MAX_USE_DEPTH: usize = 16;
+
+// Use paths
+impl AstCrate {
+    // Makes every use path absolute, requires dependencies to be set
+    pub fn resolve_uses(&mut self) -> Vec<Error> {
+        let mut errs = Vec::new();
+        let paths = self.mods.map_vec(|m| {
+            m.uses.map_vec(|u| {
+                self.resolve_use_path(&u.path, m, u.span, Some(u), MAX_USE_DEPTH)
+                    .map_err(|e| e.with_mod(self.idx, m.idx))
+                    .record_errs(&mut errs)
+                    .flatten()
+                    .unwrap_or_else(|| u.path.to_vec())
+            })
+        });
+        for (m, paths) in self.iter_mods_mut().zip(paths) {
+            for (u, path) in m.uses.iter_mut().zip(paths) {
+                u.path = path;
+            }
+        }
+        errs
+    }
+
+    // Makes a path in `m` absolute, the result starts with 'crate' or the name of a crate
+    // Paths may start with '::', a crate, a child mod, or a name imported by a use
+    // Returns None if the path doesn't start with any of these, e.g. it names an item in `m`
+    pub fn resolve_path(
+        &self,
+        path: &[String],
+        m: &AstMod,
+        span: Span,
+    ) -> CriticalResult<Option<Vec<String>>> {
+        self.resolve_use_path(path, m, span, None, MAX_USE_DEPTH)
+    }
+
+    // `u` is the use being resolved, which can't import itself
+    fn resolve_use_path(
+        &self,
+        path: &[String],
+        m: &AstMod,
+        span: Span,
+        u: Option<&AstUse>,
+        depth: usize,
+    ) -> CriticalResult<Option<Vec<String>>> {
+        let first = match path.first() {
+            Some(first) => first,
+            None => return Ok(None),
+        };
+        if depth == 0 {
+            return span
+                .error(format!(
+                    "Could not resolve '{first}', imports may be cyclic"
+                ))
+                .as_err();
+        }
+        // '::' always names a crate
+        if first.is_empty() {
+            return match path.get(1) {
+                Some(name) if name == "crate" => span
+                    .error("'crate' can only start a path, remove the leading '::'")
+                    .as_err(),
+                _ => Ok(Some(path[1..].to_vec())),
+            };
+        }
+        if first == "crate" {
+            return Ok(Some(path.to_vec()));
+        }
+
+        let is_crate =
+            STD_CRATES.contains(&first.as_str()) || self.deps.values().any(|alias| alias == first);
+        let child = self
+            .get_mods(&m.mods)?
+            .into_iter()
+            .find(|m| &m.name == first);
+        // Imports are searched by pointer so a use can't match itself
+        let import = m.uses.iter().find(|other| {
+            !u.is_some_and(|u| std::ptr::eq(*other, u))
+                    && &other.ident == first
+                    // E.g. use foo; names the crate
+                    && other.path.iter().filter(|s| !s.is_empty()).ne([first])
+        });
+
+        let kind = match (child, import) {
+            (Some(_), _) => "module",
+            (None, Some(_)) => "import",
+            (None, None) => return Ok(is_crate.then(|| path.to_vec())),
+        };
+        if is_crate {
+            return span
+                .error(format!(
+                    "'{first}' is ambiguous, use '::{first}' for the crate or 'self::{first}' for the local {kind}"
+                ))
+                .as_err();
+        }
+
+        match (child, import) {
+            (Some(child), _) => Ok(Some([child.path.to_vec(), path[1..].to_vec()].concat())),
+            (None, Some(import)) => self
+                .resolve_use_path(
+                    &[import.path.to_vec(), path[1..].to_vec()].concat(),
+                    m,
+                    import.span,
+                    Some(import),
+                    depth - 1,
+                )
+                .map(|p| p.or_else(|| Some([import.path.to_vec(), path[1..].to_vec()].concat()))),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
 // Mod access
 impl AstCrate {
     pub fn get_main_mod(&self) -> CriticalResult<&AstMod> {
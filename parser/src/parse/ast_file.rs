@@ -1,14 +1,18 @@
 use std::{fs, path::PathBuf};
 
-use diagnostic::ToErr;
+use diagnostic::{CatchErr, ToErr};
 use shared::syn::error::{CriticalResult, StrToError};
 use syn::visit::Visit;
 
 use super::{
     ast_mod::{AstModTree, AstModType},
+    cfg::CfgEnv,
     AstMod,
 };
-use crate::utils::{constants::NAMESPACE, tree::Tree};
+use crate::{
+    cache::ParseCache,
+    utils::{constants::NAMESPACE, tree::Tree},
+};
 
 #[derive(Debug)]
 pub enum DirType {
@@ -39,19 +43,29 @@ impl From<DirType> for AstModType {
 
 // Pass 1: parsing
 impl AstMod {
-    pub fn parse_mod(path: PathBuf, mod_path: &Vec<String>) -> CriticalResult<AstModTree> {
-        if path.is_dir() {
-            Self::parse_dir(path, mod_path, DirType::Mod)
+    // `dir` is the directory containing the declaring mod's child modules
+    pub fn parse_mod(
+        dir: PathBuf,
+        mod_path: &Vec<String>,
+        cfg: &CfgEnv,
+        cache: &ParseCache,
+    ) -> CriticalResult<AstModTree> {
+        let name = mod_path
+            .last()
+            .catch_err(format!("Mod path is empty: {}", mod_path.join("::")).trace())?;
+        let file = dir.join(format!("{name}.rs"));
+        if file.is_file() {
+            Self::parse_file(file, mod_path, AstModType::File, cfg, cache)
+        } else if dir.join(name).join(DirType::Mod.to_file()).is_file() {
+            Self::parse_dir(dir.join(name), mod_path, DirType::Mod, cfg, cache)
         } else {
-            let mut f_path = path.to_owned();
-            f_path.set_extension("rs");
-            if f_path.is_file() {
-                Self::parse_file(f_path, mod_path, AstModType::File)
-            } else {
-                format!("File does not exist: {}", f_path.display())
-                    .trace()
-                    .as_err()
-            }
+            format!(
+                "File does not exist: {} or {}",
+                file.display(),
+                dir.join(name).join(DirType::Mod.to_file()).display()
+            )
+            .trace()
+            .as_err()
         }
     }
 
@@ -59,15 +73,25 @@ impl AstMod {
         path: PathBuf,
         mod_path: &Vec<String>,
         ty: AstModType,
+        cfg: &CfgEnv,
+        cache: &ParseCache,
     ) -> CriticalResult<AstModTree> {
-        Self::parse(path, mod_path.to_vec(), ty)
+        Self::parse(path, mod_path.to_vec(), ty, cfg, cache)
     }
 
     pub fn parse_dir(
         path: PathBuf,
         mod_path: &Vec<String>,
         ty: DirType,
+        cfg: &CfgEnv,
+        cache: &ParseCache,
     ) -> CriticalResult<AstModTree> {
-        Self::parse_file(path.join(ty.to_file()), mod_path, AstModType::from(ty))
+        Self::parse_file(
+            path.join(ty.to_file()),
+            mod_path,
+            AstModType::from(ty),
+            cfg,
+            cache,
+        )
     }
 }
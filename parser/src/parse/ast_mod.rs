@@ -1,15 +1,19 @@
 use std::{
     fs,
     path::{Display, PathBuf},
+    time::{Duration, Instant},
 };
 
 use diagnostic::{err, ok, CatchErr, ErrForEach, ErrorSpan, ErrorTrait, ResultsTrait, ToErr};
 use proc_macro2::{Span, TokenStream};
+use serde::{Deserialize, Serialize};
 
 use syn::{spanned::Spanned, visit::Visit};
 
 use crate::{
+    cache::{parse_file, ModKey, ParseCache},
     parse::attributes::{get_attributes_if_active, Attribute, EcsAttribute},
+    parse::cfg::CfgEnv,
     parse::ItemPath,
     utils::{
         paths::{CratePath, ENGINE_PATHS, MACRO_PATHS},
@@ -20,7 +24,7 @@ use crate::{
 use super::{
     attributes::AstAttribute,
     items::{AstItems, AstUse},
-    AstItemData, Symbol,
+    AstItemData, Symbol, Visibility,
 };
 
 use shared::{
@@ -35,7 +39,7 @@ use shared::{
 };
 
 // Type of module
-#[derive(Debug)]
+#[derive(Copy, Clone, Debug, Hash)]
 pub enum AstModType {
     Main,
     Lib,
@@ -61,15 +65,41 @@ pub struct AstMod {
     pub path: Vec<String>,
     pub name: String,
     pub span: Span,
+    // Set by the parent's mod declaration
+    pub vis: Visibility,
     pub mods: Vec<usize>,
     pub uses: Vec<AstUse>,
     pub symbols: Vec<Symbol>,
     pub items: AstItems,
+    // Time spent reading and parsing the file, zero for inline mods
+    pub parse_time: Duration,
 }
 
 pub type AstModTree = Tree<WarningResult<AstMod>>;
 
-// TODO: ignore private mods to avoid name collisions
+// Where the file of a `mod name;` declaration is
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ModSource {
+    // Set with #[path = "..."]
+    File(PathBuf),
+    // Directory containing `name.rs` or `name/mod.rs`
+    Dir(PathBuf),
+}
+
+// Mods declared in a file, files of declared mods are parsed after the declaring file
+pub enum ModDecl {
+    Inline {
+        m: Box<WarningResult<AstMod>>,
+        mods: Vec<ModDecl>,
+    },
+    File {
+        path: Vec<String>,
+        source: ModSource,
+        vis: Visibility,
+        span: Span,
+    },
+}
+
 // Pass 1: parsing
 impl AstMod {
     pub fn new(
@@ -87,44 +117,115 @@ impl AstMod {
             name,
             path,
             span,
+            vis: Visibility::Public,
             mods: Vec::new(),
             uses: Vec::new(),
             symbols: Vec::new(),
             items: AstItems::new(),
+            parse_time: Duration::ZERO,
         }
     }
 
-    pub fn parse(file: PathBuf, path: Vec<String>, ty: AstModType) -> CriticalResult<AstModTree> {
+    pub fn parse(
+        file: PathBuf,
+        path: Vec<String>,
+        ty: AstModType,
+        cfg: &CfgEnv,
+        cache: &ParseCache,
+    ) -> CriticalResult<AstModTree> {
+        let start = Instant::now();
         let file_contents = fs::read_to_string(file.to_owned())
             .catch_map_err(|e| format!("Failed to read file {}: {e}", file.display()).trace())?;
-        let ast = syn::parse_file(&file_contents).catch_map_err(|e| {
-            format!("Failed to parse file contents of {}: {e}", file.display()).trace()
-        })?;
         let name = path
             .last()
             .catch_err(format!("Mod path is empty: {}", path.join("::")).trace())?
             .to_string();
 
-        let mut root = Self::new(0, file, path, name, ty, ast.span());
-
-        let mods = root.visit_items(ast.items);
-        // Resolve local use paths
-        // E.g. mod Foo; use Foo::Bar;
-        for use_path in root.uses.iter_mut() {
-            if let Some(m) = mods.value.iter().find(|m| {
-                m.root
-                    .value
-                    .path
-                    .last()
-                    .is_some_and(|p| p == &use_path.first)
-            }) {
-                use_path.path = [m.root.value.path.to_vec(), use_path.path[1..].to_vec()].concat();
-            }
-        }
+        // Child modules of mod.rs and crate roots are in the same directory
+        let dir = match ty {
+            AstModType::File => file.with_extension(""),
+            _ => file.parent().map_or(PathBuf::new(), |p| p.to_path_buf()),
+        };
 
+        let key = ModKey {
+            file: &file,
+            contents: &file_contents,
+            path: &path,
+            ty,
+            cfg,
+        };
+        let (mut root, mods) = match cache.get_mod(&key) {
+            Some((root, mods)) => (root, ok(mods)),
+            None => match parse_file(&file_contents) {
+                Ok((ast, tokens)) => {
+                    let mut root =
+                        Self::new(0, file.to_owned(), path.to_vec(), name, ty, ast.span());
+                    let mods = root.visit_items(ast.items, &dir, cfg);
+                    // Files with errors are parsed again so their errors are reported
+                    if mods.errors.is_empty() {
+                        cache.insert_mod(&key, tokens, &root, &mods.value);
+                    }
+                    (root, mods)
+                }
+                // The mod is left empty so the rest of the crate can still be checked
+                Err(e) => {
+                    return Ok(Tree {
+                        root: err(
+                            Self::new(0, file, path, name, ty, Span::call_site()),
+                            e.into_iter().map_vec_into(|e| {
+                                e.to_string()
+                                    .error()
+                                    .with_span(ErrorSpan::from_file_contents(
+                                        &file_contents,
+                                        &e.span(),
+                                    ))
+                            }),
+                        ),
+                        children: Vec::new(),
+                    })
+                }
+            },
+        };
+        root.parse_time = start.elapsed();
+
+        let mut errors = mods.errors;
+        let children = Self::parse_mod_decls(mods.value, cfg, cache).record_errs(&mut errors);
         Ok(Tree {
-            root: err(root, mods.errors),
-            children: mods.value,
+            root: err(root, errors),
+            children,
+        })
+    }
+
+    // Parses the files of declared mods, errors belong to the declaring mod
+    fn parse_mod_decls(
+        mods: Vec<ModDecl>,
+        cfg: &CfgEnv,
+        cache: &ParseCache,
+    ) -> WarningResult<Vec<AstModTree>> {
+        mods.try_for_each(|m| match m {
+            ModDecl::Inline { m, mods } => {
+                let m = *m;
+                let mut errors = m.errors;
+                let children = Self::parse_mod_decls(mods, cfg, cache).record_errs(&mut errors);
+                Ok(Tree {
+                    root: err(m.value, errors),
+                    children,
+                })
+            }
+            ModDecl::File {
+                path,
+                source,
+                vis,
+                span,
+            } => match source {
+                ModSource::File(file) => Self::parse_file(file, &path, AstModType::Mod, cfg, cache),
+                ModSource::Dir(dir) => Self::parse_mod(dir, &path, cfg, cache),
+            }
+            .with_span(&span)
+            .map(|mut tree| {
+                tree.root.value.vis = vis;
+                tree
+            }),
         })
     }
 
@@ -162,53 +263,86 @@ impl AstMod {
 
 // File/items
 impl AstMod {
-    fn visit_items(&mut self, items: Vec<syn::Item>) -> WarningResult<Vec<AstModTree>> {
+    // `dir` is the directory containing file modules declared in this mod
+    fn visit_items(
+        &mut self,
+        items: Vec<syn::Item>,
+        dir: &PathBuf,
+        cfg: &CfgEnv,
+    ) -> WarningResult<Vec<ModDecl>> {
         let mut mods = Vec::new();
         items
             .try_for_each(|i| match i {
-                syn::Item::Use(i) => self.visit_item_use(i),
-                syn::Item::Fn(i) => self.visit_item_fn(i),
-                syn::Item::Enum(i) => self.visit_item_enum(i),
-                syn::Item::Struct(i) => self.visit_item_struct(i),
-                syn::Item::Macro(i) => self.visit_item_macro(i),
-                syn::Item::Mod(i) => self.visit_item_mod(i).map(|tree| mods.push(tree)),
+                syn::Item::Use(i) => self.visit_item_use(i, cfg),
+                syn::Item::Fn(i) => self.visit_item_fn(i, cfg),
+                syn::Item::Impl(i) => self.visit_item_impl(i, cfg),
+                syn::Item::Enum(i) => self.visit_item_enum(i, cfg),
+                syn::Item::Struct(i) => self.visit_item_struct(i, cfg),
+                syn::Item::Macro(i) => self.visit_item_macro(i, cfg),
+                syn::Item::Type(i) => self.visit_item_type(i, cfg),
+                syn::Item::Mod(i) => self.visit_item_mod(i, dir, cfg).map(|m| mods.extend(m)),
                 _ => Ok(()),
             })
             .map(|_| mods)
     }
 
     // Mod
-    fn visit_item_mod(&mut self, i: syn::ItemMod) -> CriticalResult<AstModTree> {
-        if let Some(attrs) = get_attributes_if_active(&i.attrs, &self.path, &Vec::new())? {
-            match i.content {
-                // Parse inner mod
-                Some((_, items)) => {
-                    let mut new_mod = Self::new(
-                        0,
-                        self.file.to_owned(),
-                        [self.path.to_vec(), vec![i.ident.to_string()]].concat(),
-                        i.ident.to_string(),
-                        AstModType::Internal,
-                        self.span,
-                    );
-                    let children = new_mod.visit_items(items);
-                    Ok(Tree {
-                        children: children.value,
-                        root: err(new_mod, children.errors),
-                    })
+    fn visit_item_mod(
+        &mut self,
+        i: syn::ItemMod,
+        dir: &PathBuf,
+        cfg: &CfgEnv,
+    ) -> CriticalResult<Option<ModDecl>> {
+        let Some(attrs) = get_attributes_if_active(&i.attrs, &self.path, cfg)? else {
+            return Ok(None);
+        };
+        let path = [self.path.to_vec(), vec![i.ident.to_string()]].concat();
+        // #[path = "..."]
+        let file_path = attrs
+            .iter()
+            .find(|attr| attr.path == ["path"])
+            .map(|attr| {
+                syn::parse2::<syn::LitStr>(attr.args.clone())
+                    .catch_err(attr.span.error("Expected a file path"))
+                    .map(|lit| PathBuf::from(lit.value()))
+            })
+            .transpose()?;
+        let vis = Visibility::from_syn(&i.vis, &self.path);
+        Ok(Some(match i.content {
+            // Parse inner mod
+            Some((_, items)) => {
+                let mut new_mod = Self::new(
+                    0,
+                    self.file.to_owned(),
+                    path,
+                    i.ident.to_string(),
+                    AstModType::Internal,
+                    // Spans are relative to the file
+                    self.span,
+                );
+                new_mod.vis = vis;
+                let dir = dir.join(file_path.unwrap_or(PathBuf::from(i.ident.to_string())));
+                let mods = new_mod.visit_items(items, &dir, cfg);
+                ModDecl::Inline {
+                    m: Box::new(err(new_mod, mods.errors)),
+                    mods: mods.value,
                 }
-                // Parse file mod
-                None => Self::parse_mod(
-                    self.file
-                        .to_owned()
-                        .pop_into()
-                        .push_into(i.ident.to_string()),
-                    &[self.path.to_vec(), vec![i.ident.to_string()]].concat(),
-                )
-                .with_span(&i.ident),
             }
-        } else {
-            Err(Vec::new())
-        }
+            // Parse file mod
+            None => ModDecl::File {
+                path,
+                source: match file_path {
+                    // Relative to this file unless declared in an inline mod
+                    // The file owns its directory like mod.rs
+                    Some(file_path) => ModSource::File(match self.ty {
+                        AstModType::Internal => dir.join(file_path),
+                        _ => self.file.to_owned().pop_into().join(file_path),
+                    }),
+                    None => ModSource::Dir(dir.to_owned()),
+                },
+                vis,
+                span: i.ident.span(),
+            },
+        }))
     }
 }
@@ -1,15 +1,16 @@
-use diagnostic::{CatchErr, ErrorTrait, ResultsTrait};
+use diagnostic::{CatchErr, ErrorTrait, ResultsTrait, ToErr};
 use parse_cfg::Cfg;
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
-use syn::spanned::Spanned;
+use syn::{punctuated::Punctuated, spanned::Spanned, Token};
 
+use super::cfg::CfgEnv;
 use shared::{
     syn::{
         error::{CriticalResult, ToError},
-        use_path_from_vec,
+        use_path_from_syn,
     },
-    traits::NoneOr,
+    traits::{CollectVec, NoneOr},
 };
 
 #[derive(Clone, Debug)]
@@ -44,12 +45,14 @@ impl EcsAttribute {
 pub enum Attribute {
     Ecs(AstAttribute),
     Cfg(Cfg),
+    CfgAttr(Cfg, Vec<Attribute>),
 }
 
 impl Attribute {
     fn from(attr: Vec<String>, span: Span) -> Self {
         match attr.join("::").as_str() {
             "cfg" => Self::Cfg(Cfg::Is(String::new())),
+            "cfg_attr" => Self::CfgAttr(Cfg::Is(String::new()), Vec::new()),
             s => Self::Ecs(AstAttribute {
                 path: attr,
                 args: quote!(),
@@ -62,24 +65,39 @@ impl Attribute {
 pub fn get_attributes_if_active(
     attrs: &Vec<syn::Attribute>,
     path: &Vec<String>,
-    features: &Vec<String>,
+    cfg: &CfgEnv,
 ) -> CriticalResult<Option<Vec<AstAttribute>>> {
     let mut is_active = true;
-    let new_attrs =
-        get_attributes(attrs, path)?
-            .into_iter()
-            .fold(Vec::new(), |mut new_attrs, a| {
-                match a {
-                    Attribute::Ecs(attr) => new_attrs.push(attr),
-                    Attribute::Cfg(cfg) => {
-                        is_active = eval_cfg_args(&cfg, features).is_none_or_into(|b| b)
-                    }
-                }
-                new_attrs
-            });
+    let mut new_attrs = Vec::new();
+    add_active_attributes(
+        get_attributes(attrs, path)?,
+        cfg,
+        &mut is_active,
+        &mut new_attrs,
+    );
     Ok(is_active.then_some(new_attrs))
 }
 
+// Expands cfg_attr and evaluates cfgs
+fn add_active_attributes(
+    attrs: Vec<Attribute>,
+    cfg: &CfgEnv,
+    is_active: &mut bool,
+    new_attrs: &mut Vec<AstAttribute>,
+) {
+    for a in attrs {
+        match a {
+            Attribute::Ecs(attr) => new_attrs.push(attr),
+            Attribute::Cfg(c) => *is_active &= eval_cfg_args(&c, cfg).is_none_or_into(|b| b),
+            Attribute::CfgAttr(c, attrs) => {
+                if eval_cfg_args(&c, cfg).is_none_or_into(|b| b) {
+                    add_active_attributes(attrs, cfg, is_active, new_attrs)
+                }
+            }
+        }
+    }
+}
+
 // Returns list of parsed attributes from ast attributes
 pub fn get_attributes(
     attrs: &Vec<syn::Attribute>,
@@ -88,61 +106,51 @@ pub fn get_attributes(
     let mut new_attrs = Vec::new();
     let mut errs = Vec::new();
     for a in attrs {
-        if let Some(attr) = parse_attr_args(
-            Attribute::from(
-                use_path_from_vec(
-                    path,
-                    &a.path()
-                        .segments
-                        .iter()
-                        .map(|s| s.ident.to_string())
-                        .collect(),
-                ),
-                a.span(),
-            ),
-            a,
-        )
-        .record_errs(&mut errs)
-        {
+        if let Some(attr) = parse_meta(&a.meta, path).record_errs(&mut errs) {
             new_attrs.push(attr)
         }
     }
     errs.err_or(new_attrs)
 }
 
+fn parse_meta(meta: &syn::Meta, path: &Vec<String>) -> CriticalResult<Attribute> {
+    parse_attr_args(
+        Attribute::from(use_path_from_syn(path, meta.path()), meta.span()),
+        meta,
+        path,
+    )
+}
+
 // Check cfg args to make sure we are valid
-pub fn eval_cfg_args(cfg: &Cfg, features: &Vec<String>) -> Option<bool> {
+// Returns None if the cfg can't be evaluated
+pub fn eval_cfg_args(cfg: &Cfg, env: &CfgEnv) -> Option<bool> {
     match cfg {
-        Cfg::Any(cfgs) => Some(
-            cfgs.iter()
-                .map(|cfg| eval_cfg_args(&cfg, &features).is_some_and(|b| b))
-                .collect::<Vec<_>>()
-                .contains(&true),
-        ),
-        Cfg::All(cfgs) => Some(
-            !cfgs
-                .iter()
-                .map(|cfg| eval_cfg_args(&cfg, &features).is_none_or_into(|b| b))
-                .collect::<Vec<_>>()
-                .contains(&false),
-        ),
-        Cfg::Not(cfg) => eval_cfg_args(cfg, &features).map(|b| !b),
-        Cfg::Equal(k, v) => {
-            if k == "feature" {
-                Some(features.contains(&v))
-            } else {
-                None
-            }
-        }
-        Cfg::Is(_) => None,
+        // A known true/false short-circuits any unknown predicates
+        Cfg::Any(cfgs) => eval_cfg_list(cfgs, env, true),
+        Cfg::All(cfgs) => eval_cfg_list(cfgs, env, false),
+        Cfg::Not(cfg) => eval_cfg_args(cfg, env).map(|b| !b),
+        Cfg::Equal(k, v) => env.eval_value(k, v),
+        Cfg::Is(k) => env.eval_name(k),
+    }
+}
+
+fn eval_cfg_list(cfgs: &[Cfg], env: &CfgEnv, short_circuit: bool) -> Option<bool> {
+    let vals = cfgs.map_vec(|cfg| eval_cfg_args(cfg, env));
+    match vals.contains(&Some(short_circuit)) {
+        true => Some(short_circuit),
+        false => vals.iter().all(|b| b.is_some()).then_some(!short_circuit),
     }
 }
 
 // Parses arguments to a single ast attribute
 // This is the only function that can produce Err
-fn parse_attr_args(mut attr_type: Attribute, attr: &syn::Attribute) -> CriticalResult<Attribute> {
+fn parse_attr_args(
+    mut attr_type: Attribute,
+    meta: &syn::Meta,
+    path: &Vec<String>,
+) -> CriticalResult<Attribute> {
     match &mut attr_type {
-        Attribute::Ecs(ast_attr) => match &attr.meta {
+        Attribute::Ecs(ast_attr) => match meta {
             syn::Meta::List(l) => {
                 for t in l.to_token_stream() {
                     match t {
@@ -153,18 +161,69 @@ fn parse_attr_args(mut attr_type: Attribute, attr: &syn::Attribute) -> CriticalR
                     }
                 }
             }
+            // E.g. #[path = "file.rs"]
+            syn::Meta::NameValue(nv) => ast_attr.args = nv.value.to_token_stream(),
             _ => (),
         },
-        Attribute::Cfg(cfg) => match &attr.meta {
-            syn::Meta::List(l) => {
-                *cfg = l
-                    .to_token_stream()
-                    .to_string()
-                    .parse()
-                    .catch_err(l.error("Could not parse cfg_str"))?;
+        Attribute::Cfg(cfg) => {
+            if let syn::Meta::List(l) = meta {
+                *cfg = parse_cfg(
+                    &l.parse_args()
+                        .catch_err(l.error("Could not parse cfg predicate"))?,
+                )?
             }
-            _ => (),
-        },
+        }
+        // First argument is the cfg, the rest are attributes
+        Attribute::CfgAttr(cfg, attrs) => {
+            if let syn::Meta::List(l) = meta {
+                let args = l
+                    .parse_args_with(Punctuated::<syn::Meta, Token![,]>::parse_terminated)
+                    .catch_err(l.error("Could not parse cfg_attr arguments"))?;
+                let mut args = args.iter();
+                let pred = args
+                    .next()
+                    .catch_err(l.error("Missing cfg_attr predicate"))?;
+                *cfg = parse_cfg(pred)?;
+                for meta in args {
+                    attrs.push(parse_meta(meta, path)?);
+                }
+            }
+        }
     };
     Ok(attr_type)
 }
+
+// Builds a cfg predicate from its syntax tree, e.g. `all(unix, not(feature = "a"))`
+fn parse_cfg(meta: &syn::Meta) -> CriticalResult<Cfg> {
+    match meta {
+        syn::Meta::Path(p) => p
+            .get_ident()
+            .map(|i| Cfg::Is(i.to_string()))
+            .catch_err(p.error("Expected cfg name")),
+        syn::Meta::NameValue(nv) => match (nv.path.get_ident(), &nv.value) {
+            (
+                Some(i),
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(s),
+                    ..
+                }),
+            ) => Ok(Cfg::Equal(i.to_string(), s.value())),
+            _ => nv.error("Expected `name = \"value\"` in cfg").as_err(),
+        },
+        syn::Meta::List(l) => {
+            let mut args = l
+                .parse_args_with(Punctuated::<syn::Meta, Token![,]>::parse_terminated)
+                .catch_err(l.error("Could not parse cfg predicate"))?
+                .iter()
+                .map(parse_cfg)
+                .collect::<CriticalResult<Vec<_>>>()?;
+            match l.path.get_ident().map(|i| i.to_string()).as_deref() {
+                Some("all") => Ok(Cfg::All(args)),
+                Some("any") => Ok(Cfg::Any(args)),
+                Some("not") if args.len() == 1 => Ok(Cfg::Not(Box::new(args.remove(0)))),
+                Some("not") => l.error("`not` expects exactly one predicate").as_err(),
+                _ => l.error("Unknown cfg operator").as_err(),
+            }
+        }
+    }
+}
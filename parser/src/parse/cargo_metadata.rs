@@ -0,0 +1,363 @@
+use std::{
+    collections::{HashMap, HashSet},
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use diagnostic::{err, ok, CatchErr};
+use serde::Deserialize;
+use shared::{
+    syn::error::{CriticalResult, Result, StrToError},
+    traits::{CollectVec, NoneOr},
+};
+
+use super::cargo_toml::{CargoDep, CargoToml};
+
+// Set by Cargo for build scripts
+const CARGO_ENV: &str = "CARGO";
+const TARGET_ENV: &str = "TARGET";
+const OFFLINE_ENV: &str = "CARGO_NET_OFFLINE";
+
+// Subset of the `cargo metadata` output
+#[derive(Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+    resolve: Option<Resolve>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    id: String,
+    manifest_path: PathBuf,
+    dependencies: Vec<Dependency>,
+    features: HashMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct Dependency {
+    name: String,
+    rename: Option<String>,
+    kind: Option<String>,
+    features: Vec<String>,
+    uses_default_features: bool,
+    #[serde(default)]
+    optional: bool,
+}
+
+impl Dependency {
+    // Name used in Cargo.toml and feature strings
+    fn key(&self) -> &str {
+        self.rename.as_ref().unwrap_or(&self.name)
+    }
+}
+
+#[derive(Deserialize)]
+struct Resolve {
+    nodes: Vec<Node>,
+}
+
+#[derive(Deserialize)]
+struct Node {
+    id: String,
+    deps: Vec<NodeDep>,
+}
+
+#[derive(Deserialize)]
+struct NodeDep {
+    // Crate name as used in code, includes renames
+    name: String,
+    pkg: String,
+    dep_kinds: Vec<DepKind>,
+}
+
+#[derive(Deserialize)]
+struct DepKind {
+    kind: Option<String>,
+}
+
+impl NodeDep {
+    fn is_normal(&self) -> bool {
+        self.dep_kinds.iter().any(|k| k.kind.is_none())
+    }
+}
+
+// Reads the resolved dependency graph using `cargo metadata`
+// Returns None with a warning if cargo can't be run or fails, e.g. when offline without downloaded dependencies
+// An existing Cargo.lock is used as is, otherwise cargo metadata could update it
+pub fn read_cargo_metadata(
+    entry: &Path,
+    engine_dir: &Path,
+    macros_dir: &Path,
+) -> Result<Option<Vec<CargoToml>>> {
+    let cargo = env::var(CARGO_ENV).unwrap_or("cargo".to_string());
+    let mut cmd = Command::new(&cargo);
+    cmd.args(["metadata", "--format-version", "1", "--manifest-path"])
+        .arg(entry.join("Cargo.toml"));
+    if let Ok(target) = env::var(TARGET_ENV) {
+        cmd.args(["--filter-platform", &target]);
+    }
+    if env::var(OFFLINE_ENV).is_ok_and(|v| v == "true") {
+        cmd.arg("--offline");
+    }
+    if find_lockfile(&cargo, entry).is_some() {
+        cmd.arg("--locked");
+    }
+    let output = match cmd.output() {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            return Ok(err(
+                None,
+                vec![format!(
+                    "cargo metadata failed, only path dependencies will be found:\n{}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )
+                .warning()],
+            ))
+        }
+        Err(e) => {
+            return Ok(err(
+                None,
+                vec![format!(
+                    "Could not run cargo metadata, only path dependencies will be found: {e}"
+                )
+                .warning()],
+            ))
+        }
+    };
+
+    let metadata: Metadata = serde_json::from_slice(&output.stdout)
+        .catch_err("Could not parse cargo metadata output".trace())?;
+    get_engine_manifests(metadata, entry, engine_dir, macros_dir).map(|m| ok(Some(m)))
+}
+
+// The lockfile is in the workspace root, which may be above the main crate
+fn find_lockfile(cargo: &str, entry: &Path) -> Option<PathBuf> {
+    let output = Command::new(cargo)
+        .args([
+            "locate-project",
+            "--workspace",
+            "--message-format",
+            "plain",
+            "--manifest-path",
+        ])
+        .arg(entry.join("Cargo.toml"))
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let manifest = PathBuf::from(String::from_utf8(output.stdout).ok()?.trim());
+    Some(manifest.parent()?.join("Cargo.lock")).filter(|lockfile| lockfile.is_file())
+}
+
+// Engine crates are the engine and macros crates and any crate that depends on the engine
+// The main crate is first, followed by the engine crates it uses in breadth first order
+fn get_engine_manifests(
+    metadata: Metadata,
+    entry: &Path,
+    engine_dir: &Path,
+    macros_dir: &Path,
+) -> CriticalResult<Vec<CargoToml>> {
+    let nodes = metadata
+        .resolve
+        .catch_err("cargo metadata did not resolve dependencies".trace())?
+        .nodes;
+    let packages = metadata
+        .packages
+        .iter()
+        .map(|p| (p.id.as_str(), p))
+        .collect::<HashMap<_, _>>();
+    let dirs = metadata
+        .packages
+        .iter()
+        .filter_map(|p| {
+            p.manifest_path
+                .parent()
+                .and_then(|dir| fs::canonicalize(dir).ok())
+                .map(|dir| (p.id.as_str(), dir))
+        })
+        .collect::<HashMap<_, _>>();
+    let deps = nodes
+        .iter()
+        .map(|n| {
+            (
+                n.id.as_str(),
+                n.deps.iter().filter(|d| d.is_normal()).collect::<Vec<_>>(),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+
+    // Find every crate that depends on the engine
+    let mut engine_crates = dirs
+        .iter()
+        .filter_map(|(id, dir)| (dir == engine_dir || dir == macros_dir).then_some(*id))
+        .collect::<HashSet<_>>();
+    loop {
+        let new_crates = deps
+            .iter()
+            .filter(|(id, deps)| {
+                !engine_crates.contains(*id)
+                    && deps.iter().any(|d| engine_crates.contains(d.pkg.as_str()))
+            })
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        if new_crates.is_empty() {
+            break;
+        }
+        engine_crates.extend(new_crates);
+    }
+
+    let root = dirs
+        .iter()
+        .find_map(|(id, dir)| (dir == entry).then_some(*id))
+        .catch_err(format!("Could not find main crate package: {}", entry.display()).trace())?;
+
+    let mut order = vec![root];
+    let mut manifests = Vec::new();
+    let mut i = 0;
+    while let Some(id) = order.get(i).copied() {
+        let package = packages
+            .get(id)
+            .catch_err(format!("Unknown package: {id}").trace())?;
+        let dir = dirs
+            .get(id)
+            .catch_err(format!("Could not find directory of package: {id}").trace())?;
+
+        let mut cr_deps = Vec::new();
+        for dep in deps.get(id).map_or(&Vec::new(), |d| d) {
+            let dep_id = dep.pkg.as_str();
+            if !engine_crates.contains(dep_id) {
+                continue;
+            }
+            let dep_dir = dirs
+                .get(dep_id)
+                .catch_err(format!("Could not find directory of package: {dep_id}").trace())?;
+            let decl = package
+                .dependencies
+                .iter()
+                .find(|d| d.kind.is_none() && d.key().replace('-', "_") == dep.name);
+            cr_deps.push(CargoDep {
+                name: decl.map_or(dep.name.to_string(), |d| d.key().to_string()),
+                path: dep_dir.display().to_string(),
+                features: decl.map_or(Vec::new(), |d| d.features.to_vec()),
+                default_features: decl.is_none_or_into(|d| d.uses_default_features),
+                optional: decl.is_some_and(|d| d.optional),
+            });
+            if !order.contains(&dep_id) {
+                order.push(dep_id);
+            }
+        }
+
+        manifests.push(CargoToml {
+            dir: dir.to_owned(),
+            deps: cr_deps,
+            features: package.features.clone(),
+        });
+        i += 1;
+    }
+
+    Ok(manifests)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    use serde_json::json;
+
+    use super::{get_engine_manifests, Metadata};
+
+    fn dir(path: &str) -> PathBuf {
+        fs::canonicalize(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(path)).unwrap()
+    }
+
+    fn package(id: &str, dir: &Path, dependencies: serde_json::Value) -> serde_json::Value {
+        json!({
+            "id": id,
+            "manifest_path": dir.join("Cargo.toml"),
+            "dependencies": dependencies,
+            "features": {},
+        })
+    }
+
+    fn dependency(name: &str, rename: Option<&str>, kind: Option<&str>) -> serde_json::Value {
+        json!({
+            "name": name,
+            "rename": rename,
+            "kind": kind,
+            "features": ["debug"],
+            "uses_default_features": kind.is_none(),
+        })
+    }
+
+    fn node(id: &str, deps: &[(&str, &str, Option<&str>)]) -> serde_json::Value {
+        json!({
+            "id": id,
+            "deps": deps.iter().map(|(name, pkg, kind)| json!({
+                "name": name,
+                "pkg": pkg,
+                "dep_kinds": [{ "kind": kind }],
+            })).collect::<Vec<_>>(),
+        })
+    }
+
+    #[test]
+    fn renamed_engine() {
+        let (entry, engine_dir, macros_dir) =
+            (dir("tests/fixtures/renamed"), dir(".."), dir("../macros"));
+        // The engine is renamed to `hyper-fold`, which is `hyper_fold` in code
+        let metadata: Metadata = serde_json::from_value(json!({
+            "packages": [
+                package("renamed", &entry, json!([
+                    dependency("hyperfold_engine", Some("hyper-fold"), None),
+                    dependency("hyperfold_engine", Some("dev-engine"), Some("dev")),
+                ])),
+                package("engine", &engine_dir, json!([
+                    dependency("macros", None, None),
+                    dependency("shared", None, None),
+                ])),
+                package("macros", &macros_dir, json!([])),
+                package("shared", &dir("../shared"), json!([])),
+            ],
+            "resolve": {
+                "nodes": [
+                    node("renamed", &[
+                        ("hyper_fold", "engine", None),
+                        ("dev_engine", "engine", Some("dev")),
+                    ]),
+                    node("engine", &[("macros", "macros", None), ("shared", "shared", None)]),
+                    node("macros", &[]),
+                    node("shared", &[]),
+                ],
+            },
+        }))
+        .unwrap();
+
+        let manifests = get_engine_manifests(metadata, &entry, &engine_dir, &macros_dir)
+            .unwrap_or_else(|_| panic!("Failed to read metadata"));
+        assert_eq!(
+            manifests
+                .iter()
+                .map(|m| m.dir.to_owned())
+                .collect::<Vec<_>>(),
+            vec![entry, engine_dir.to_owned(), macros_dir.to_owned()]
+        );
+
+        let main_deps = &manifests[0].deps;
+        assert_eq!(main_deps.len(), 1);
+        assert_eq!(main_deps[0].name, "hyper-fold");
+        assert_eq!(main_deps[0].path, engine_dir.display().to_string());
+        assert_eq!(main_deps[0].features, vec!["debug".to_string()]);
+        assert!(main_deps[0].default_features);
+        assert_eq!(
+            manifests[1]
+                .deps
+                .iter()
+                .map(|d| d.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["macros"]
+        );
+    }
+}
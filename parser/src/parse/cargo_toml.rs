@@ -0,0 +1,568 @@
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    fs::{self, File},
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use diagnostic::CatchErr;
+use shared::{
+    syn::error::{CriticalResult, StrToError},
+    traits::{CollectVec, CollectVecInto},
+};
+
+const DEFAULT_FEATURE: &str = "default";
+
+pub fn read_cargo_toml(dir: &Path) -> CriticalResult<toml::Value> {
+    // Get the path to the `Cargo.toml` file
+    let cargo_toml_path = dir.join("Cargo.toml");
+
+    // Read the `Cargo.toml` file into a string
+    let mut cargo_toml = String::new();
+    let mut file = File::open(&cargo_toml_path)
+        .catch_err(format!("Could not open Cargo.toml: {}", cargo_toml_path.display()).trace())?;
+    file.read_to_string(&mut cargo_toml)
+        .catch_err(format!("Could not read Cargo.toml: {}", cargo_toml_path.display()).trace())?;
+
+    // Parse the `Cargo.toml` file as TOML
+    cargo_toml
+        .parse::<toml::Value>()
+        .catch_err(format!("Could not parse Cargo.toml: {}", cargo_toml_path.display()).trace())
+}
+
+// Dependency on another engine crate
+#[derive(Debug)]
+pub struct CargoDep {
+    pub name: String,
+    pub path: String,
+    pub features: Vec<String>,
+    pub default_features: bool,
+    // Only enabled by a feature of the crate
+    pub optional: bool,
+}
+
+#[derive(Debug)]
+pub struct CargoToml {
+    pub dir: PathBuf,
+    pub deps: Vec<CargoDep>,
+    pub features: HashMap<String, Vec<String>>,
+}
+
+impl CargoToml {
+    pub fn parse(dir: PathBuf) -> CriticalResult<Self> {
+        let rel_dir = dir.to_owned();
+        let dir: PathBuf = fs::canonicalize(dir)
+            .catch_err(format!("Could not canonicalize path: {}", rel_dir.display()).trace())?;
+
+        let cargo_toml = read_cargo_toml(&dir)?;
+
+        // Extract the list of path dependencies from the `Cargo.toml` file
+        let deps = cargo_toml
+            .get("dependencies")
+            .map(|deps| {
+                deps.as_table()
+                    .catch_err("Could not convert 'dependencies' section to a table".trace())
+            })
+            .transpose()?
+            .into_iter()
+            .flatten()
+            .filter_map(|(k, v)| match v {
+                toml::Value::Table(t) => match t.get("path") {
+                    Some(toml::Value::String(p)) => Some(CargoDep {
+                        name: k.to_string(),
+                        path: p.to_string(),
+                        features: get_str_array(t.get("features")),
+                        default_features: t
+                            .get("default-features")
+                            .or_else(|| t.get("default_features"))
+                            .and_then(|b| b.as_bool())
+                            .unwrap_or(true),
+                        optional: t.get("optional").and_then(|b| b.as_bool()).unwrap_or(false),
+                    }),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+
+        // Extract the features and what they enable
+        let features = cargo_toml
+            .get("features")
+            .and_then(|f| f.as_table())
+            .map(|f| {
+                f.iter()
+                    .map(|(k, v)| (k.to_string(), get_str_array(Some(v))))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Self {
+            dir,
+            deps,
+            features,
+        })
+    }
+}
+
+// Finds engine crates by following path dependencies, used when cargo metadata isn't available
+// Like `read_cargo_metadata`, the main crate is first, followed by its engine crates in breadth first order
+// Dependencies from registries or git are never found
+pub fn read_path_dependencies(
+    entry: &Path,
+    engine_dir: &Path,
+    macros_dir: &Path,
+) -> CriticalResult<Vec<CargoToml>> {
+    // Parse every crate reachable through path dependencies
+    let mut manifests = vec![CargoToml::parse(entry.to_path_buf())?];
+    let mut deps = Vec::new();
+    while let Some(m) = manifests.get(deps.len()) {
+        let dep_dirs = m
+            .deps
+            .iter()
+            .map(|dep| {
+                fs::canonicalize(m.dir.join(&dep.path)).catch_err(
+                    format!(
+                        "Could not canonicalize dependency path: {}: {}/{}",
+                        dep.name,
+                        m.dir.display(),
+                        dep.path
+                    )
+                    .trace(),
+                )
+            })
+            .collect::<CriticalResult<Vec<_>>>()?;
+        let mut cr_deps = Vec::new();
+        for dep_dir in dep_dirs {
+            cr_deps.push(match manifests.iter().position(|m| m.dir == dep_dir) {
+                Some(i) => i,
+                None => {
+                    manifests.push(CargoToml::parse(dep_dir)?);
+                    manifests.len() - 1
+                }
+            });
+        }
+        deps.push(cr_deps);
+    }
+
+    // Find every crate that depends on the engine
+    let mut is_engine = manifests.map_vec(|m| m.dir == engine_dir || m.dir == macros_dir);
+    loop {
+        let new_crates = (0..manifests.len())
+            .filter(|i| !is_engine[*i] && deps[*i].iter().any(|d| is_engine[*d]))
+            .collect::<Vec<_>>();
+        if new_crates.is_empty() {
+            break;
+        }
+        new_crates.into_iter().for_each(|i| is_engine[i] = true);
+    }
+
+    // Keep only engine crates reachable from the main crate
+    let mut order = vec![0];
+    let mut i = 0;
+    while let Some(cr_idx) = order.get(i).copied() {
+        for dep_idx in &deps[cr_idx] {
+            if is_engine[*dep_idx] && !order.contains(dep_idx) {
+                order.push(*dep_idx);
+            }
+        }
+        i += 1;
+    }
+    let dirs = manifests.map_vec(|m| m.dir.to_owned());
+    let mut manifests = manifests.into_iter().map(Some).collect::<Vec<_>>();
+    order
+        .into_iter()
+        .map(|cr_idx| {
+            let mut m = manifests[cr_idx]
+                .take()
+                .catch_err(format!("Crate visited twice: {}", dirs[cr_idx].display()).trace())?;
+            m.deps = m
+                .deps
+                .into_iter()
+                .zip(&deps[cr_idx])
+                .filter(|(_, dep_idx)| is_engine[**dep_idx])
+                .map(|(dep, dep_idx)| CargoDep {
+                    path: dirs[*dep_idx].display().to_string(),
+                    ..dep
+                })
+                .collect();
+            Ok(m)
+        })
+        .collect()
+}
+
+fn get_str_array(v: Option<&toml::Value>) -> Vec<String> {
+    v.and_then(|v| v.as_array())
+        .map(|v| v.filter_map_vec(|s| s.as_str().map(|s| s.to_string())))
+        .unwrap_or_default()
+}
+
+// Returns the enabled features for each crate
+// `deps[i][j]` is the crate index of the jth dependency of crate i
+// Crate 0 is the main crate, which uses its default features and `main_features`
+// The crate running the build script gets its features from `CARGO_FEATURE_*`
+pub fn resolve_features(
+    manifests: &[CargoToml],
+    deps: &[Vec<usize>],
+    main_features: &[String],
+) -> Vec<Vec<String>> {
+    let mut resolver = FeatureResolver {
+        manifests,
+        deps,
+        tables: manifests.map_vec(|m| m.get_feature_table()),
+        features: manifests.map_vec(|_| HashSet::new()),
+        is_used: manifests.map_vec(|_| false),
+        active_deps: manifests.map_vec(|_| HashSet::new()),
+        weak_features: manifests.map_vec(|_| HashMap::new()),
+    };
+
+    // Features requested directly
+    resolver.use_crate(0);
+    resolver.enable(0, DEFAULT_FEATURE);
+    for f in main_features {
+        resolver.enable(0, f);
+    }
+    let build_dir = env::var("CARGO_MANIFEST_DIR")
+        .ok()
+        .and_then(|dir| fs::canonicalize(dir).ok());
+    if let Some(cr_idx) = build_dir.and_then(|dir| manifests.iter().position(|m| m.dir == dir)) {
+        let enabled = resolver.tables[cr_idx].keys().filter_map_vec_into(|f| {
+            env::var(format!(
+                "CARGO_FEATURE_{}",
+                f.to_uppercase().replace('-', "_")
+            ))
+            .ok()
+            .map(|_| f.to_string())
+        });
+        for f in enabled {
+            resolver.enable(cr_idx, &f);
+        }
+    }
+
+    resolver.features.map_vec_into(|f| f.into_iter().collect())
+}
+
+impl CargoToml {
+    // Optional dependencies have an implicit feature unless a feature names them with 'dep:'
+    fn get_feature_table(&self) -> HashMap<String, Vec<String>> {
+        let mut table = self.features.clone();
+        for dep in self.deps.iter().filter(|d| d.optional) {
+            let dep_feature = format!("dep:{}", dep.name);
+            if !self.features.values().flatten().any(|f| f == &dep_feature) {
+                table
+                    .entry(dep.name.to_string())
+                    .or_insert(vec![dep_feature]);
+            }
+        }
+        table
+    }
+}
+
+// Enables features the same way Cargo's feature resolver does
+struct FeatureResolver<'a> {
+    manifests: &'a [CargoToml],
+    deps: &'a [Vec<usize>],
+    // Features of each crate, including implicit features
+    tables: Vec<HashMap<String, Vec<String>>>,
+    features: Vec<HashSet<String>>,
+    // Whether another crate depends on the crate, only used crates enable their dependencies
+    is_used: Vec<bool>,
+    // Positions of enabled dependencies, optional dependencies are enabled by features
+    active_deps: Vec<HashSet<usize>>,
+    // Features of dependencies which wait for the dependency to be enabled, e.g. "dep?/feature"
+    weak_features: Vec<HashMap<usize, Vec<String>>>,
+}
+
+impl FeatureResolver<'_> {
+    fn use_crate(&mut self, cr_idx: usize) {
+        if std::mem::replace(&mut self.is_used[cr_idx], true) {
+            return;
+        }
+        for (i, dep) in self.manifests[cr_idx].deps.iter().enumerate() {
+            if !dep.optional {
+                self.enable_dep(cr_idx, i);
+            }
+        }
+    }
+
+    fn enable_dep(&mut self, cr_idx: usize, i: usize) {
+        if !self.active_deps[cr_idx].insert(i) {
+            return;
+        }
+        let dep = &self.manifests[cr_idx].deps[i];
+        let dep_idx = self.deps[cr_idx][i];
+        self.use_crate(dep_idx);
+        if dep.default_features {
+            self.enable(dep_idx, DEFAULT_FEATURE);
+        }
+        for f in &dep.features {
+            self.enable(dep_idx, f);
+        }
+        for f in self.weak_features[cr_idx].remove(&i).unwrap_or_default() {
+            self.enable(dep_idx, &f);
+        }
+    }
+
+    // `feature` may be a feature of this crate, "dep:name", "name/feature", or "name?/feature"
+    fn enable(&mut self, cr_idx: usize, feature: &str) {
+        let find_dep = |name: &str| {
+            self.manifests[cr_idx]
+                .deps
+                .iter()
+                .position(|d| d.name == name)
+        };
+        if let Some(name) = feature.strip_prefix("dep:") {
+            if let Some(i) = find_dep(name) {
+                self.enable_dep(cr_idx, i);
+            }
+            return;
+        }
+        match feature.split_once('/') {
+            // Feature of a dependency, which is enabled if it is optional
+            Some((name, dep_feature)) => match name.strip_suffix('?') {
+                Some(name) => {
+                    if let Some(i) = find_dep(name) {
+                        match self.active_deps[cr_idx].contains(&i) {
+                            true => self.enable(self.deps[cr_idx][i], dep_feature),
+                            false => self.weak_features[cr_idx]
+                                .entry(i)
+                                .or_default()
+                                .push(dep_feature.to_string()),
+                        }
+                    }
+                }
+                None => {
+                    if let Some(i) = find_dep(name) {
+                        self.enable_dep(cr_idx, i);
+                        self.enable(self.deps[cr_idx][i], dep_feature);
+                    }
+                }
+            },
+            // Feature of this crate
+            None => {
+                let enables = match self.tables[cr_idx].get(feature) {
+                    Some(enables) if !self.features[cr_idx].contains(feature) => enables.to_vec(),
+                    _ => return,
+                };
+                self.features[cr_idx].insert(feature.to_string());
+                for f in enables {
+                    self.enable(cr_idx, &f);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{resolve_features, CargoDep, CargoToml};
+
+    fn manifest(name: &str, features: &[(&str, &[&str])], deps: Vec<CargoDep>) -> CargoToml {
+        CargoToml {
+            dir: PathBuf::from("/fixtures").join(name),
+            deps,
+            features: features
+                .iter()
+                .map(|(f, enables)| {
+                    (
+                        f.to_string(),
+                        enables.iter().map(|s| s.to_string()).collect(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn dep(name: &str, features: &[&str], default_features: bool) -> CargoDep {
+        CargoDep {
+            name: name.to_string(),
+            path: format!("../{name}"),
+            features: features.iter().map(|f| f.to_string()).collect(),
+            default_features,
+            optional: false,
+        }
+    }
+
+    fn optional_dep(name: &str) -> CargoDep {
+        CargoDep {
+            optional: true,
+            ..dep(name, &[], true)
+        }
+    }
+
+    fn resolve(manifests: &[CargoToml], deps: &[Vec<usize>]) -> Vec<Vec<String>> {
+        resolve_main(manifests, deps, &[])
+    }
+
+    fn resolve_main(
+        manifests: &[CargoToml],
+        deps: &[Vec<usize>],
+        main_features: &[&str],
+    ) -> Vec<Vec<String>> {
+        let main_features = main_features
+            .iter()
+            .map(|f| f.to_string())
+            .collect::<Vec<_>>();
+        resolve_features(manifests, deps, &main_features)
+            .into_iter()
+            .map(|mut f| {
+                f.sort();
+                f
+            })
+            .collect()
+    }
+
+    #[test]
+    fn default_features() {
+        let manifests = vec![
+            manifest(
+                "game",
+                &[("default", &["fast"]), ("fast", &[])],
+                vec![dep("engine", &[], true)],
+            ),
+            manifest(
+                "engine",
+                &[("default", &["render"]), ("render", &[]), ("audio", &[])],
+                vec![],
+            ),
+        ];
+        assert_eq!(
+            resolve(&manifests, &[vec![1], vec![]]),
+            vec![vec!["default", "fast"], vec!["default", "render"]]
+        );
+    }
+
+    #[test]
+    fn transitive_features() {
+        let manifests = vec![
+            manifest(
+                "game",
+                &[("default", &["sound"]), ("sound", &["engine/audio"])],
+                vec![dep("engine", &[], false)],
+            ),
+            manifest(
+                "engine",
+                &[("audio", &["mixer", "macros?/audio"]), ("mixer", &[])],
+                vec![dep("macros", &[], false)],
+            ),
+            manifest("macros", &[("audio", &[])], vec![]),
+        ];
+        assert_eq!(
+            resolve(&manifests, &[vec![1], vec![2], vec![]]),
+            vec![
+                vec!["default", "sound"],
+                vec!["audio", "mixer"],
+                vec!["audio"]
+            ]
+        );
+    }
+
+    #[test]
+    fn disabled_features() {
+        let manifests = vec![
+            manifest("game", &[], vec![dep("engine", &["audio"], false)]),
+            manifest(
+                "engine",
+                &[
+                    ("default", &["render"]),
+                    ("render", &[]),
+                    ("audio", &["dep:rodio"]),
+                ],
+                vec![],
+            ),
+        ];
+        assert_eq!(
+            resolve(&manifests, &[vec![1], vec![]]),
+            vec![vec![], vec!["audio"]]
+        );
+    }
+
+    #[test]
+    fn main_features() {
+        let manifests = vec![
+            manifest(
+                "game",
+                &[("default", &[]), ("debug", &["engine/debug"])],
+                vec![dep("engine", &[], false)],
+            ),
+            manifest("engine", &[("debug", &[]), ("audio", &[])], vec![]),
+        ];
+        // E.g. `cargo build --features debug,engine/audio`
+        assert_eq!(
+            resolve_main(&manifests, &[vec![1], vec![]], &["debug", "engine/audio"]),
+            vec![vec!["debug", "default"], vec!["audio", "debug"]]
+        );
+    }
+
+    #[test]
+    fn weak_features() {
+        let manifests = vec![
+            manifest(
+                "game",
+                &[
+                    ("default", &["plugin?/audio"]),
+                    ("plugins", &["dep:plugin"]),
+                ],
+                vec![optional_dep("plugin")],
+            ),
+            manifest("plugin", &[("audio", &[])], vec![]),
+        ];
+        // The weak feature doesn't enable the optional dependency
+        assert_eq!(
+            resolve(&manifests, &[vec![1], vec![]]),
+            vec![vec!["default"], vec![]]
+        );
+        // Once it is enabled, so is its feature
+        assert_eq!(
+            resolve_main(&manifests, &[vec![1], vec![]], &["plugins"]),
+            vec![vec!["default", "plugins"], vec!["audio"]]
+        );
+    }
+
+    #[test]
+    fn implicit_features() {
+        let manifests = vec![
+            manifest(
+                "game",
+                &[],
+                vec![optional_dep("plugin"), optional_dep("renamed")],
+            ),
+            manifest("plugin", &[("default", &["audio"]), ("audio", &[])], vec![]),
+            manifest(
+                "renamed",
+                &[("default", &["video"]), ("video", &[])],
+                vec![],
+            ),
+        ];
+        let deps = [vec![1, 2], vec![], vec![]];
+        assert_eq!(
+            resolve(&manifests, &deps),
+            vec![Vec::<String>::new(), vec![], vec![]]
+        );
+        assert_eq!(
+            resolve_main(&manifests, &deps, &["plugin"]),
+            vec![vec!["plugin"], vec!["audio", "default"], vec![]]
+        );
+
+        // Naming the dependency with 'dep:' removes its implicit feature
+        let manifests = vec![
+            manifest(
+                "game",
+                &[("extras", &["dep:renamed"])],
+                vec![optional_dep("plugin"), optional_dep("renamed")],
+            ),
+            manifests.into_iter().nth(1).unwrap(),
+            manifest(
+                "renamed",
+                &[("default", &["video"]), ("video", &[])],
+                vec![],
+            ),
+        ];
+        assert_eq!(
+            resolve_main(&manifests, &deps, &["renamed", "extras"]),
+            vec![vec!["extras"], vec![], vec!["default", "video"]]
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    hash::{Hash, Hasher},
+};
+
+// Cfgs which are never set when the build script runs
+const UNSET_CFGS: [&str; 4] = ["test", "doc", "doctest", "miri"];
+
+// Cfg options for the current build, e.g. `unix` or `target_os = "linux"`
+#[derive(Debug, Clone, Default)]
+pub struct CfgEnv {
+    pub features: Vec<String>,
+    names: HashSet<String>,
+    values: HashMap<String, HashSet<String>>,
+    // If false, cfgs which aren't set are unknown rather than inactive
+    is_known: bool,
+}
+
+impl CfgEnv {
+    // Reads `CARGO_CFG_*` and `--cfg` flags from `CARGO_ENCODED_RUSTFLAGS`
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+        for (k, v) in env::vars() {
+            if let Some(k) = k.strip_prefix("CARGO_CFG_") {
+                cfg.is_known = true;
+                let k = k.to_lowercase();
+                // Features are set per crate
+                if k == "feature" {
+                    continue;
+                }
+                if v.is_empty() {
+                    cfg.names.insert(k);
+                } else {
+                    cfg.values
+                        .entry(k)
+                        .or_default()
+                        .extend(v.split(',').map(|v| v.to_string()));
+                }
+            }
+        }
+
+        // `CARGO_CFG_*` names are uppercased, so get the originals
+        if let Ok(flags) = env::var("CARGO_ENCODED_RUSTFLAGS") {
+            let mut flags = flags.split('\x1f');
+            while let Some(flag) = flags.next() {
+                let spec = match flag.strip_prefix("--cfg") {
+                    Some("") => flags.next(),
+                    Some(spec) => spec.strip_prefix('='),
+                    None => None,
+                };
+                if let Some(spec) = spec {
+                    cfg.add_cfg(spec);
+                }
+            }
+        }
+
+        cfg
+    }
+
+    pub fn with_features(&self, features: Vec<String>) -> Self {
+        Self {
+            features,
+            ..self.clone()
+        }
+    }
+
+    // Adds a cfg in `--cfg` format, e.g. `name` or `key="value"`
+    pub fn add_cfg(&mut self, spec: &str) {
+        match spec.split_once('=') {
+            Some((k, v)) => {
+                self.values
+                    .entry(k.trim().to_string())
+                    .or_default()
+                    .insert(v.trim().trim_matches('"').to_string());
+            }
+            None => {
+                self.names.insert(spec.trim().to_string());
+            }
+        }
+    }
+
+    pub fn eval_name(&self, name: &str) -> Option<bool> {
+        if self.names.contains(name) {
+            Some(true)
+        } else {
+            self.eval_unset(name)
+        }
+    }
+
+    pub fn eval_value(&self, key: &str, value: &str) -> Option<bool> {
+        if key == "feature" {
+            Some(self.features.iter().any(|f| f == value))
+        } else if self.values.get(key).is_some_and(|v| v.contains(value)) {
+            Some(true)
+        } else {
+            self.eval_unset(key)
+        }
+    }
+
+    fn eval_unset(&self, key: &str) -> Option<bool> {
+        (self.is_known || UNSET_CFGS.contains(&key)).then_some(false)
+    }
+}
+
+// Sets are sorted so equal environments have equal hashes
+impl Hash for CfgEnv {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut names = self.names.iter().collect::<Vec<_>>();
+        names.sort();
+        let mut values = self
+            .values
+            .iter()
+            .map(|(k, v)| {
+                let mut v = v.iter().collect::<Vec<_>>();
+                v.sort();
+                (k, v)
+            })
+            .collect::<Vec<_>>();
+        values.sort();
+        (&self.features, names, values, self.is_known).hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream;
+    use quote::quote;
+
+    use super::CfgEnv;
+    use crate::parse::attributes::get_attributes_if_active;
+
+    // Build for linux with the "audio" feature
+    fn linux() -> CfgEnv {
+        let mut cfg = CfgEnv {
+            features: vec!["audio".to_string()],
+            is_known: true,
+            ..Default::default()
+        };
+        cfg.add_cfg("unix");
+        cfg.add_cfg("target_os=\"linux\"");
+        cfg
+    }
+
+    // Names of the item's attributes, None if the item is inactive
+    fn active(attrs: TokenStream, cfg: &CfgEnv) -> Option<Vec<String>> {
+        let item = syn::parse2::<syn::ItemStruct>(quote!(#attrs struct Item;)).unwrap();
+        get_attributes_if_active(&item.attrs, &vec!["crate".to_string()], cfg)
+            .unwrap()
+            .map(|attrs| {
+                attrs
+                    .into_iter()
+                    .filter_map(|a| a.path.last().cloned())
+                    .collect()
+            })
+    }
+
+    #[test]
+    fn cfg_attr() {
+        let cfg = linux();
+        assert_eq!(
+            active(
+                quote!(
+                    #[cfg_attr(unix, hyperfold_engine::component)]
+                    #[cfg_attr(windows, hyperfold_engine::global)]
+                ),
+                &cfg
+            ),
+            Some(vec!["component".to_string()])
+        );
+        assert_eq!(
+            active(
+                quote!(#[cfg_attr(feature = "audio", cfg(feature = "video"))]),
+                &cfg
+            ),
+            None
+        );
+        assert_eq!(
+            active(
+                quote!(#[cfg_attr(unix, cfg_attr(target_os = "linux", hyperfold_engine::event))]),
+                &cfg
+            ),
+            Some(vec!["event".to_string()])
+        );
+    }
+
+    #[test]
+    fn nested_predicates() {
+        let cfg = linux();
+        assert!(active(
+            quote!(#[cfg(all(unix, not(windows), any(feature = "video", target_os = "linux")))]),
+            &cfg
+        )
+        .is_some());
+        assert!(active(
+            quote!(#[cfg(any(windows, all(unix, not(feature = "audio"))))]),
+            &cfg
+        )
+        .is_none());
+        assert!(active(quote!(#[cfg(not(any(test, all())))]), &cfg).is_none());
+    }
+
+    #[test]
+    fn empty_predicates() {
+        let cfg = linux();
+        assert!(active(quote!(#[cfg(all())]), &cfg).is_some());
+        assert!(active(quote!(#[cfg(any())]), &cfg).is_none());
+        assert_eq!(
+            active(quote!(#[cfg_attr(all(), hyperfold_engine::system)]), &cfg),
+            Some(vec!["system".to_string()])
+        );
+    }
+
+    #[test]
+    fn unknown_predicates() {
+        // Outside of a build script, only features and unset cfgs are known
+        let cfg = CfgEnv::default();
+        assert!(active(quote!(#[cfg(windows)]), &cfg).is_some());
+        assert!(active(quote!(#[cfg(not(windows))]), &cfg).is_some());
+        assert!(active(quote!(#[cfg(any(feature = "audio", windows))]), &cfg).is_some());
+        assert!(active(quote!(#[cfg(test)]), &cfg).is_none());
+        assert!(active(quote!(#[cfg(all(windows, test))]), &cfg).is_none());
+        assert!(active(quote!(#[cfg(feature = "audio")]), &cfg).is_none());
+    }
+}
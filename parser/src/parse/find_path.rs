@@ -6,37 +6,113 @@ use crate::parse::{
     AstCrate, ModInfo, {AstMod, Symbol},
 };
 use shared::{
+    macros::ExpandEnum,
     syn::{
         error::{CriticalResult, StrToError},
         use_path_from_syn,
     },
-    traits::{Catch, CollectVecInto},
+    traits::{Catch, CollectVec, CollectVecInto},
 };
 
-use super::MatchSymbol;
+use super::{BuiltinSymbol, MatchSymbol, SymbolType, Visibility};
 
-#[derive(PartialEq, Eq, Clone, Debug)]
+// Crates whose items can be named from anywhere
+pub const STD_CRATES: [&str; 3] = ["std", "core", "alloc"];
+
+// Std crates aren't parsed, so their items are added manually
+// Symbols aren't Sync, leaked so resolved paths can borrow them
+thread_local! {
+    static BUILTIN_SYMBOLS: &'static Vec<Symbol> = Box::leak(Box::new(
+        BuiltinSymbol::VARIANTS
+            .into_iter()
+            .flat_map(|sym| {
+                sym.get_paths().map_vec_into(|path| Symbol {
+                    kind: SymbolType::Builtin(sym),
+                    path: path.map_vec(|s| s.to_string()),
+                    vis: Visibility::Public,
+                })
+            })
+            .collect(),
+    ));
+}
+
+fn resolve_builtin(path: &Vec<String>) -> Option<&'static Symbol> {
+    BUILTIN_SYMBOLS.with(|syms| syms.iter().find(|sym| &sym.path == path))
+}
+
+fn resolve_prelude(path: &[String]) -> Option<&'static Symbol> {
+    match path {
+        [name] => BUILTIN_SYMBOLS.with(|syms| {
+            syms.iter().find(|sym| match sym.kind {
+                SymbolType::Builtin(b) => b.get_prelude_name().is_some_and(|n| n == name),
+                _ => false,
+            })
+        }),
+        _ => None,
+    }
+}
+
+// Generic argument of an instantiated item
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub enum GenericArg {
+    // Item in one of the parsed crates
+    Item(ItemPath),
+    // Type that can be named from any crate, e.g. primitives or std types
+    Builtin(String, Vec<GenericArg>),
+}
+
+impl std::fmt::Display for GenericArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenericArg::Item(path) => f.write_str(&path.to_string()),
+            GenericArg::Builtin(ty, generics) => {
+                f.write_fmt(format_args!("{ty}{}", generics_string(generics)))
+            }
+        }
+    }
+}
+
+pub fn generics_string(generics: &Vec<GenericArg>) -> String {
+    match generics.is_empty() {
+        true => String::new(),
+        false => format!("<{}>", generics.map_vec(|g| g.to_string()).join(", ")),
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
 pub struct ItemPath {
     pub cr_idx: usize,
     pub path: Vec<String>,
+    // Empty unless this is an instantiation of a generic item
+    pub generics: Vec<GenericArg>,
 }
 
 impl ItemPath {
     pub fn new(cr_idx: usize, path: Vec<String>) -> Self {
-        Self { cr_idx, path }
+        Self {
+            cr_idx,
+            path,
+            generics: Vec::new(),
+        }
+    }
+
+    pub fn with_generics(mut self, generics: Vec<GenericArg>) -> Self {
+        self.generics = generics;
+        self
     }
 
     pub fn to_string(&self) -> String {
-        self.path.join("::")
+        format!("{}{}", self.path.join("::"), self.generics_string())
+    }
+
+    pub fn generics_string(&self) -> String {
+        generics_string(&self.generics)
     }
 }
 
 impl Default for ItemPath {
     fn default() -> Self {
-        Self {
-            cr_idx: 0,
-            path: Vec::new(),
-        }
+        Self::new(0, Vec::new())
     }
 }
 
@@ -45,13 +121,55 @@ fn resolve_error(path: Vec<String>) -> String {
     format!("Could not resolve path: {path}")
 }
 
+// Mod that a path is written in, names along the path must be visible from here
+#[derive(Copy, Clone)]
+struct PathOrigin<'a> {
+    cr_idx: usize,
+    path: &'a [String],
+}
+
+impl<'a> PathOrigin<'a> {
+    fn new(m: &'a AstMod, cr: &AstCrate) -> Self {
+        Self {
+            cr_idx: cr.idx,
+            path: &m.path,
+        }
+    }
+
+    fn can_see(&self, vis: &Visibility, cr: &AstCrate) -> bool {
+        vis.is_visible_from(cr.idx, self.cr_idx, self.path)
+    }
+
+    fn check(
+        &self,
+        vis: &Visibility,
+        cr: &AstCrate,
+        msg: impl FnOnce() -> String,
+    ) -> CriticalResult<()> {
+        match self.can_see(vis, cr) {
+            true => Ok(()),
+            false => msg().error().as_err(),
+        }
+    }
+}
+
+// Resolves a path starting with 'crate' or a dependency, ignoring visibility
 // Err means:
 // 1) Not from a valid crate
 // 2) resolve_mod() returns Err
 pub fn resolve_path_from_crate<'a>(
-    mut path: Vec<String>,
+    path: Vec<String>,
+    cr: &'a AstCrate,
+    crates: &'a Vec<AstCrate>,
+) -> CriticalResult<&'a Symbol> {
+    resolve_from_crate(path, cr, crates, None)
+}
+
+fn resolve_from_crate<'a>(
+    path: Vec<String>,
     cr: &'a AstCrate,
     crates: &'a Vec<AstCrate>,
+    origin: Option<PathOrigin>,
 ) -> CriticalResult<&'a Symbol> {
     // println!("Resolve: {}, crate: {}", path.join("::"), cr.idx);
     match path.first() {
@@ -62,20 +180,23 @@ pub fn resolve_path_from_crate<'a>(
                     path.to_vec(),
                     1,
                     (cr.get_main_mod()?, cr, crates),
+                    origin,
                 )),
                 // Match dependency
                 _ => cr.deps.iter().find_map(|(idx, alias)| {
                     (alias == p).then(|| {
-                        resolve_path_from_crate(
+                        resolve_from_crate(
                             [vec!["crate".to_string()], path[1..].to_vec()].concat(),
                             crates
                                 .get(*idx)
                                 .catch_err("Invalid dependency index".trace())?,
                             crates,
+                            origin,
                         )
                     })
                 }),
             }
+            .or_else(|| resolve_builtin(&path).map(Ok))
             .map_or_else(|| resolve_error(path).trace().as_err(), |r| r)
         }
         None => resolve_error(path).trace().as_err(),
@@ -87,18 +208,18 @@ pub fn resolve_path_from_crate<'a>(
 // 1) Does not match anything
 // 2) Matches mod but remainder doesn't match
 // 3) Matches use statement new path doesn't match
+// 4) Matches something that isn't visible from the origin
 fn resolve_path_from_mod<'a>(
     path: Vec<String>,
     idx: usize,
     (m, cr, crates): ModInfo<'a>,
+    origin: Option<PathOrigin>,
 ) -> CriticalResult<&'a Symbol> {
     // println!(
     //     "Resolve Mod: {} at {}",
     //     path.join("::"),
     //     path.get(idx).unwrap_or(&"None".to_string())
     // );
-    let cr_idx = cr.idx;
-
     let name = path
         .get(idx)
         .catch_err(
@@ -111,25 +232,30 @@ fn resolve_path_from_mod<'a>(
         )?
         .to_string();
     let is_path_end = idx == path.len() - 1;
+    let check = |vis: &Visibility, kind: &str| match origin {
+        Some(origin) => origin.check(vis, cr, || format!("{kind} '{name}' is private")),
+        None => Ok(()),
+    };
 
     // println!("Finding: {name}");
     // Check sub modules
     for m in cr.get_mods(&m.mods)? {
         if name == m.name {
             // println!("Found Mod: {}", name);
+            check(&m.vis, "Module")?;
             return if is_path_end {
                 // The path points to a mod
                 resolve_error(path).trace().as_err()
             } else {
-                resolve_path_from_mod(path, idx + 1, (m, cr, crates))
+                resolve_path_from_mod(path, idx + 1, (m, cr, crates), origin)
             };
         }
     }
     // Check symbols
-    // TODO: previously filtered public
     for sym in m.symbols.iter() {
         if sym.path.last().is_some_and(|s| s == &name) {
             // println!("Found Symbol: {}", sym.path.join("::"));
+            check(&sym.vis, &sym.kind.to_string())?;
             return if is_path_end {
                 Ok(sym)
             } else {
@@ -138,18 +264,24 @@ fn resolve_path_from_mod<'a>(
         }
     }
     // Check use statements
+    // Imported paths are resolved from the mod containing the use
+    let use_origin = origin.map(|_| PathOrigin::new(m, cr));
     for sym in m.uses.iter() {
-        // Glob - this is allowed to fail
+        // Glob - this is allowed to fail, only visible imports are included
         if sym.ident == "*" {
+            if origin.is_some_and(|origin| !origin.can_see(&sym.vis, cr)) {
+                continue;
+            }
             let path = [sym.path.to_vec(), path[idx..].to_vec()].concat();
-            if let Ok(v) = resolve_path(path.to_vec(), (m, cr, crates)) {
+            if let Ok(v) = resolve_from_crate(path.to_vec(), cr, crates, use_origin) {
                 return Ok(v);
             }
         // Use
         } else if sym.ident == name {
+            check(&sym.vis, "Import")?;
             let path = [sym.path.to_vec(), path[idx + 1..].to_vec()].concat();
             // println!("Matched Use: {}", sym.path.join("::"));
-            return resolve_path(path.to_vec(), (m, cr, crates));
+            return resolve_from_crate(path.to_vec(), cr, crates, use_origin);
         }
     }
 
@@ -157,12 +289,23 @@ fn resolve_path_from_mod<'a>(
 }
 
 // Paths that start relative to some mod item
-pub fn resolve_path<'a>(
+// Names must be visible from that mod
+pub fn resolve_path<'a>(path: Vec<String>, info: ModInfo<'a>) -> PathResolution<'a> {
+    PathResolution {
+        result: resolve_local(path.to_vec(), info),
+        unresolved: false,
+        path,
+        info,
+    }
+    .mark_unresolved()
+}
+
+fn resolve_local<'a>(
     path: Vec<String>,
     (m, cr, crates): ModInfo<'a>,
 ) -> CriticalResult<&'a Symbol> {
     // println!("Local Resolve: {}", path.join("::"));
-    let cr_idx = cr.idx;
+    let origin = Some(PathOrigin::new(m, cr));
 
     let name = path
         .first()
@@ -170,7 +313,11 @@ pub fn resolve_path<'a>(
 
     // Can't be local
     if name == "crate" {
-        return resolve_path_from_crate(path, cr, crates);
+        return resolve_from_crate(path, cr, crates, origin);
+    }
+    // '::' always names a crate
+    if name.is_empty() {
+        return resolve_from_crate(path[1..].to_vec(), cr, crates, origin);
     }
 
     // Search for a symbol
@@ -186,20 +333,31 @@ pub fn resolve_path<'a>(
 
     // Check mods
     if let Some(m) = cr.get_mods(&m.mods)?.into_iter().find(|m| name == &m.name) {
-        return resolve_path_from_crate([m.path.to_vec(), path[1..].to_vec()].concat(), cr, crates);
+        return resolve_from_crate(
+            [m.path.to_vec(), path[1..].to_vec()].concat(),
+            cr,
+            crates,
+            origin,
+        );
     }
 
     // Check possible paths
     if let Some(res) = m.uses.iter().find_map(|syn| {
         // Get possible path
         if syn.ident == "*" {
-            resolve_path_from_crate([syn.path.to_vec(), path.to_vec()].concat(), cr, crates)
-                .map_or(None, |p| Some(Ok(p)))
+            resolve_from_crate(
+                [syn.path.to_vec(), path.to_vec()].concat(),
+                cr,
+                crates,
+                origin,
+            )
+            .map_or(None, |p| Some(Ok(p)))
         } else if name == &syn.ident {
-            Some(resolve_path_from_crate(
+            Some(resolve_from_crate(
                 [syn.path.to_vec(), path[1..].to_vec()].concat(),
                 cr,
                 crates,
+                origin,
             ))
         } else {
             None
@@ -208,18 +366,140 @@ pub fn resolve_path<'a>(
         return res;
     }
 
+    // Check prelude
+    if let Some(sym) = resolve_prelude(&path) {
+        return Ok(sym);
+    }
+
     // Check dependencies
-    resolve_path_from_crate(path, cr, crates)
+    resolve_from_crate(path, cr, crates, origin)
 }
 
 pub fn resolve_syn_path<'a>(
     parent_path: &Vec<String>,
     path: &syn::Path,
     (m, cr, crates): ModInfo<'a>,
-) -> CriticalResult<&'a Symbol> {
+) -> PathResolution<'a> {
     resolve_path(use_path_from_syn(&m.path, path), (m, cr, crates))
 }
 
+// Result of resolving a path from a mod
+// Keeps the path so failures can suggest similar symbols
+pub struct PathResolution<'a> {
+    result: CriticalResult<&'a Symbol>,
+    // The path itself failed to resolve
+    unresolved: bool,
+    path: Vec<String>,
+    info: ModInfo<'a>,
+}
+
+// Most suggestions to show for an unresolved path
+const MAX_SUGGESTIONS: usize = 3;
+
+impl<'a> PathResolution<'a> {
+    fn mark_unresolved(mut self) -> Self {
+        self.unresolved = self.result.is_err();
+        self
+    }
+
+    pub fn and_then(self, f: impl FnOnce(&'a Symbol) -> CriticalResult<&'a Symbol>) -> Self {
+        Self {
+            result: self.result.and_then(f),
+            ..self
+        }
+    }
+
+    pub fn into_result(self) -> CriticalResult<&'a Symbol> {
+        self.result
+    }
+
+    // Symbols named like the last path segment, written as paths that resolve from the mod
+    fn find_similar(&self, is_expected: impl Fn(&SymbolType) -> bool) -> Vec<String> {
+        let (m, cr, crates) = self.info;
+        let name = match self.path.last() {
+            Some(name) => name,
+            None => return Vec::new(),
+        };
+        let max_dist = (name.len() / 3).max(1);
+
+        let mut similar = crates
+            .iter()
+            .flat_map(|sym_cr| sym_cr.iter_mods().map(move |sym_m| (sym_cr, sym_m)))
+            .flat_map(|(sym_cr, sym_m)| sym_m.symbols.iter().map(move |sym| (sym_cr, sym_m, sym)))
+            .filter(|(_, _, sym)| is_expected(&sym.kind))
+            .filter_map(|(sym_cr, sym_m, sym)| {
+                let dist = edit_distance(name, sym.path.last()?);
+                if dist > max_dist {
+                    return None;
+                }
+                // Write the path relative to the mod if possible
+                let path = if sym_cr.idx != cr.idx {
+                    let alias = cr.deps.get(&sym_cr.idx)?;
+                    [vec![alias.to_string()], sym.path[1..].to_vec()].concat()
+                } else if sym_m.idx == m.idx {
+                    sym.path.last().map(|name| vec![name.to_string()])?
+                } else {
+                    sym.path.to_vec()
+                };
+                // Must be visible and not shadowed
+                resolve_local(path.to_vec(), self.info)
+                    .is_ok_and(|res| std::ptr::eq(res, sym))
+                    .then(|| (dist, path.join("::")))
+            })
+            .collect::<Vec<_>>();
+        similar.sort_by(|(d1, p1), (d2, p2)| d1.cmp(d2).then(p1.len().cmp(&p2.len())));
+        similar.dedup_by(|(_, p1), (_, p2)| p1 == p2);
+        similar
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map_vec_into(|(_, path)| path)
+    }
+}
+
+impl<'a> MatchSymbol<'a> for PathResolution<'a> {
+    fn and_then_impl<T>(
+        self,
+        f: impl FnOnce(&'a Symbol) -> CriticalResult<T>,
+    ) -> CriticalResult<T> {
+        self.result.and_then(f)
+    }
+
+    fn with_suggestions(self, is_expected: impl Fn(&SymbolType) -> bool) -> Self {
+        if !self.unresolved {
+            return self;
+        }
+        let notes = self.find_similar(is_expected).map_vec_into(|path| {
+            format!("Did you mean '{path}'?")
+                .note()
+                .with_replacement(path)
+        });
+        Self {
+            result: self
+                .result
+                .map_err(|errs| errs.map_vec_into(|e| e.with_notes(notes.to_vec()))),
+            ..self
+        }
+    }
+}
+
+// Number of single character insertions, deletions, or substitutions between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            curr.push(
+                (prev[j] + (ca != *cb) as usize)
+                    .min(prev[j + 1] + 1)
+                    .min(curr[j] + 1),
+            );
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
 impl<'a> MatchSymbol<'a> for CriticalResult<&'a Symbol> {
     fn and_then_impl<T>(
         self,
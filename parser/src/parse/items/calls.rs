@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+
+use proc_macro2::Span;
+use serde::{Deserialize, Serialize};
+use shared::syn::{error::CriticalResult, use_path_from_syn};
+use syn::{punctuated::Punctuated, spanned::Spanned, visit::Visit, Token};
+
+use crate::parse::{attributes::get_attributes_if_active, cfg::CfgEnv, AstMod};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EngineCallKind {
+    // events.new_event(E)
+    NewEvent,
+    // cm.add_component(eid, C) or add_components!(cm, eid, C...)
+    AddComponent,
+}
+
+// Engine call in a function body, used by lints and to find generic instances
+// `path` is the type of the argument, e.g. the struct being constructed
+// It is empty if the type isn't known, e.g. the argument is returned by a function
+// `generics` are the type arguments written in the path, e.g. `Foo::<u32>::new()`
+#[derive(Debug)]
+pub struct AstEngineCall {
+    pub kind: EngineCallKind,
+    pub path: Vec<String>,
+    pub generics: Vec<syn::Type>,
+    pub span: Span,
+}
+
+pub struct EngineCallVisitor<'a> {
+    mod_path: &'a Vec<String>,
+    calls: Vec<AstEngineCall>,
+    // Types of local variables by scope, None if the type isn't known
+    scopes: Vec<HashMap<String, Option<syn::Path>>>,
+}
+
+impl<'a> EngineCallVisitor<'a> {
+    fn add_call(&mut self, kind: EngineCallKind, arg: &syn::Expr) {
+        let (path, generics) = self
+            .get_expr_path(arg)
+            .map_or((Vec::new(), Vec::new()), |path| {
+                (
+                    use_path_from_syn(self.mod_path, path),
+                    get_path_generics(path),
+                )
+            });
+        self.calls.push(AstEngineCall {
+            kind,
+            path,
+            generics,
+            span: arg.span(),
+        })
+    }
+
+    // Variables are followed to the type they were declared with or the value they were set to
+    fn get_expr_path<'b>(&'b self, expr: &'b syn::Expr) -> Option<&'b syn::Path> {
+        match expr {
+            syn::Expr::Struct(e) => Some(&e.path),
+            syn::Expr::Path(e) => match e.path.get_ident() {
+                Some(ident) => self
+                    .scopes
+                    .iter()
+                    .rev()
+                    .find_map(|scope| scope.get(&ident.to_string()))
+                    .map_or(Some(&e.path), |path| path.as_ref()),
+                None => Some(&e.path),
+            },
+            syn::Expr::Call(e) => match &*e.func {
+                syn::Expr::Path(f) => Some(&f.path),
+                _ => None,
+            },
+            syn::Expr::Paren(e) => self.get_expr_path(&e.expr),
+            syn::Expr::Group(e) => self.get_expr_path(&e.expr),
+            _ => None,
+        }
+    }
+
+    fn add_variable(&mut self, pat: &syn::Pat, path: Option<syn::Path>) {
+        if let (syn::Pat::Ident(i), Some(scope)) = (pat, self.scopes.last_mut()) {
+            scope.insert(i.ident.to_string(), path);
+        }
+    }
+
+    fn in_scope(&mut self, visit: impl FnOnce(&mut Self)) {
+        self.scopes.push(HashMap::new());
+        visit(self);
+        self.scopes.pop();
+    }
+}
+
+fn get_type_path(ty: &syn::Type) -> Option<syn::Path> {
+    match ty {
+        syn::Type::Path(p) if p.qself.is_none() => Some(p.path.clone()),
+        syn::Type::Paren(p) => get_type_path(&p.elem),
+        syn::Type::Group(g) => get_type_path(&g.elem),
+        _ => None,
+    }
+}
+
+// Type arguments of the last segment that has them
+fn get_path_generics(path: &syn::Path) -> Vec<syn::Type> {
+    path.segments
+        .iter()
+        .rev()
+        .find_map(|s| match &s.arguments {
+            syn::PathArguments::AngleBracketed(ab) => Some(
+                ab.args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => Some(ty.clone()),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+impl<'a, 'ast> Visit<'ast> for EngineCallVisitor<'a> {
+    fn visit_block(&mut self, i: &'ast syn::Block) {
+        self.in_scope(|v| syn::visit::visit_block(v, i))
+    }
+
+    // Arguments are declared outside of the function body
+    fn visit_item_fn(&mut self, i: &'ast syn::ItemFn) {
+        self.in_scope(|v| syn::visit::visit_item_fn(v, i))
+    }
+
+    fn visit_impl_item_fn(&mut self, i: &'ast syn::ImplItemFn) {
+        self.in_scope(|v| syn::visit::visit_impl_item_fn(v, i))
+    }
+
+    fn visit_expr_closure(&mut self, i: &'ast syn::ExprClosure) {
+        self.in_scope(|v| syn::visit::visit_expr_closure(v, i))
+    }
+
+    fn visit_pat_type(&mut self, i: &'ast syn::PatType) {
+        syn::visit::visit_pat_type(self, i);
+        self.add_variable(&i.pat, get_type_path(&i.ty));
+    }
+
+    // The variable is in scope after its initializer
+    fn visit_local(&mut self, i: &'ast syn::Local) {
+        if let Some(init) = &i.init {
+            self.visit_expr(&init.expr);
+            if let Some((_, diverge)) = &init.diverge {
+                self.visit_expr(diverge);
+            }
+        }
+        match &i.pat {
+            syn::Pat::Type(p) => self.visit_pat_type(p),
+            pat => self.add_variable(
+                pat,
+                i.init
+                    .as_ref()
+                    .and_then(|init| self.get_expr_path(&init.expr))
+                    .cloned(),
+            ),
+        }
+    }
+
+    fn visit_expr_method_call(&mut self, i: &'ast syn::ExprMethodCall) {
+        match (i.method.to_string().as_str(), i.args.len()) {
+            ("new_event", 1) => self.add_call(EngineCallKind::NewEvent, &i.args[0]),
+            ("add_component", 2) => self.add_call(EngineCallKind::AddComponent, &i.args[1]),
+            _ => (),
+        }
+        syn::visit::visit_expr_method_call(self, i)
+    }
+
+    fn visit_macro(&mut self, i: &'ast syn::Macro) {
+        if i.path
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "add_components")
+        {
+            if let Ok(args) =
+                i.parse_body_with(Punctuated::<syn::Expr, Token![,]>::parse_terminated)
+            {
+                args.iter()
+                    .skip(2)
+                    .for_each(|arg| self.add_call(EngineCallKind::AddComponent, arg));
+            }
+        }
+        syn::visit::visit_macro(self, i)
+    }
+}
+
+impl AstMod {
+    pub fn visit_item_impl(&mut self, i: syn::ItemImpl, cfg: &CfgEnv) -> CriticalResult<()> {
+        if get_attributes_if_active(&i.attrs, &self.path, cfg)?.is_some() {
+            self.add_engine_calls(|v| v.visit_item_impl(&i));
+        }
+        Ok(())
+    }
+
+    // Called on active items
+    pub fn add_engine_calls(&mut self, visit: impl FnOnce(&mut EngineCallVisitor)) {
+        let mut visitor = EngineCallVisitor {
+            mod_path: &self.path,
+            calls: Vec::new(),
+            scopes: Vec::new(),
+        };
+        visit(&mut visitor);
+        self.items.engine_calls.extend(visitor.calls);
+    }
+}
@@ -1,9 +1,9 @@
 use shared::{syn::error::CriticalResult, traits::PushInto};
 use syn::spanned::Spanned;
 
-use crate::parse::{attributes::get_attributes_if_active, AstAttribute, AstMod};
+use crate::parse::{attributes::get_attributes_if_active, cfg::CfgEnv, AstAttribute, AstMod};
 
-use super::AstItemData;
+use super::{get_docs, get_generic_params, AstItemData};
 
 #[derive(Debug)]
 pub struct AstEnum {
@@ -12,8 +12,8 @@ pub struct AstEnum {
 }
 
 impl AstMod {
-    pub fn visit_item_enum(&mut self, i: syn::ItemEnum) -> CriticalResult<()> {
-        if let Some(attrs) = get_attributes_if_active(&i.attrs, &self.path, &Vec::new())? {
+    pub fn visit_item_enum(&mut self, i: syn::ItemEnum, cfg: &CfgEnv) -> CriticalResult<()> {
+        if let Some(attrs) = get_attributes_if_active(&i.attrs, &self.path, cfg)? {
             if !attrs.is_empty() {
                 self.items.enums.push(AstEnum {
                     attrs,
@@ -21,6 +21,8 @@ impl AstMod {
                         path: self.path.to_vec().push_into(i.ident.to_string()),
                         ident: i.ident.to_string(),
                         span: i.span(),
+                        generics: get_generic_params(&i.generics),
+                        docs: get_docs(&i.attrs),
                     },
                 });
             }
@@ -1,9 +1,9 @@
 use shared::{syn::error::CriticalResult, traits::PushInto};
-use syn::spanned::Spanned;
+use syn::{spanned::Spanned, visit::Visit};
 
-use crate::parse::{attributes::get_attributes_if_active, AstAttribute, AstMod};
+use crate::parse::{attributes::get_attributes_if_active, cfg::CfgEnv, AstAttribute, AstMod};
 
-use super::AstItemData;
+use super::{get_docs, get_generic_params, AstItemData};
 
 #[derive(Debug)]
 pub struct AstFunction {
@@ -14,8 +14,9 @@ pub struct AstFunction {
 
 impl AstMod {
     // Systems
-    pub fn visit_item_fn(&mut self, i: syn::ItemFn) -> CriticalResult<()> {
-        if let Some(attrs) = get_attributes_if_active(&i.attrs, &self.path, &Vec::new())? {
+    pub fn visit_item_fn(&mut self, i: syn::ItemFn, cfg: &CfgEnv) -> CriticalResult<()> {
+        if let Some(attrs) = get_attributes_if_active(&i.attrs, &self.path, cfg)? {
+            self.add_engine_calls(|v| v.visit_item_fn(&i));
             if !attrs.is_empty() {
                 self.items.functions.push(AstFunction {
                     attrs,
@@ -23,6 +24,8 @@ impl AstMod {
                         path: self.path.to_vec().push_into(i.sig.ident.to_string()),
                         ident: i.sig.ident.to_string(),
                         span: i.span(),
+                        generics: get_generic_params(&i.sig.generics),
+                        docs: get_docs(&i.attrs),
                     },
                     sig: i.sig,
                 });
@@ -6,9 +6,9 @@ use shared::syn::{
 };
 use syn::spanned::Spanned;
 
-use crate::parse::{attributes::get_attributes_if_active, AstMod};
+use crate::parse::{attributes::get_attributes_if_active, cfg::CfgEnv, AstMod};
 
-use super::AstItemData;
+use super::{get_docs, AstItemData};
 
 #[derive(Debug)]
 pub struct AstMacroCall {
@@ -18,8 +18,8 @@ pub struct AstMacroCall {
 
 impl AstMod {
     // Macro call
-    pub fn visit_item_macro(&mut self, i: syn::ItemMacro) -> CriticalResult<()> {
-        if let Some(_) = get_attributes_if_active(&i.attrs, &self.path, &Vec::new())? {
+    pub fn visit_item_macro(&mut self, i: syn::ItemMacro, cfg: &CfgEnv) -> CriticalResult<()> {
+        if let Some(_) = get_attributes_if_active(&i.attrs, &self.path, cfg)? {
             // Some is for macro_rules!
             if i.ident.is_none() {
                 let path = use_path_from_syn(&self.path, &i.mac.path);
@@ -31,6 +31,8 @@ impl AstMod {
                             .to_string(),
                         path,
                         span: i.mac.span(),
+                        generics: Vec::new(),
+                        docs: get_docs(&i.attrs),
                     },
                     args: i.mac.tokens,
                 });
@@ -1,15 +1,19 @@
 use proc_macro2::Span;
 
+mod calls;
 mod enums;
 mod functions;
 mod macros;
 mod structs;
+mod types;
 mod uses;
 
+pub use calls::{AstEngineCall, EngineCallKind};
 pub use enums::AstEnum;
 pub use functions::AstFunction;
 pub use macros::AstMacroCall;
 pub use structs::AstStruct;
+pub use types::AstTypeAlias;
 pub use uses::AstUse;
 
 use super::AstAttribute;
@@ -21,6 +25,40 @@ pub struct AstItemData {
     pub path: Vec<String>,
     pub ident: String,
     pub span: Span,
+    // Names of generic type parameters
+    pub generics: Vec<String>,
+    // Text of the item's doc comments, empty if undocumented
+    pub docs: String,
+}
+
+pub fn get_docs(attrs: &[syn::Attribute]) -> String {
+    attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                path,
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(doc),
+                        ..
+                    }),
+                ..
+            }) if path.is_ident("doc") => Some(doc.value()),
+            _ => None,
+        })
+        .map(|line| {
+            line.strip_prefix(' ')
+                .map_or(line.to_string(), |l| l.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn get_generic_params(generics: &syn::Generics) -> Vec<String> {
+    generics
+        .type_params()
+        .map(|p| p.ident.to_string())
+        .collect()
 }
 
 #[derive(Debug)]
@@ -29,6 +67,8 @@ pub struct AstItems {
     pub enums: Vec<AstEnum>,
     pub functions: Vec<AstFunction>,
     pub macro_calls: Vec<AstMacroCall>,
+    pub type_aliases: Vec<AstTypeAlias>,
+    pub engine_calls: Vec<AstEngineCall>,
 }
 
 impl AstItems {
@@ -38,6 +78,8 @@ impl AstItems {
             enums: Vec::new(),
             functions: Vec::new(),
             macro_calls: Vec::new(),
+            type_aliases: Vec::new(),
+            engine_calls: Vec::new(),
         }
     }
 
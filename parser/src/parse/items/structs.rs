@@ -2,9 +2,9 @@ use proc_macro2::{Span, TokenStream};
 use shared::{syn::error::CriticalResult, traits::PushInto};
 use syn::spanned::Spanned;
 
-use crate::parse::{attributes::get_attributes_if_active, AstAttribute, AstMod};
+use crate::parse::{attributes::get_attributes_if_active, cfg::CfgEnv, AstAttribute, AstMod};
 
-use super::AstItemData;
+use super::{get_docs, get_generic_params, AstItemData};
 
 #[derive(Debug)]
 pub struct AstStruct {
@@ -13,8 +13,8 @@ pub struct AstStruct {
 }
 
 impl AstMod {
-    pub fn visit_item_struct(&mut self, i: syn::ItemStruct) -> CriticalResult<()> {
-        if let Some(attrs) = get_attributes_if_active(&i.attrs, &self.path, &Vec::new())? {
+    pub fn visit_item_struct(&mut self, i: syn::ItemStruct, cfg: &CfgEnv) -> CriticalResult<()> {
+        if let Some(attrs) = get_attributes_if_active(&i.attrs, &self.path, cfg)? {
             if !attrs.is_empty() {
                 self.items.structs.push(AstStruct {
                     attrs,
@@ -22,6 +22,8 @@ impl AstMod {
                         path: self.path.to_vec().push_into(i.ident.to_string()),
                         ident: i.ident.to_string(),
                         span: i.span(),
+                        generics: get_generic_params(&i.generics),
+                        docs: get_docs(&i.attrs),
                     },
                 });
             }
@@ -0,0 +1,51 @@
+use shared::{syn::error::CriticalResult, traits::PushInto};
+use syn::spanned::Spanned;
+
+use crate::parse::{
+    attributes::get_attributes_if_active, cfg::CfgEnv, AliasSymbol, AstMod, Symbol, SymbolType,
+    Visibility,
+};
+
+use super::{get_docs, get_generic_params, AstItemData};
+
+#[derive(Debug)]
+pub struct AstTypeAlias {
+    pub ty: syn::Type,
+    pub data: AstItemData,
+    pub vis: Visibility,
+}
+
+impl AstMod {
+    // Type aliases
+    pub fn visit_item_type(&mut self, i: syn::ItemType, cfg: &CfgEnv) -> CriticalResult<()> {
+        if get_attributes_if_active(&i.attrs, &self.path, cfg)?.is_some() {
+            self.items.type_aliases.push(AstTypeAlias {
+                data: AstItemData {
+                    path: self.path.to_vec().push_into(i.ident.to_string()),
+                    ident: i.ident.to_string(),
+                    span: i.span(),
+                    generics: get_generic_params(&i.generics),
+                    docs: get_docs(&i.attrs),
+                },
+                vis: Visibility::from_syn(&i.vis, &self.path),
+                ty: *i.ty,
+            });
+        }
+        Ok(())
+    }
+
+    // Called once the crate and mod indices are known
+    pub fn add_type_alias_symbols(&mut self, cr_idx: usize) {
+        for (idx, alias) in self.items.type_aliases.iter().enumerate() {
+            self.symbols.push(Symbol {
+                kind: SymbolType::TypeAlias(AliasSymbol {
+                    cr_idx,
+                    m_idx: self.idx,
+                    idx,
+                }),
+                path: alias.data.path.to_vec(),
+                vis: alias.vis.clone(),
+            });
+        }
+    }
+}
@@ -1,49 +1,35 @@
-use diagnostic::{CatchErr, ErrorTrait, ResultsTrait};
+use diagnostic::{CatchErr, ErrorTrait, ResultsTrait, ToErr};
+use proc_macro2::Span;
 use shared::syn::{
     add_use_item,
     error::{CriticalResult, ToError},
 };
+use syn::spanned::Spanned;
 
-use crate::parse::{attributes::get_attributes_if_active, AstMod};
+use crate::parse::{attributes::get_attributes_if_active, cfg::CfgEnv, AstMod, Visibility};
 
+// Paths are absolute once `AstCrate::resolve_uses()` runs
+// They start with 'crate' or the name of an external crate
 #[derive(Clone, Debug)]
 pub struct AstUse {
     pub ident: String,
-    pub first: String,
     pub path: Vec<String>,
-    pub public: bool,
+    pub vis: Visibility,
+    pub span: Span,
 }
 
-impl AstUse {
-    pub fn from(mut path: Vec<String>, ident: &syn::Ident, vis: &syn::Visibility) -> Option<Self> {
-        path.first().cloned().map(|first| {
-            path.push(ident.to_string());
-            Self {
-                ident: ident.to_string(),
-                first,
-                path,
-                public: match vis {
-                    syn::Visibility::Public(_) => true,
-                    syn::Visibility::Restricted(_) | syn::Visibility::Inherited => false,
-                },
-            }
-        })
-    }
-}
-
-// TODO: Ambiguous paths, :: paths
-// TODO: type redeclarations
-
 impl AstMod {
-    pub fn visit_item_use(&mut self, i: syn::ItemUse) -> CriticalResult<()> {
-        if let Some(attrs) = get_attributes_if_active(&i.attrs, &self.path, &Vec::new())? {
+    pub fn visit_item_use(&mut self, i: syn::ItemUse, cfg: &CfgEnv) -> CriticalResult<()> {
+        if let Some(attrs) = get_attributes_if_active(&i.attrs, &self.path, cfg)? {
             let mut uses = Vec::new();
-            self.visit_use_tree(i.tree, &mut Vec::new(), &mut uses)?;
-            let public = match i.vis {
-                syn::Visibility::Public(_) => true,
-                syn::Visibility::Restricted(_) | syn::Visibility::Inherited => false,
+            // A leading '::' becomes an empty first segment
+            let mut path = match i.leading_colon {
+                Some(_) => vec![String::new()],
+                None => Vec::new(),
             };
-            uses.iter_mut().for_each(|u| u.public = public);
+            self.visit_use_tree(i.tree, &mut path, &mut uses)?;
+            let vis = Visibility::from_syn(&i.vis, &self.path);
+            uses.iter_mut().for_each(|u| u.vis = vis.clone());
             self.uses.append(&mut uses);
         }
 
@@ -87,12 +73,9 @@ impl AstMod {
                 .last()
                 .catch_err(i.error("Empty use path with 'self'"))?
                 .to_string(),
-            first: path
-                .first()
-                .catch_err(i.error("Empty use path with 'self'"))?
-                .to_string(),
             path: path.to_vec(),
-            public: false,
+            vis: Visibility::Restricted(self.path.to_vec()),
+            span: i.span(),
         });
         Ok(())
     }
@@ -103,14 +86,16 @@ impl AstMod {
         path: &mut Vec<String>,
         items: &mut Vec<AstUse>,
     ) -> CriticalResult<()> {
+        // E.g. use foo::{self as bar}
+        add_use_item(&self.path, path, i.ident.to_string());
+        if path.is_empty() {
+            return i.error("Empty use path with 'self'").as_err();
+        }
         items.push(AstUse {
             ident: i.rename.to_string(),
-            first: path
-                .first()
-                .catch_err(i.error("Empty use path with 'self'"))?
-                .to_string(),
-            path: [path.to_owned(), vec![i.ident.to_string()]].concat(),
-            public: false,
+            path: path.to_vec(),
+            vis: Visibility::Restricted(self.path.to_vec()),
+            span: i.span(),
         });
         Ok(())
     }
@@ -121,14 +106,14 @@ impl AstMod {
         path: &mut Vec<String>,
         items: &mut Vec<AstUse>,
     ) -> CriticalResult<()> {
+        if path.is_empty() {
+            return i.error("Empty use path with 'self'").as_err();
+        }
         items.push(AstUse {
             ident: "*".to_string(),
-            first: path
-                .first()
-                .catch_err(i.error("Empty use path with 'self'"))?
-                .to_string(),
             path: path.to_owned(),
-            public: false,
+            vis: Visibility::Restricted(self.path.to_vec()),
+            span: i.span(),
         });
         Ok(())
     }
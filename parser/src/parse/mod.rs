@@ -2,20 +2,31 @@ mod ast_crate;
 mod ast_file;
 mod ast_mod;
 mod attributes;
+mod cargo_metadata;
+mod cargo_toml;
+mod cfg;
 mod find_path;
 mod items;
 mod symbol;
+mod visibility;
 
 pub use ast_crate::AstCrate;
-pub use ast_mod::{AstMod, AstModType, NewMod};
+pub use ast_mod::{AstMod, AstModType, ModDecl, ModSource, NewMod};
 pub use attributes::AstAttribute;
-pub use find_path::{resolve_path, resolve_path_from_crate, resolve_syn_path, ItemPath};
+pub use cargo_toml::read_cargo_toml;
+pub use cfg::CfgEnv;
+pub use find_path::{
+    resolve_path, resolve_path_from_crate, resolve_syn_path, GenericArg, ItemPath, PathResolution,
+    STD_CRATES,
+};
 pub use items::*;
 use proc_macro2::Span;
 use shared::syn::error::{CriticalResult, Error, MutateResults, Note, StrToError, ToError};
 pub use symbol::{
-    ComponentSymbol, DiscardSymbol, GlobalSymbol, HardcodedSymbol, MatchSymbol, Symbol, SymbolType,
+    AliasSymbol, BuiltinSymbol, ComponentSymbol, DiscardSymbol, GlobalSymbol, HardcodedSymbol,
+    MatchSymbol, Symbol, SymbolType,
 };
+pub use visibility::Visibility;
 
 pub type ModInfo<'a> = (&'a AstMod, &'a AstCrate, &'a Vec<AstCrate>);
 pub type ModInfoMut<'a> = (&'a mut AstMod, &'a mut AstCrate, &'a Vec<AstCrate>);
@@ -6,7 +6,7 @@ use syn::spanned::Spanned;
 
 use crate::utils::paths::{CratePath, ENGINE_PATHS, MACRO_PATHS};
 
-use super::AstMod;
+use super::{AstMod, Visibility};
 
 use shared::{
     macros::{expand_enum, ExpandEnum},
@@ -16,7 +16,7 @@ use shared::{
 };
 
 // Symbol with path - Edit this to add new engine items
-#[derive(Eq, PartialEq)]
+#[derive(Eq, PartialEq, Hash)]
 #[expand_enum]
 pub enum HardcodedSymbol {
     // Macros crate
@@ -42,6 +42,36 @@ impl HardcodedSymbol {
     }
 }
 
+// Items from std that can be used in place of engine items
+#[derive(Eq, PartialEq, Hash)]
+#[expand_enum]
+pub enum BuiltinSymbol {
+    Vec,
+}
+
+impl BuiltinSymbol {
+    pub fn get_paths(&self) -> Vec<Vec<&'static str>> {
+        match self {
+            BuiltinSymbol::Vec => vec![vec!["std", "vec", "Vec"], vec!["alloc", "vec", "Vec"]],
+        }
+    }
+
+    // Names that are in scope without a use statement
+    pub fn get_prelude_name(&self) -> Option<&'static str> {
+        match self {
+            BuiltinSymbol::Vec => Some("Vec"),
+        }
+    }
+}
+
+// Location of the type alias in `AstMod::items`
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AliasSymbol {
+    pub cr_idx: usize,
+    pub m_idx: usize,
+    pub idx: usize,
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct ComponentSymbol {
     pub idx: usize,
@@ -66,6 +96,10 @@ pub enum SymbolType {
     System(usize, Span),
     ComponentSet(usize),
     Hardcoded(HardcodedSymbol),
+    Builtin(BuiltinSymbol),
+    TypeAlias(AliasSymbol),
+    // Index into `Items::generics`, must be instantiated before use
+    Generic(usize),
 }
 
 impl std::fmt::Display for SymbolType {
@@ -79,6 +113,9 @@ impl std::fmt::Display for SymbolType {
             SymbolType::System(..) => "System",
             SymbolType::ComponentSet(..) => "ComponentSet",
             SymbolType::Hardcoded(..) => "Hardcoded Path",
+            SymbolType::Builtin(..) => "Builtin Type",
+            SymbolType::TypeAlias(..) => "Type Alias",
+            SymbolType::Generic(..) => "Generic Item",
         })
     }
 }
@@ -87,7 +124,7 @@ impl std::fmt::Display for SymbolType {
 pub struct Symbol {
     pub kind: SymbolType,
     pub path: Vec<String>,
-    pub public: bool,
+    pub vis: Visibility,
 }
 
 // Need &dyn for type alias and passing 'None'
@@ -106,60 +143,81 @@ where
     fn and_then_impl<T>(self, f: impl FnOnce(&'a Symbol) -> CriticalResult<T>)
         -> CriticalResult<T>;
 
+    // Adds close matches accepted by `is_expected` to unresolved path errors
+    fn with_suggestions(self, _is_expected: impl Fn(&SymbolType) -> bool) -> Self {
+        self
+    }
+
     fn expect_component(self) -> CriticalResult<(&'a Symbol, ComponentSymbol)> {
-        self.and_then_impl(|arg| match arg.kind {
-            SymbolType::Component(c_sym) => Ok((arg, c_sym)),
-            _ => arg.error("Component").as_err(),
-        })
+        self.with_suggestions(|k| matches!(k, SymbolType::Component(_)))
+            .and_then_impl(|arg| match arg.kind {
+                SymbolType::Component(c_sym) => Ok((arg, c_sym)),
+                _ => arg.error("Component").as_err(),
+            })
     }
 
     fn expect_global(self) -> CriticalResult<(&'a Symbol, GlobalSymbol)> {
-        self.and_then_impl(|arg| match arg.kind {
-            SymbolType::Global(g_sym) => Ok((arg, g_sym)),
-            _ => arg.error("Global").as_err(),
-        })
+        self.with_suggestions(|k| matches!(k, SymbolType::Global(_)))
+            .and_then_impl(|arg| match arg.kind {
+                SymbolType::Global(g_sym) => Ok((arg, g_sym)),
+                _ => arg.error("Global").as_err(),
+            })
     }
 
     fn expect_trait(self) -> CriticalResult<(&'a Symbol, GlobalSymbol)> {
-        self.and_then_impl(|arg| match arg.kind {
-            SymbolType::Trait(g_sym) => Ok((arg, g_sym)),
-            _ => arg.error("Trait").as_err(),
-        })
+        self.with_suggestions(|k| matches!(k, SymbolType::Trait(_)))
+            .and_then_impl(|arg| match arg.kind {
+                SymbolType::Trait(g_sym) => Ok((arg, g_sym)),
+                _ => arg.error("Trait").as_err(),
+            })
     }
 
     fn expect_global_or_trait(self) -> CriticalResult<(&'a Symbol, GlobalSymbol)> {
-        self.and_then_impl(|arg| match arg.kind {
-            SymbolType::Global(g_sym) | SymbolType::Trait(g_sym) => Ok((arg, g_sym)),
-            _ => arg.error("Global or Trait").as_err(),
-        })
+        self.with_suggestions(|k| matches!(k, SymbolType::Global(_) | SymbolType::Trait(_)))
+            .and_then_impl(|arg| match arg.kind {
+                SymbolType::Global(g_sym) | SymbolType::Trait(g_sym) => Ok((arg, g_sym)),
+                _ => arg.error("Global or Trait").as_err(),
+            })
     }
 
     fn expect_event(self) -> CriticalResult<(&'a Symbol, usize)> {
-        self.and_then_impl(|arg| match arg.kind {
-            SymbolType::Event(i) => Ok((arg, i)),
-            _ => arg.error("Event").as_err(),
-        })
+        self.with_suggestions(|k| matches!(k, SymbolType::Event(_)))
+            .and_then_impl(|arg| match arg.kind {
+                SymbolType::Event(i) => Ok((arg, i)),
+                _ => arg.error("Event").as_err(),
+            })
     }
 
     fn expect_state(self) -> CriticalResult<(&'a Symbol, usize)> {
-        self.and_then_impl(|arg| match arg.kind {
-            SymbolType::State(i) => Ok((arg, i)),
-            _ => arg.error("State").as_err(),
-        })
+        self.with_suggestions(|k| matches!(k, SymbolType::State(_)))
+            .and_then_impl(|arg| match arg.kind {
+                SymbolType::State(i) => Ok((arg, i)),
+                _ => arg.error("State").as_err(),
+            })
     }
 
     fn expect_system(self) -> CriticalResult<(&'a Symbol, (usize, Span))> {
-        self.and_then_impl(|arg| match arg.kind {
-            SymbolType::System(i, span) => Ok((arg, (i, span))),
-            _ => arg.error("System").as_err(),
-        })
+        self.with_suggestions(|k| matches!(k, SymbolType::System(..)))
+            .and_then_impl(|arg| match arg.kind {
+                SymbolType::System(i, span) => Ok((arg, (i, span))),
+                _ => arg.error("System").as_err(),
+            })
     }
 
     fn expect_component_set(self) -> CriticalResult<(&'a Symbol, usize)> {
-        self.and_then_impl(|arg| match arg.kind {
-            SymbolType::ComponentSet(i) => Ok((arg, i)),
-            _ => arg.error("Component Set").as_err(),
-        })
+        self.with_suggestions(|k| matches!(k, SymbolType::ComponentSet(_)))
+            .and_then_impl(|arg| match arg.kind {
+                SymbolType::ComponentSet(i) => Ok((arg, i)),
+                _ => arg.error("Component Set").as_err(),
+            })
+    }
+
+    fn expect_type_alias(self) -> CriticalResult<(&'a Symbol, AliasSymbol)> {
+        self.with_suggestions(|k| matches!(k, SymbolType::TypeAlias(_)))
+            .and_then_impl(|arg| match arg.kind {
+                SymbolType::TypeAlias(a_sym) => Ok((arg, a_sym)),
+                _ => arg.error("Type Alias").as_err(),
+            })
     }
 
     fn expect_any_hardcoded(self) -> CriticalResult<(&'a Symbol, HardcodedSymbol)> {
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use shared::syn::use_path_from_syn;
+
+// Where an item can be named from
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Visibility {
+    Public,
+    // Visible inside this mod of the same crate, including child mods
+    // E.g. pub(crate) is ["crate"], private items use the path of their own mod
+    Restricted(Vec<String>),
+}
+
+impl Visibility {
+    // `mod_path` is the path of the mod containing the item
+    pub fn from_syn(vis: &syn::Visibility, mod_path: &Vec<String>) -> Self {
+        match vis {
+            syn::Visibility::Public(_) => Self::Public,
+            syn::Visibility::Restricted(r) => {
+                Self::Restricted(use_path_from_syn(mod_path, &r.path))
+            }
+            syn::Visibility::Inherited => Self::Restricted(mod_path.to_vec()),
+        }
+    }
+
+    pub fn is_visible_from(&self, item_cr_idx: usize, cr_idx: usize, mod_path: &[String]) -> bool {
+        match self {
+            Visibility::Public => true,
+            Visibility::Restricted(path) => item_cr_idx == cr_idx && mod_path.starts_with(path),
+        }
+    }
+}
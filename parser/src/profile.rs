@@ -0,0 +1,244 @@
+use std::{
+    cell::RefCell,
+    fmt::Display,
+    fs,
+    time::{Duration, Instant},
+};
+
+use diagnostic::CatchErr;
+use proc_macro2::TokenStream;
+use serde::Serialize;
+
+use shared::{
+    syn::error::{CriticalResult, StrToError},
+    traits::{CollectVec, CollectVecInto},
+};
+
+use crate::{
+    codegen::{get_codegen_dir, Crates},
+    utils::paths::Crate,
+};
+
+const PROFILE_FILE: &str = "hyperfold_profile.json";
+// Increment when the report format changes
+const PROFILE_VERSION: u32 = 1;
+// Number of modules listed in the warning summary
+const SLOWEST_MODS: usize = 3;
+
+// Set with HYPERFOLD_PROFILE
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProfileMode {
+    // Write hyperfold_profile.json to OUT_DIR
+    Report,
+    // Also print a summary as a cargo warning
+    Warn,
+}
+
+#[derive(Serialize)]
+struct Timing {
+    name: String,
+    ms: f64,
+}
+
+impl Timing {
+    fn new(name: impl Display, time: Duration) -> Self {
+        Self {
+            name: name.to_string(),
+            ms: time.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ModProfile {
+    path: String,
+    file: String,
+    parse_ms: f64,
+    symbols: usize,
+}
+
+#[derive(Serialize)]
+struct CrateProfile {
+    name: String,
+    // Total time reading and parsing the crate's files
+    parse_ms: f64,
+    symbols: usize,
+    mods: Vec<ModProfile>,
+}
+
+#[derive(Serialize)]
+struct CodeSize {
+    name: String,
+    bytes: usize,
+}
+
+#[derive(Serialize, Default)]
+struct ProfileReport {
+    version: u32,
+    total_ms: f64,
+    steps: Vec<Timing>,
+    crates: Vec<CrateProfile>,
+    resolve_passes: Vec<Timing>,
+    // Unformatted size of each part of the main crate's codegen
+    // Empty if the previous codegen was reused
+    codegen_sections: Vec<CodeSize>,
+    // Formatted size of each crate's generated file
+    codegen_files: Vec<CodeSize>,
+}
+
+// Times the steps of the build script and prints them to stderr
+// With a `ProfileMode`, also collects a report of where the time went
+pub struct Profiler {
+    mode: Option<ProfileMode>,
+    print: bool,
+    start: Instant,
+    last_step: RefCell<Instant>,
+    last_pass: RefCell<Instant>,
+    report: RefCell<ProfileReport>,
+}
+
+impl Profiler {
+    pub fn new(mode: Option<ProfileMode>) -> Self {
+        Self {
+            mode,
+            print: true,
+            start: Instant::now(),
+            last_step: RefCell::new(Instant::now()),
+            last_pass: RefCell::new(Instant::now()),
+            report: RefCell::new(ProfileReport {
+                version: PROFILE_VERSION,
+                ..Default::default()
+            }),
+        }
+    }
+
+    // Nothing is printed or collected
+    pub fn disabled() -> Self {
+        Self {
+            print: false,
+            ..Self::new(None)
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.mode.is_some()
+    }
+
+    pub fn step(&self, msg: impl Display) {
+        let time = self.last_step.replace(Instant::now()).elapsed();
+        *self.last_pass.borrow_mut() = Instant::now();
+        if self.print {
+            eprintln!("{msg}: {time:#?}");
+        }
+        if self.is_enabled() {
+            self.report.borrow_mut().steps.push(Timing::new(msg, time));
+        }
+    }
+
+    // Time since the previous pass or step
+    pub fn pass(&self, name: &str) {
+        let time = self.last_pass.replace(Instant::now()).elapsed();
+        if self.is_enabled() {
+            self.report
+                .borrow_mut()
+                .resolve_passes
+                .push(Timing::new(name, time));
+        }
+    }
+
+    pub fn codegen_section(&self, name: &str, code: Option<&TokenStream>) {
+        if let (true, Some(code)) = (self.is_enabled(), code) {
+            self.report.borrow_mut().codegen_sections.push(CodeSize {
+                name: name.to_string(),
+                bytes: code.to_string().len(),
+            });
+        }
+    }
+
+    pub fn codegen_file(&self, name: &str, code: &str) {
+        if self.is_enabled() {
+            self.report.borrow_mut().codegen_files.push(CodeSize {
+                name: name.to_string(),
+                bytes: code.len(),
+            });
+        }
+    }
+
+    // Records parse times and symbol counts, call after resolving items
+    pub fn add_crates(&self, crates: &Crates) {
+        if !self.is_enabled() {
+            return;
+        }
+        let macro_cr_idx = crates.get_crate_index(Crate::Macros);
+        self.report.borrow_mut().crates = crates
+            .iter_except([macro_cr_idx])
+            .map(|cr| {
+                let mods = cr.iter_mods().map_vec_into(|m| ModProfile {
+                    path: m.path.join("::"),
+                    file: m.get_file(),
+                    parse_ms: m.parse_time.as_secs_f64() * 1000.0,
+                    symbols: m.symbols.len(),
+                });
+                CrateProfile {
+                    name: cr.name.to_string(),
+                    parse_ms: mods.iter().map(|m| m.parse_ms).sum(),
+                    symbols: mods.iter().map(|m| m.symbols).sum(),
+                    mods,
+                }
+            })
+            .collect();
+    }
+
+    pub fn finish(&self, msg: impl Display) {
+        if self.print {
+            eprintln!("{msg}: {:#?}", self.start.elapsed());
+        }
+    }
+
+    // Writes the report to OUT_DIR and prints the summary if requested
+    pub fn write_report(&self) -> CriticalResult<()> {
+        let Some(mode) = self.mode else {
+            return Ok(());
+        };
+        let mut report = self.report.borrow_mut();
+        report.total_ms = self.start.elapsed().as_secs_f64() * 1000.0;
+        if mode == ProfileMode::Warn {
+            for line in summarize(&report) {
+                println!("cargo:warning={line}");
+            }
+        }
+
+        let file = get_codegen_dir()?.join(PROFILE_FILE);
+        let data = serde_json::to_string_pretty(&*report)
+            .catch_err("Could not serialize profile".trace())?;
+        fs::write(&file, data).catch_err(format!("Could not write to: {}", file.display()).trace())
+    }
+}
+
+fn summarize(report: &ProfileReport) -> Vec<String> {
+    let mut lines = vec![format!("Hyperfold build took {:.1}ms", report.total_ms)];
+    lines.extend(
+        report
+            .steps
+            .map_vec(|step| format!("  {}: {:.1}ms", step.name, step.ms)),
+    );
+
+    let mut mods = report
+        .crates
+        .iter()
+        .flat_map(|cr| cr.mods.iter())
+        .collect::<Vec<_>>();
+    mods.sort_by(|m1, m2| m2.parse_ms.total_cmp(&m1.parse_ms));
+    if !mods.is_empty() {
+        lines.push("  Slowest modules:".to_string());
+        lines.extend(
+            mods.iter()
+                .take(SLOWEST_MODS)
+                .map(|m| format!("    {}: {:.1}ms", m.file, m.parse_ms)),
+        );
+    }
+
+    let bytes = report.codegen_files.iter().map(|f| f.bytes).sum::<usize>();
+    lines.push(format!("  Generated {bytes} bytes"));
+    lines
+}
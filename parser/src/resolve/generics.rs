@@ -0,0 +1,323 @@
+use diagnostic::{CatchErr, CombineResults, ToErr};
+use proc_macro2::Span;
+use syn::spanned::Spanned;
+
+use shared::{
+    parsing::{ComponentMacroArgs, EventMacroArgs, GlobalMacroArgs},
+    syn::{
+        error::{CriticalResult, GetVec, StrToError, ToError},
+        get_type_generics, parse_tokens, use_path_from_syn,
+    },
+    traits::{CollectVec, CollectVecInto, GetSlice},
+};
+
+use crate::{
+    codegen::Crates,
+    component_set::AstComponentSet,
+    parse::{
+        resolve_path, resolve_syn_path, GenericArg, HardcodedSymbol, ItemPath, MatchSymbol,
+        ModInfo, Symbol, SymbolType, Visibility,
+    },
+    utils::paths::Crate,
+};
+
+use super::{ItemComponent, ItemData, ItemEvent, ItemGlobal, Items};
+
+// Types that are in scope without a use statement
+const PRELUDE_TYPES: [&str; 21] = [
+    "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64",
+    "i128", "isize", "f32", "f64", "String", "Vec", "Option", "Box",
+];
+
+#[derive(Copy, Clone, Debug, Hash)]
+pub enum GenericKind {
+    Component(ComponentMacroArgs),
+    Global(GlobalMacroArgs),
+    Event(EventMacroArgs),
+}
+
+// Generic component, global, or event
+// These have no storage until instantiated with concrete arguments
+#[derive(Clone, Debug)]
+pub struct ItemGeneric {
+    pub data: ItemData,
+    pub kind: GenericKind,
+    pub params: Vec<String>,
+}
+
+// Concrete instantiation of a generic item
+#[derive(Debug)]
+pub struct ItemInstance {
+    pub generic: usize,
+    pub args: Vec<GenericArg>,
+    pub symbol: Symbol,
+}
+
+pub fn get_type_args(ty: &syn::TypePath) -> Vec<syn::Type> {
+    get_type_generics(ty).map_or(Vec::new(), |args| {
+        args.into_iter()
+            .filter_map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Some(ty.clone()),
+                _ => None,
+            })
+            .collect()
+    })
+}
+
+// Resolves a generic argument to a path that can be named from other crates
+// Arguments don't have to be engine items, so only mods, uses, and dependencies are followed
+pub fn resolve_generic_arg(ty: &syn::Type, (m, cr, crates): ModInfo) -> CriticalResult<GenericArg> {
+    let p = match ty {
+        syn::Type::Path(p) if p.qself.is_none() => p,
+        _ => return ty.error("Generic arguments must be type paths").as_err(),
+    };
+    let generics = get_type_args(p)
+        .map_vec(|ty| resolve_generic_arg(ty, (m, cr, crates)))
+        .combine_results()?;
+    let path = use_path_from_syn(&m.path, &p.path);
+    resolve_arg_path(path, ty.span(), (m, cr, crates)).map(|(cr_idx, path)| match cr_idx {
+        Some(cr_idx) => GenericArg::Item(ItemPath::new(cr_idx, path).with_generics(generics)),
+        None => GenericArg::Builtin(path.join("::"), generics),
+    })
+}
+
+// Returns the crate index and full path of the argument, no crate for builtin types
+// Crates which aren't engine crates, e.g. std, only have builtin types
+fn resolve_arg_path(
+    path: Vec<String>,
+    span: Span,
+    (m, cr, _): ModInfo,
+) -> CriticalResult<(Option<usize>, Vec<String>)> {
+    let first = path
+        .first()
+        .catch_err("Empty generic argument path".trace())?;
+    match cr.resolve_path(&path, m, span)? {
+        Some(path) => match path.first() {
+            Some(first) if first == "crate" => Ok((Some(cr.idx), path)),
+            first => Ok(
+                match cr.deps.iter().find(|(_, alias)| first == Some(alias)) {
+                    Some((idx, _)) => (
+                        Some(*idx),
+                        [vec!["crate".to_string()], path[1..].to_vec()].concat(),
+                    ),
+                    None => (None, path),
+                },
+            ),
+        },
+        // Prelude
+        None if path.len() == 1 && PRELUDE_TYPES.contains(&first.as_str()) => Ok((None, path)),
+        // Item in this mod
+        None => Ok((Some(cr.idx), [m.path.to_vec(), path].concat())),
+    }
+}
+
+// Instantiations are discovered from type aliases, component sets, system arguments, and engine calls
+// Engine calls only name an instance if their generics are written, e.g. `Foo::<u32>::new()`
+fn find_instances(crates: &Crates) -> Vec<(usize, Vec<GenericArg>)> {
+    let macro_cr_idx = crates.get_crate_index(Crate::Macros);
+    let all_crates = crates.get_crates();
+    let mut instances = Vec::new();
+
+    for cr in crates.iter_except([macro_cr_idx]) {
+        for m in cr.iter_mods() {
+            let info = (m, cr, all_crates);
+            // Generic aliases are instantiated where they are used
+            for alias in &m.items.type_aliases {
+                if alias.data.generics.is_empty() {
+                    find_type_instances(&alias.ty, info, &mut instances);
+                }
+            }
+            for call in &m.items.macro_calls {
+                if resolve_path(call.data.path.to_vec(), info)
+                    .expect_hardcoded(HardcodedSymbol::ComponentsMacro)
+                    .is_err()
+                {
+                    continue;
+                }
+                if let Ok(cs) = parse_tokens::<AstComponentSet>(call.args.clone()) {
+                    for arg in cs.args {
+                        add_instance(
+                            resolve_path(arg.ty.path.to_vec(), info).into_result(),
+                            &arg.generics,
+                            info,
+                            &mut instances,
+                        );
+                    }
+                }
+            }
+            for fun in &m.items.functions {
+                if !fun.data.generics.is_empty()
+                    || !fun.attrs.iter().any(|attr| {
+                        resolve_path(attr.path.to_vec(), info)
+                            .expect_hardcoded(HardcodedSymbol::SystemMacro)
+                            .is_ok()
+                    })
+                {
+                    continue;
+                }
+                for arg in &fun.sig.inputs {
+                    if let syn::FnArg::Typed(syn::PatType { ty, .. }) = arg {
+                        find_type_instances(ty, info, &mut instances);
+                    }
+                }
+            }
+            for call in &m.items.engine_calls {
+                if call.generics.is_empty() {
+                    continue;
+                }
+                // Enum variants and constructors name their type without the last segment
+                if let Some(sym) = [call.path.to_vec(), call.path.slice_to(-1).to_vec()]
+                    .into_iter()
+                    .find_map(|path| resolve_path(path, info).into_result().ok())
+                {
+                    add_instance(Ok(sym), &call.generics, info, &mut instances);
+                }
+            }
+        }
+    }
+
+    instances
+}
+
+fn find_type_instances(
+    ty: &syn::Type,
+    (m, cr, crates): ModInfo,
+    instances: &mut Vec<(usize, Vec<GenericArg>)>,
+) {
+    match ty {
+        syn::Type::Path(p) => {
+            let args = get_type_args(p);
+            for arg in &args {
+                find_type_instances(arg, (m, cr, crates), instances);
+            }
+            if !args.is_empty() {
+                add_instance(
+                    resolve_syn_path(&m.path, &p.path, (m, cr, crates)).into_result(),
+                    &args,
+                    (m, cr, crates),
+                    instances,
+                );
+            }
+        }
+        syn::Type::Reference(syn::TypeReference { elem, .. })
+        | syn::Type::Paren(syn::TypeParen { elem, .. })
+        | syn::Type::Group(syn::TypeGroup { elem, .. })
+        | syn::Type::Slice(syn::TypeSlice { elem, .. })
+        | syn::Type::Array(syn::TypeArray { elem, .. }) => {
+            find_type_instances(elem, (m, cr, crates), instances)
+        }
+        syn::Type::Tuple(t) => {
+            for ty in &t.elems {
+                find_type_instances(ty, (m, cr, crates), instances);
+            }
+        }
+        _ => (),
+    }
+}
+
+// Errors are ignored, they are reported when the item is resolved
+fn add_instance(
+    sym: CriticalResult<&Symbol>,
+    args: &Vec<syn::Type>,
+    info: ModInfo,
+    instances: &mut Vec<(usize, Vec<GenericArg>)>,
+) {
+    if let Ok(Symbol {
+        kind: SymbolType::Generic(g_idx),
+        ..
+    }) = sym
+    {
+        if let Ok(args) = args
+            .map_vec(|ty| resolve_generic_arg(ty, info))
+            .combine_results()
+        {
+            instances.push((*g_idx, args));
+        }
+    }
+}
+
+impl Items {
+    pub(super) fn add_generic(&mut self, generic: ItemGeneric) -> Symbol {
+        let sym = Symbol {
+            kind: SymbolType::Generic(self.generics.len()),
+            path: generic.data.path.path.to_vec(),
+            vis: Visibility::Public,
+        };
+        self.generics.push(generic);
+        sym
+    }
+
+    // Each instantiation gets its own component, global, or event
+    pub(super) fn add_instances(&mut self, crates: &Crates) -> CriticalResult<()> {
+        for (generic, args) in find_instances(crates) {
+            if self
+                .instances
+                .iter()
+                .any(|i| i.generic == generic && i.args == args)
+            {
+                continue;
+            }
+
+            let ItemGeneric { data, kind, .. } = self.generics.try_get(generic)?.clone();
+            let data = ItemData {
+                path: data.path.with_generics(args.to_vec()),
+                ..data
+            };
+            let symbol = match kind {
+                GenericKind::Component(args) => self.add_component(ItemComponent { data, args }),
+                GenericKind::Global(args) => self.add_global(ItemGlobal { data, args }),
+                GenericKind::Event(args) => self.add_event(ItemEvent {
+                    data,
+                    args,
+                    state: None,
+                }),
+            };
+            self.instances.push(ItemInstance {
+                generic,
+                args,
+                symbol,
+            });
+        }
+        Ok(())
+    }
+
+    // Replaces a generic symbol with the symbol of its instantiation
+    pub fn resolve_instance<'a>(
+        &'a self,
+        sym: &'a Symbol,
+        args: &Vec<syn::Type>,
+        info: ModInfo,
+    ) -> CriticalResult<&'a Symbol> {
+        let g_idx = match sym.kind {
+            SymbolType::Generic(g_idx) => g_idx,
+            _ => return Ok(sym),
+        };
+
+        let generic = self.generics.try_get(g_idx)?;
+        if args.len() != generic.params.len() {
+            return format!(
+                "Expected {} generic arguments for {} but found {}",
+                generic.params.len(),
+                generic.data.path.to_string(),
+                args.len()
+            )
+            .error()
+            .as_err();
+        }
+
+        let args = args
+            .map_vec(|ty| resolve_generic_arg(ty, info))
+            .combine_results()?;
+        self.instances
+            .iter()
+            .find(|i| i.generic == g_idx && i.args == args)
+            .map(|i| &i.symbol)
+            .catch_err(
+                format!(
+                    "Could not find instantiation: {}",
+                    generic.data.path.clone().with_generics(args).to_string()
+                )
+                .trace(),
+            )
+    }
+}
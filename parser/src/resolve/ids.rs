@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use shared::{
+    syn::error::{Error, StrToError},
+    traits::{CollectVec, CollectVecInto},
+};
+
+use crate::{codegen::Crates, parse::ItemPath};
+
+use super::Items;
+
+// Hashes of each item's path from the main crate, used to name generated code
+// Unlike indices these don't change when other items are added, removed, or reordered
+#[derive(Debug, Default)]
+pub struct ItemIds {
+    pub components: Vec<u32>,
+    pub globals: Vec<u32>,
+    pub events: Vec<u32>,
+    pub states: Vec<u32>,
+    pub component_sets: Vec<u32>,
+}
+
+impl ItemIds {
+    pub fn new(items: &Items, crates: &Crates) -> Result<Self, Vec<Error>> {
+        let mut errs = Vec::new();
+        // Names only need to be unique within each kind of item
+        let mut get_ids = |paths: Vec<&ItemPath>| {
+            let mut seen = HashMap::new();
+            paths.map_vec_into(|path| {
+                let name = crates.get_item_name(path);
+                let id = hash(&name);
+                match seen.insert(id, name.clone()) {
+                    Some(other) if other != name => errs.push(
+                        format!("Generated name for '{name}' collides with '{other}', rename one of them")
+                            .error(),
+                    ),
+                    _ => (),
+                }
+                id
+            })
+        };
+
+        let ids = Self {
+            components: get_ids(items.components.map_vec(|c| &c.data.path)),
+            globals: get_ids(items.globals.map_vec(|g| &g.data.path)),
+            events: get_ids(items.events.map_vec(|e| &e.data.path)),
+            states: get_ids(items.states.map_vec(|s| &s.data.path)),
+            component_sets: get_ids(items.component_sets.map_vec(|cs| &cs.path)),
+        };
+        match errs.is_empty() {
+            true => Ok(ids),
+            false => Err(errs),
+        }
+    }
+
+    pub fn component(&self, c_idx: usize) -> u32 {
+        self.components.get(c_idx).copied().unwrap_or_default()
+    }
+
+    pub fn global(&self, g_idx: usize) -> u32 {
+        self.globals.get(g_idx).copied().unwrap_or_default()
+    }
+
+    pub fn event(&self, e_idx: usize) -> u32 {
+        self.events.get(e_idx).copied().unwrap_or_default()
+    }
+
+    pub fn state(&self, s_idx: usize) -> u32 {
+        self.states.get(s_idx).copied().unwrap_or_default()
+    }
+
+    pub fn component_set(&self, cs_idx: usize) -> u32 {
+        self.component_sets.get(cs_idx).copied().unwrap_or_default()
+    }
+}
+
+// FNV-1a, unlike DefaultHasher its output is fixed across Rust versions
+fn hash(name: &str) -> u32 {
+    name.bytes()
+        .fold(0x811c9dc5, |h, b| (h ^ b as u32).wrapping_mul(0x01000193))
+}
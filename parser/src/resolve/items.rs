@@ -1,17 +1,24 @@
-use diagnostic::{err, CatchErr, CombineResults, ErrForEach, ErrorSpan, ErrorTrait, ResultsTrait};
+use diagnostic::{
+    err, CatchErr, CombineResults, ErrForEach, ErrorSpan, ErrorTrait, ResultsTrait, ToErr,
+};
 use proc_macro2::{token_stream::IntoIter, Span, TokenStream, TokenTree};
 use quote::{quote, ToTokens};
 use std::{collections::VecDeque, env::temp_dir, fs, path::PathBuf};
 use syn::{parenthesized, parse_macro_input, spanned::Spanned, token::Trait, PatType, Token};
 
+use super::{
+    generics::{GenericKind, ItemGeneric, ItemInstance},
+    ids::ItemIds,
+};
 use crate::{
     codegen::{self as codegen, Crates, Traits},
     component_set::ComponentSet,
     parse::{
         resolve_path, AstCrate, AstFunction, AstItemData, AstItems, AstMod, AstModType, AstUse,
         ComponentSymbol, DiscardSymbol, GlobalSymbol, HardcodedSymbol, ItemPath, MatchSymbol,
-        ModInfo, NewMod, Symbol, SymbolType,
+        ModInfo, NewMod, Symbol, SymbolType, Visibility,
     },
+    profile::Profiler,
     system::ItemSystem,
     utils::{
         constants::NAMESPACE,
@@ -22,9 +29,9 @@ use crate::{
 use shared::{
     constants::{INDEX, INDEX_SEP, STATE_DATA, STATE_ENTER_EVENT, STATE_EXIT_EVENT, STATE_LABEL},
     macros::ExpandEnum,
-    parsing::{ComponentMacroArgs, GlobalMacroArgs, SystemMacroArgs},
+    parsing::{ComponentMacroArgs, EventMacroArgs, GlobalMacroArgs, SystemMacroArgs},
     syn::{
-        error::{CriticalResult, Error, MutateResults, WarningResult},
+        error::{CriticalResult, Error, MutateResults, ToError, WarningResult},
         parse_tokens,
     },
     traits::{
@@ -54,6 +61,7 @@ pub struct ItemTrait {
 #[derive(Debug)]
 pub struct ItemEvent {
     pub data: ItemData,
+    pub args: EventMacroArgs,
     pub state: Option<usize>,
 }
 
@@ -71,6 +79,7 @@ pub struct ItemData {
     pub path: ItemPath,
     pub mod_idx: usize,
     pub span: Span,
+    pub docs: String,
 }
 
 impl ItemData {
@@ -79,11 +88,14 @@ impl ItemData {
             path: ItemPath::new(cr_idx, item.path.to_vec()),
             mod_idx,
             span: item.span,
+            docs: item.docs.to_string(),
         }
     }
 
+    // The doc comment describes the parent item, not the generated one
     pub fn add_path(mut self, segment: &str) -> Self {
         self.path.path.push(segment.to_string());
+        self.docs.clear();
         self
     }
 }
@@ -95,6 +107,32 @@ enum NewItem {
     State(AstItemData),
     ComponentSet(ComponentSet),
     System(ItemSystem),
+    Generic(ItemGeneric),
+}
+
+impl NewItem {
+    // Generic items are added as templates and instantiated later
+    fn or_generic(self, item: &AstItemData) -> CriticalResult<Self> {
+        if item.generics.is_empty() {
+            return Ok(self);
+        }
+        let kind = match &self {
+            NewItem::Component(c) => GenericKind::Component(c.args),
+            NewItem::Global(g) => GenericKind::Global(g.args),
+            NewItem::Event(e) => GenericKind::Event(e.args),
+            _ => return item.span.error("This item may not be generic").as_err(),
+        };
+        Ok(match self {
+            NewItem::Component(ItemComponent { data, .. })
+            | NewItem::Global(ItemGlobal { data, .. })
+            | NewItem::Event(ItemEvent { data, .. }) => NewItem::Generic(ItemGeneric {
+                data,
+                kind,
+                params: item.generics.to_vec(),
+            }),
+            item => item,
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -105,6 +143,9 @@ pub struct Items {
     pub states: Vec<ItemState>,
     pub component_sets: Vec<ComponentSet>,
     pub systems: Vec<ItemSystem>,
+    pub generics: Vec<ItemGeneric>,
+    pub instances: Vec<ItemInstance>,
+    pub ids: ItemIds,
 }
 
 impl Items {
@@ -116,10 +157,13 @@ impl Items {
             states: Vec::new(),
             component_sets: Vec::new(),
             systems: Vec::new(),
+            generics: Vec::new(),
+            instances: Vec::new(),
+            ids: ItemIds::default(),
         }
     }
 
-    fn add_component(&mut self, comp: ItemComponent) -> Symbol {
+    pub(super) fn add_component(&mut self, comp: ItemComponent) -> Symbol {
         let args = comp.args;
         let span = comp.data.span;
         let path = comp.data.path.path.clone();
@@ -131,11 +175,11 @@ impl Items {
                 span,
             }),
             path,
-            public: true,
+            vis: Visibility::Public,
         }
     }
 
-    fn add_global(&mut self, global: ItemGlobal) -> Symbol {
+    pub(super) fn add_global(&mut self, global: ItemGlobal) -> Symbol {
         let args = global.args;
         let span = global.data.span;
         let path = global.data.path.path.clone();
@@ -147,17 +191,17 @@ impl Items {
                 span,
             }),
             path,
-            public: true,
+            vis: Visibility::Public,
         }
     }
 
-    fn add_event(&mut self, event: ItemEvent) -> Symbol {
+    pub(super) fn add_event(&mut self, event: ItemEvent) -> Symbol {
         let path = event.data.path.path.clone();
         self.events.push(event);
         Symbol {
             kind: SymbolType::Event(self.events.len() - 1),
             path,
-            public: true,
+            vis: Visibility::Public,
         }
     }
 
@@ -166,11 +210,13 @@ impl Items {
         let symbols = vec![
             // OnEnter
             self.add_event(ItemEvent {
+                args: EventMacroArgs::default(),
                 state: Some(state_idx),
                 data: ItemData::from_ast(cr_idx, mod_idx, item).add_path(STATE_ENTER_EVENT),
             }),
             // OnExit
             self.add_event(ItemEvent {
+                args: EventMacroArgs::default(),
                 state: Some(state_idx),
                 data: ItemData::from_ast(cr_idx, mod_idx, item).add_path(STATE_EXIT_EVENT),
             }),
@@ -182,10 +228,7 @@ impl Items {
             {
                 let data_path = item.path.to_vec().push_into(STATE_DATA.to_string());
                 self.states.push(ItemState {
-                    data_path: ItemPath {
-                        cr_idx,
-                        path: data_path.to_vec(),
-                    },
+                    data_path: ItemPath::new(cr_idx, data_path.to_vec()),
                     enter_event: self.events.len() - 2,
                     exit_event: self.events.len() - 1,
                     label: self.components.len() - 1,
@@ -194,7 +237,7 @@ impl Items {
                 Symbol {
                     kind: SymbolType::State(state_idx),
                     path: data_path,
-                    public: true,
+                    vis: Visibility::Public,
                 }
             },
         ];
@@ -211,7 +254,7 @@ impl Items {
         let sym = Symbol {
             kind: SymbolType::ComponentSet(self.component_sets.len()),
             path: cs.path.path.to_vec(),
-            public: true,
+            vis: Visibility::Public,
         };
         self.component_sets.push(cs);
         sym
@@ -221,7 +264,7 @@ impl Items {
         let sym = Symbol {
             kind: SymbolType::System(self.systems.len(), sys.span.span),
             path: sys.path.path.to_vec(),
-            public: true,
+            vis: Visibility::Public,
         };
         self.systems.push(sys);
         sym
@@ -269,6 +312,7 @@ impl Items {
                         }
                         NewItem::ComponentSet(cs) => self.add_component_set(cs),
                         NewItem::System(s) => self.add_system(s),
+                        NewItem::Generic(g) => self.add_generic(g),
                     });
                 }
             }
@@ -277,7 +321,7 @@ impl Items {
     }
 
     // Returns errs first, warnings inside
-    pub fn resolve(crates: &mut Crates) -> WarningResult<WarningResult<Self>> {
+    pub fn resolve(crates: &mut Crates, profiler: &Profiler) -> WarningResult<WarningResult<Self>> {
         let mut errs = Vec::new();
         let mut warnings = Vec::new();
         let mut items = Items::new();
@@ -288,6 +332,7 @@ impl Items {
         for sym in HardcodedSymbol::VARIANTS {
             AstCrate::add_hardcoded_symbol(crates, sym).record_errs(&mut errs);
         }
+        profiler.pass("Hardcoded symbols");
 
         // Resolve components, globals, events, and states
         items.add_symbols(&mut errs, crates, |_, new_items, (m, cr, crates)| {
@@ -301,27 +346,38 @@ impl Items {
                                     .expect_any_hardcoded()
                                     .discard_symbol()
                                 {
-                                    Ok(HardcodedSymbol::ComponentMacro) => {
-                                        Some(new_items.push(NewItem::Component(ItemComponent {
-                                            args: parse_tokens(attr.args.clone())?,
-                                            data: ItemData::from_ast(cr.idx, m.idx, item),
-                                        })))
-                                    }
-                                    Ok(HardcodedSymbol::GlobalMacro) => {
-                                        Some(new_items.push(NewItem::Global(ItemGlobal {
-                                            args: parse_tokens(attr.args.clone())?,
-                                            data: ItemData::from_ast(cr.idx, m.idx, item),
-                                        })))
-                                    }
-                                    Ok(HardcodedSymbol::EventMacro) => {
-                                        Some(new_items.push(NewItem::Event(ItemEvent {
-                                            state: None,
-                                            data: ItemData::from_ast(cr.idx, m.idx, item),
-                                        })))
-                                    }
-                                    Ok(HardcodedSymbol::StateMacro) => {
-                                        Some(new_items.push(NewItem::State(item.clone())))
-                                    }
+                                    Ok(HardcodedSymbol::ComponentMacro) => Some(
+                                        new_items.push(
+                                            NewItem::Component(ItemComponent {
+                                                args: parse_tokens(attr.args.clone())?,
+                                                data: ItemData::from_ast(cr.idx, m.idx, item),
+                                            })
+                                            .or_generic(item)?,
+                                        ),
+                                    ),
+                                    Ok(HardcodedSymbol::GlobalMacro) => Some(
+                                        new_items.push(
+                                            NewItem::Global(ItemGlobal {
+                                                args: parse_tokens(attr.args.clone())?,
+                                                data: ItemData::from_ast(cr.idx, m.idx, item),
+                                            })
+                                            .or_generic(item)?,
+                                        ),
+                                    ),
+                                    Ok(HardcodedSymbol::EventMacro) => Some(
+                                        new_items.push(
+                                            NewItem::Event(ItemEvent {
+                                                args: parse_tokens(attr.args.clone())?,
+                                                state: None,
+                                                data: ItemData::from_ast(cr.idx, m.idx, item),
+                                            })
+                                            .or_generic(item)?,
+                                        ),
+                                    ),
+                                    Ok(HardcodedSymbol::StateMacro) => Some(
+                                        new_items
+                                            .push(NewItem::State(item.clone()).or_generic(item)?),
+                                    ),
                                     _ => None,
                                 },
                             )
@@ -332,9 +388,14 @@ impl Items {
                 .discard_value()
                 .critical()
         });
+        profiler.pass("Components, globals, events, and states");
+
+        // Instantiate generic items
+        items.add_instances(crates).record_errs(&mut errs);
+        profiler.pass("Generic instances");
 
         // Resolve component sets
-        items.add_symbols(&mut errs, crates, |_, new_items, (m, cr, crates)| {
+        items.add_symbols(&mut errs, crates, |items, new_items, (m, cr, crates)| {
             (&m.items.macro_calls)
                 .try_for_each(|call| {
                     if resolve_path(call.data.path.to_vec(), (m, cr, crates))
@@ -342,7 +403,7 @@ impl Items {
                         .is_ok()
                     {
                         new_items.push(NewItem::ComponentSet(
-                            ComponentSet::parse(call.args.clone(), (m, cr, crates))?
+                            ComponentSet::parse(call.args.clone(), items, (m, cr, crates))?
                                 .record_errs(&mut warnings),
                         ));
                     }
@@ -351,6 +412,7 @@ impl Items {
                 .discard_value()
                 .critical()
         });
+        profiler.pass("Component sets");
 
         // Insert namespace mod
         // TODO: detect game_crate!() and add there with span
@@ -361,9 +423,9 @@ impl Items {
                 .iter()
                 .map(|(_, alias)| AstUse {
                     ident: alias.to_string(),
-                    first: alias.to_string(),
                     path: vec![alias.to_string()],
-                    public: true,
+                    vis: Visibility::Public,
+                    span: Span::call_site(),
                 })
                 .collect();
             let num_mods = cr.mods.len();
@@ -401,6 +463,7 @@ impl Items {
                                 mod_idx: ns_mod.idx,
                                 // TODO: refine the span
                                 span: ns_mod.span,
+                                docs: String::new(),
                             },
                         });
                     }
@@ -414,7 +477,7 @@ impl Items {
                         cr.add_symbol(Symbol {
                             kind: SymbolType::Global(g_sym),
                             path: gl_path.to_vec(),
-                            public: true,
+                            vis: Visibility::Public,
                         })
                         .record_errs(&mut errs);
                     }
@@ -422,13 +485,15 @@ impl Items {
                     cr.add_symbol(Symbol {
                         kind: SymbolType::Trait(g_sym),
                         path: path.to_vec(),
-                        public: true,
+                        vis: Visibility::Public,
                     })
                     .record_errs(&mut errs);
                 }
             }
         }
 
+        profiler.pass("Namespace and trait symbols");
+
         // Resolve systems
         items.add_symbols(&mut errs, crates, |items, new_items, (m, cr, crates)| {
             (&m.items.functions)
@@ -450,6 +515,13 @@ impl Items {
                 .discard_value()
                 .critical()
         });
+        profiler.pass("Systems");
+
+        // Name generated code after item paths
+        if let Some(ids) = ItemIds::new(&items, crates).record_errs(&mut errs) {
+            items.ids = ids;
+        }
+        profiler.pass("Generated names");
 
         err(err(items, warnings), errs)
     }
@@ -1,4 +1,8 @@
 // mod basic_items;
+mod generics;
+mod ids;
 mod items;
 
-pub use items::{ItemComponent, ItemEvent, ItemGlobal, ItemState, Items};
+pub use generics::{get_type_args, resolve_generic_arg, GenericKind, ItemGeneric, ItemInstance};
+pub use ids::ItemIds;
+pub use items::{ItemComponent, ItemData, ItemEvent, ItemGlobal, ItemState, Items};
@@ -13,9 +13,9 @@ use shared::{
 };
 
 use crate::{
-    codegen::Crates,
+    codegen::{source_doc, Crates, SourceKind},
     component_set::{BuildSetsArg, BuildSetsResult, ComponentSet},
-    resolve::Items,
+    resolve::{ItemIds, Items},
     utils::{
         idents::{component_var, event_variant, global_var, CodegenIdents, CODEGEN_IDENTS},
         paths::{ENGINE_PATHS, ENGINE_TRAITS},
@@ -27,7 +27,11 @@ use super::{
     ItemSystem,
 };
 
-fn codegen_init_system(mut global_args: Vec<GlobalFnArg>, func_name: syn::Path) -> TokenStream {
+fn codegen_init_system(
+    mut global_args: Vec<GlobalFnArg>,
+    func_name: syn::Path,
+    ids: &ItemIds,
+) -> TokenStream {
     let CodegenIdents {
         gfoo_var: globals_var,
         ..
@@ -35,13 +39,14 @@ fn codegen_init_system(mut global_args: Vec<GlobalFnArg>, func_name: syn::Path)
     global_args.sort_by_key(|g| g.arg_idx);
     let func_args = global_args.map_vec(|g| {
         let mut_tok = if g.is_mut { quote!(mut) } else { quote!() };
-        let var = global_var(g.idx);
+        let var = global_var(ids, g.idx);
         quote!(&#mut_tok self.#globals_var.#var)
     });
     quote!(#func_name(#(#func_args),*))
 }
 
 pub struct CodegenData<'a> {
+    ids: &'a ItemIds,
     func_name: syn::Path,
     event: EventFnArg,
     globals: Vec<GlobalFnArg>,
@@ -64,6 +69,7 @@ pub struct CodegenItems<'a> {
 // Codegens event systems
 fn codegen_event_system(
     CodegenData {
+        ids,
         func_name,
         event: event_arg,
         globals: global_args,
@@ -93,7 +99,7 @@ fn codegen_event_system(
     for g in global_args {
         func_args[g.arg_idx] = {
             let mut_tok = if g.is_mut { quote!(mut) } else { quote!() };
-            let var = global_var(g.idx);
+            let var = global_var(ids, g.idx);
             quote!(&#mut_tok #globals_var.#var)
         };
     }
@@ -101,7 +107,7 @@ fn codegen_event_system(
         build_sets_code,
         func_args: cs_func_args,
         singletons,
-    } = ComponentSet::codegen_build_sets(&component_sets, intersect, intersect_opt);
+    } = ComponentSet::codegen_build_sets(&component_sets, ids, intersect, intersect_opt);
     for (cs, tok) in component_sets.iter().zip(cs_func_args) {
         func_args[cs.fn_arg.arg_idx] = tok;
     }
@@ -141,7 +147,7 @@ fn codegen_system(
     } = cargs;
 
     match args {
-        FnArgs::Init { globals } => Ok((codegen_init_system(globals, func_name), None)),
+        FnArgs::Init { globals } => Ok((codegen_init_system(globals, func_name, &items.ids), None)),
         FnArgs::System {
             event,
             globals,
@@ -160,6 +166,7 @@ fn codegen_system(
                 (
                     codegen_event_system(
                         CodegenData {
+                            ids: &items.ids,
                             func_name,
                             event,
                             globals,
@@ -167,7 +174,7 @@ fn codegen_system(
                         },
                         funcs,
                     ),
-                    Some(event_variant(event.idx)),
+                    Some(event_variant(&items.ids, event.idx)),
                 )
             }),
     }
@@ -207,6 +214,8 @@ pub struct SystemsCodegenResult {
     pub init_systems: Vec<TokenStream>,
     pub systems: Vec<TokenStream>,
     pub system_events: Vec<syn::Ident>,
+    // Source comments for `systems`
+    pub system_docs: Vec<TokenStream>,
 }
 
 pub fn codegen_systems(
@@ -221,10 +230,18 @@ pub fn codegen_systems(
     let mut init_systems = Vec::new();
     let mut systems = Vec::new();
     let mut system_events = Vec::new();
+    let mut system_docs = Vec::new();
 
     zip_match!((event_trait, intersect, intersect_opt) => {
         (&items.systems)
             .try_for_each(|system| {
+                let doc = source_doc(
+                    crates,
+                    SourceKind::System,
+                    &system.path,
+                    system.span.m_idx,
+                    &system.span.span,
+                );
                 validate_system(
                     CodegenItems {
                         cr_idx,
@@ -242,14 +259,16 @@ pub fn codegen_systems(
                     Some(e) => {
                         system_events.push(e);
                         systems.push(sys);
+                        system_docs.push(doc);
                     }
-                    None => init_systems.push(sys),
+                    None => init_systems.push(quote!(#doc #sys)),
                 })
             })
             .map(|_| SystemsCodegenResult {
                 init_systems,
                 systems,
                 system_events,
+                system_docs,
             })
             .critical()
     })
@@ -3,5 +3,5 @@ mod parse;
 mod resolve;
 
 pub use codegen::{codegen_systems, SystemsCodegenResult};
-pub use parse::ItemSystem;
+pub use parse::{FnArg, FnArgType, ItemSystem};
 pub use resolve::{ComponentSetFnArg, EventFnArg, FnArgs, GlobalFnArg};
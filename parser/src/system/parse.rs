@@ -4,9 +4,10 @@ use quote::ToTokens;
 use syn::spanned::Spanned;
 
 use shared::{
+    error_codes::ErrorCode,
     parsing::SystemMacroArgs,
     syn::{
-        error::{CriticalResult, MutateResults, ToError},
+        error::{CriticalResult, GetVec, MutateResults, StrToError, ToError},
         get_type_generics, parse_tokens, use_path_from_syn, ToRange,
     },
     traits::{Call, CollectVecInto, CombineOptions, PushInto, ToNone},
@@ -14,13 +15,14 @@ use shared::{
 
 use crate::{
     parse::{
-        resolve_path, resolve_syn_path, AstAttribute, AstFunction, DiscardSymbol, HardcodedSymbol,
-        ItemPath, ItemSpan, MatchSymbol, ModInfo,
+        resolve_path, resolve_syn_path, AliasSymbol, AstAttribute, AstCrate, AstFunction,
+        BuiltinSymbol, DiscardSymbol, HardcodedSymbol, ItemPath, ItemSpan, MatchSymbol, ModInfo,
+        SymbolType,
     },
-    resolve::Items,
+    resolve::{get_type_args, Items},
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash)]
 pub enum FnArgType {
     Event(usize),
     Global(usize),
@@ -63,43 +65,75 @@ impl FnArg {
             .map_vec_into(|arg| match arg {
                 syn::FnArg::Receiver(r) => r.error("Cannot use self in system").as_err(),
                 syn::FnArg::Typed(syn::PatType { ty, .. }) => {
-                    FnArg::parse_type(ty, (m, cr, crates))
+                    FnArg::parse_type(ty, items, &mut Vec::new(), (m, cr, crates))
                 }
             })
             .combine_results()
     }
 
-    fn parse_type(ty: &syn::Type, (m, cr, crates): ModInfo) -> CriticalResult<Self> {
+    // `aliases` contains the type aliases being expanded, used to detect cycles
+    fn parse_type(
+        ty: &syn::Type,
+        items: &Items,
+        aliases: &mut Vec<AliasSymbol>,
+        (m, cr, crates): ModInfo,
+    ) -> CriticalResult<Self> {
         let ty_str = ty.to_token_stream().to_string();
         match ty {
             syn::Type::Path(p) => {
-                // TODO: Type alias support must include Vec (Add path resolution for built-ins)
-                let path = use_path_from_syn(&m.path, &p.path);
-                match path.join("::").as_str() {
-                    "Vec" => match get_type_generics(p).as_ref().and_then(|v| v.first()) {
-                        Some(syn::GenericArgument::Type(syn::Type::Path(ty))) => {
-                            resolve_syn_path(&m.path, &ty.path, (m, cr, crates))
-                                .expect_component_set()
-                                .discard_symbol()
-                                .with_span(ty)
-                                .map(|idx| FnArgType::Entities { idx, is_vec: true })
+                let sym = resolve_syn_path(&m.path, &p.path, (m, cr, crates))
+                    .and_then(|sym| items.resolve_instance(sym, &get_type_args(p), (m, cr, crates)))
+                    .with_suggestions(|k| {
+                        matches!(
+                            k,
+                            SymbolType::Global(_)
+                                | SymbolType::Event(_)
+                                | SymbolType::ComponentSet(_)
+                                | SymbolType::TypeAlias(_)
+                        )
+                    })
+                    .into_result()
+                    .with_span(ty)?;
+                match sym.kind {
+                    SymbolType::Builtin(BuiltinSymbol::Vec) => {
+                        match get_type_generics(p).as_ref().and_then(|v| v.first()) {
+                            Some(syn::GenericArgument::Type(arg_ty)) => Self::parse_type(
+                                arg_ty,
+                                items,
+                                aliases,
+                                (m, cr, crates),
+                            )
+                            .and_then(|arg| match arg {
+                                Self {
+                                    ty: FnArgType::Entities { idx, is_vec: false },
+                                    ref_cnt: 0,
+                                    ..
+                                } => Ok(FnArgType::Entities { idx, is_vec: true }),
+                                _ => arg_ty
+                                    .error(format!("Expected Component Set but found {}", arg))
+                                    .as_err(),
+                            }),
+                            _ => ty
+                                .error("Invalid argument type")
+                                .with_code(ErrorCode::InvalidArgumentType)
+                                .as_err(),
                         }
-                        _ => ty.error("Invalid argument type").as_err(),
-                    },
-                    _ => {
-                        resolve_path(path, (m, cr, crates))
+                    }
+                    SymbolType::TypeAlias(a_sym) => {
+                        return Self::parse_alias(a_sym, items, aliases, crates)
                             .with_span(ty)
-                            .and_then(|sym| match sym.kind {
-                                crate::parse::SymbolType::Global(g_sym) => {
-                                    Ok(FnArgType::Global(g_sym.idx))
-                                }
-                                crate::parse::SymbolType::Event(i) => Ok(FnArgType::Event(i)),
-                                crate::parse::SymbolType::ComponentSet(idx) => {
-                                    Ok(FnArgType::Entities { idx, is_vec: false })
-                                }
-                                _ => ty.error("Invalid argument type").as_err(),
+                            .map(|arg| Self {
+                                span: ty.span(),
+                                ..arg
                             })
                     }
+                    SymbolType::Global(g_sym) => Ok(FnArgType::Global(g_sym.idx)),
+                    SymbolType::Event(i) => Ok(FnArgType::Event(i)),
+                    SymbolType::ComponentSet(idx) => Ok(FnArgType::Entities { idx, is_vec: false }),
+                    _ => ty
+                        .error("Invalid argument type")
+                        .with_code(ErrorCode::InvalidArgumentType)
+                        .as_err(),
                 }
                 .map(|data| Self {
                     ty: data,
@@ -108,13 +142,12 @@ impl FnArg {
                     span: ty.span(),
                 })
             }
-            syn::Type::Reference(r) => {
-                Self::parse_type(&r.elem, (m, cr, crates)).map(|mut fn_arg| {
+            syn::Type::Reference(r) => Self::parse_type(&r.elem, items, aliases, (m, cr, crates))
+                .map(|mut fn_arg| {
                     fn_arg.ref_cnt += 1;
                     fn_arg.is_mut = fn_arg.is_mut || r.mutability.is_some();
                     fn_arg
-                })
-            }
+                }),
             syn::Type::TraitObject(t) => {
                 let traits = t
                     .bounds
@@ -142,8 +175,33 @@ impl FnArg {
                         .as_err(),
                 }
             }
-            _ => ty.error("Invalid argument type").as_err(),
+            _ => ty
+                .error("Invalid argument type")
+                .with_code(ErrorCode::InvalidArgumentType)
+                .as_err(),
+        }
+    }
+}
+
+impl FnArg {
+    fn parse_alias(
+        a_sym: AliasSymbol,
+        items: &Items,
+        aliases: &mut Vec<AliasSymbol>,
+        crates: &Vec<AstCrate>,
+    ) -> CriticalResult<Self> {
+        let cr = crates.try_get(a_sym.cr_idx)?;
+        let m = cr.get_mod(a_sym.m_idx)?;
+        let alias = m.items.type_aliases.try_get(a_sym.idx)?;
+        if aliases.contains(&a_sym) {
+            return format!("Type alias is recursive: {}", alias.data.path.join("::"))
+                .error()
+                .as_err();
         }
+        aliases.push(a_sym);
+        let arg = Self::parse_type(&alias.ty, items, aliases, (m, cr, crates));
+        aliases.pop();
+        arg
     }
 }
 
@@ -164,6 +222,7 @@ pub struct ItemSystem {
     pub args: Vec<FnArg>,
     pub attr_args: SystemMacroArgs,
     pub span: ItemSpan,
+    pub docs: String,
 }
 
 impl ItemSystem {
@@ -180,6 +239,7 @@ impl ItemSystem {
                 args,
                 attr_args,
                 span: ItemSpan::new(cr, m, fun.sig.ident.span()),
+                docs: fun.data.docs.to_string(),
             })
         })
     }
@@ -4,6 +4,7 @@ use std::collections::{HashMap, HashSet};
 
 use shared::{
     constants::TAB,
+    error_codes::ErrorCode,
     parsing::SystemMacroArgs,
     syn::error::{CriticalResult, Note, SpanTrait, ToError},
     traits::{CollectVec, CollectVecInto, MapOr, PushInto, ThenOk},
@@ -139,7 +140,11 @@ impl ComponentRefTracker {
                     .collect();
                 refs.sort_by(|(_, s1), (_, s2)| s1.cmp(s2));
                 refs.dedup_by(|(_, s1), (_, s2)| s1 == s2);
-                refs.map_vec_into(|(s, _)| s.error(msg.to_string()).with_notes(notes.to_vec()))
+                refs.map_vec_into(|(s, _)| {
+                    s.error(msg.to_string())
+                        .with_code(ErrorCode::ConflictingReferences)
+                        .with_notes(notes.to_vec())
+                })
             },
             || (),
         )
@@ -164,6 +169,7 @@ impl ItemSystem {
                     FnArgType::Event(_) | FnArgType::Entities { .. } => arg
                         .span
                         .error(format!("Init systems may not contain {}", arg.ty))
+                        .with_code(ErrorCode::InvalidInitArgument)
                         .as_err(),
                 })
                 .combine_results()
@@ -177,7 +183,11 @@ impl ItemSystem {
                 self.args
                     .enumer_map_vec(|(arg_idx, arg)| match &arg.ty {
                         FnArgType::Event(idx) => match event {
-                            Some(_) => arg.span.error("Event already specified").as_err(),
+                            Some(_) => arg
+                                .span
+                                .error("Event already specified")
+                                .with_code(ErrorCode::DuplicateEvent)
+                                .as_err(),
                             None => self
                                 .validate_event(arg, *idx, items)
                                 .map(|_| event = Some(EventFnArg { arg_idx, idx: *idx })),
@@ -204,7 +214,12 @@ impl ItemSystem {
                     .combine_results()
                     // Require event
                     .take_value(
-                        event.ok_or(self.span.error("System must specify an event").as_vec()),
+                        event.ok_or(
+                            self.span
+                                .error("System must specify an event")
+                                .with_code(ErrorCode::MissingEvent)
+                                .as_vec(),
+                        ),
                     )
                     // Check component reference mutability
                     .take_errs(
@@ -246,9 +261,13 @@ impl ItemSystem {
             })
             .take_errs(self.validate_ref(arg, 1))
             .take_errs(
-                globals
-                    .insert(i)
-                    .ok((), arg.span.error("Duplicate global").as_vec()),
+                globals.insert(i).ok(
+                    (),
+                    arg.span
+                        .error("Duplicate global")
+                        .with_code(ErrorCode::DuplicateGlobal)
+                        .as_vec(),
+                ),
             )
     }
 
@@ -305,7 +324,8 @@ impl ItemSystem {
                     false => {
                         let mut err = arg
                             .span
-                            .error("Entity set must contain singletons or be wrapped with Vec<>");
+                            .error("Entity set must contain singletons or be wrapped with Vec<>")
+                            .with_code(ErrorCode::MissingSingleton);
                         if let Some(ComponentSetLabels::Expression(e)) = &cs.labels {
                             for (symbs, verb) in [
                                 (&e.false_symbols, "is forbidden"),
@@ -347,6 +367,7 @@ impl ItemSystem {
                     },
                     arg
                 ))
+                .with_code(ErrorCode::WrongReferenceCount)
                 .as_vec(),
         )
     }
@@ -364,6 +385,7 @@ impl ItemSystem {
                     },
                     arg
                 ))
+                .with_code(ErrorCode::WrongMutability)
                 .as_vec(),
         )
     }
@@ -0,0 +1,239 @@
+use std::{collections::HashMap, env, fs, path::PathBuf, process};
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use regex::Regex;
+
+use shared::traits::{CollectVec, CollectVecInto};
+
+use crate::{format_code, parse_crate, Crate, ParseOptions};
+
+// Snapshot tests for the game crates in tests/fixtures
+// Each fixture is run through `parse_crate()` and the game crate's generated code and
+// diagnostics are compared with tests/snapshots/<fixture>.rs and <fixture>.stderr
+// Dependencies are read from Cargo.toml files so the tests never run cargo
+// Set HYPERFOLD_BLESS=1 to write the new output to the snapshots instead
+const BLESS_ENV: &str = "HYPERFOLD_BLESS";
+
+// Replaces absolute paths in the output
+const FIXTURE_DIR: &str = "$DIR";
+const ENGINE_DIR: &str = "$ENGINE";
+
+macro_rules! fixtures {
+    ($($name: ident),*) => {
+        $(
+            #[test]
+            fn $name() {
+                check_fixture(stringify!($name))
+            }
+        )*
+    };
+}
+
+fixtures!(basic, errors, lints, renamed, uses);
+
+#[test]
+fn stable_event_discriminants() {
+    let dir = copy_fixture("basic", "events");
+    let discriminants = || {
+        let options = ParseOptions {
+            cargo_metadata: false,
+            ..ParseOptions::default()
+        };
+        let analysis = parse_crate(dir.to_owned(), &options)
+            .unwrap_or_else(|_| panic!("Failed to parse {}", dir.display()));
+        let main_cr_idx = analysis.crates.get_crate_index(Crate::Main);
+        let code = analysis
+            .code
+            .into_iter()
+            .find(|(cr_idx, _)| *cr_idx == main_cr_idx)
+            .map_or(String::new(), |(_, code)| format_code(&code));
+        Regex::new(r"\b(E[0-9A-F]{8}) = (\d+)u32")
+            .unwrap()
+            .captures_iter(&code)
+            .map(|c| (c[1].to_string(), c[2].to_string()))
+            .collect::<HashMap<_, _>>()
+    };
+
+    let before = discriminants();
+    assert!(!before.is_empty(), "No event discriminants were generated");
+
+    // Declare an event before the others
+    let main = dir.join("src").join("main.rs");
+    let code = fs::read_to_string(&main).unwrap().replacen(
+        "#[hyperfold_engine::component]",
+        "#[hyperfold_engine::event]\nstruct Added;\n\n#[hyperfold_engine::component]",
+        1,
+    );
+    fs::write(&main, code).unwrap();
+
+    let after = discriminants();
+    assert_eq!(after.len(), before.len() + 1);
+    for (variant, discriminant) in &before {
+        assert_eq!(after.get(variant), Some(discriminant), "{variant} changed");
+    }
+}
+
+fn check_fixture(name: &str) {
+    let tests_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests");
+    let (code, diagnostics) = run_fixture(tests_dir.join("fixtures").join(name));
+
+    let snapshots_dir = tests_dir.join("snapshots");
+    let changed = [
+        (format!("{name}.rs"), code),
+        (format!("{name}.stderr"), diagnostics),
+    ]
+    .into_iter()
+    .filter(|(file, output)| !check_snapshot(snapshots_dir.join(file), output))
+    .map_vec_into(|(file, _)| file);
+
+    assert!(
+        changed.is_empty(),
+        "Snapshots changed: {}\nRerun with {BLESS_ENV}=1 to update them",
+        changed.join(", ")
+    );
+}
+
+// Returns the formatted codegen and rendered diagnostics
+fn run_fixture(dir: PathBuf) -> (String, String) {
+    let options = ParseOptions {
+        cargo_metadata: false,
+        ..ParseOptions::default()
+    };
+    let analysis = parse_crate(dir, &options).unwrap_or_else(|diagnostics| {
+        panic!(
+            "Failed to parse fixture:\n{}",
+            diagnostics.map_vec(|d| d.message.to_string()).join("\n")
+        )
+    });
+
+    let entry = analysis.config.entry.display().to_string();
+    let engine_dir = analysis.config.engine_dir.display().to_string();
+    let normalize = |text: &str| {
+        text.replace(&entry, FIXTURE_DIR)
+            .replace(&engine_dir, ENGINE_DIR)
+    };
+
+    // The engine's generated code doesn't depend on the fixture
+    let main_cr_idx = analysis.crates.get_crate_index(Crate::Main);
+    let code = analysis
+        .code
+        .into_iter()
+        .find(|(cr_idx, _)| *cr_idx == main_cr_idx)
+        .map_or(String::new(), |(_, code)| {
+            normalize(&format_code(&game_items(code)))
+        });
+
+    let ansi = Regex::new("\x1b\\[[0-9;]*m").unwrap();
+    let diagnostics = analysis
+        .diagnostics
+        .map_vec_into(|d| normalize(&ansi.replace_all(&d.rendered.unwrap_or(d.message), "")))
+        .join("\n");
+
+    (code, diagnostics)
+}
+
+// Keeps only generated items which use the fixture's own items, e.g. `crate::Position`
+// The rest is generated for the engine's items and is the same for every fixture
+fn game_items(code: TokenStream) -> TokenStream {
+    let Ok(mut file) = syn::parse2::<syn::File>(code.clone()) else {
+        return code;
+    };
+    let game_path = Regex::new(r"\bcrate :: (\w+)").unwrap();
+    retain_game_items(&mut file.items, &game_path);
+    file.into_token_stream()
+}
+
+fn retain_game_items(items: &mut Vec<syn::Item>, game_path: &Regex) {
+    let uses_game = |tokens: &dyn ToTokens| {
+        game_path
+            .captures_iter(&tokens.to_token_stream().to_string())
+            .any(|c| &c[1] != "_engine")
+    };
+    items.retain_mut(|item| match item {
+        syn::Item::Mod(syn::ItemMod {
+            content: Some((_, items)),
+            ..
+        }) => {
+            retain_game_items(items, game_path);
+            !items.is_empty()
+        }
+        // Impls for engine types, e.g. `impl SFoo`, only keep the fixture's functions
+        syn::Item::Impl(i)
+            if !uses_game(&i.self_ty) && !uses_game(&i.trait_.as_ref().map(|t| &t.1)) =>
+        {
+            i.items.retain(|item| uses_game(item));
+            // E.g. `add_systems()` only keeps the fixture's systems
+            for item in i.items.iter_mut() {
+                if let syn::ImplItem::Fn(f) = item {
+                    f.block.stmts.retain(|stmt| uses_game(stmt));
+                }
+            }
+            !i.items.is_empty()
+        }
+        item => uses_game(item),
+    });
+}
+
+// Returns whether the output matches the snapshot
+fn check_snapshot(file: PathBuf, output: &str) -> bool {
+    if env::var(BLESS_ENV).is_ok() {
+        fs::write(&file, output)
+            .unwrap_or_else(|e| panic!("Failed to write {}: {e}", file.display()));
+        return true;
+    }
+
+    let Ok(snapshot) = fs::read_to_string(&file) else {
+        eprintln!("Missing snapshot: {}", file.display());
+        return false;
+    };
+    if snapshot == output {
+        return true;
+    }
+
+    // Show the first line that differs
+    let i = snapshot
+        .lines()
+        .zip(output.lines())
+        .position(|(l1, l2)| l1 != l2)
+        .unwrap_or_else(|| snapshot.lines().count().min(output.lines().count()));
+    eprintln!(
+        "{} differs at line {}:\n- {}\n+ {}",
+        file.display(),
+        i + 1,
+        snapshot.lines().nth(i).unwrap_or_default(),
+        output.lines().nth(i).unwrap_or_default()
+    );
+    false
+}
+
+// Copies a fixture to a temporary directory so it can be edited
+pub(crate) fn copy_fixture(name: &str, test: &str) -> PathBuf {
+    let parser_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let fixture = parser_dir.join("tests").join("fixtures").join(name);
+    let dir = env::temp_dir().join(format!("hyperfold-{test}-{name}-{}", process::id()));
+    fs::remove_dir_all(&dir).ok();
+    copy_dir(&fixture, &dir);
+
+    // The engine path is relative to the fixture
+    let engine_dir = fs::canonicalize(parser_dir.join("..")).unwrap();
+    let cargo_toml = fs::read_to_string(dir.join("Cargo.toml"))
+        .unwrap()
+        .replace("../../../..", &engine_dir.display().to_string());
+    fs::write(dir.join("Cargo.toml"), cargo_toml).unwrap();
+    dir
+}
+
+fn copy_dir(from: &PathBuf, to: &PathBuf) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let path = entry.unwrap().path();
+        let to = to.join(path.file_name().unwrap());
+        match path.is_dir() {
+            true => copy_dir(&path, &to),
+            false => {
+                fs::copy(&path, &to).unwrap();
+            }
+        }
+    }
+}
@@ -2,6 +2,8 @@ use once_cell::sync::Lazy;
 
 use quote::format_ident;
 
+use crate::resolve::ItemIds;
+
 use super::paths::{ENGINE_GLOBALS, ENGINE_TRAITS};
 
 use super::constants::NAMESPACE;
@@ -36,7 +38,6 @@ idents!(CODEGEN_IDENTS = CodegenIdents {
     add_component => ENGINE_TRAITS.components.get_ident(),
     add_event => ENGINE_TRAITS.events.get_ident(),
     event_enum => "E",
-    event_enum_len => "E_LEN",
     state_enum => "S",
     e_var => "e",
     v_var => "v",
@@ -53,38 +54,39 @@ idents!(CODEGEN_IDENTS = CodegenIdents {
 });
 
 // Returns names of codegen items
-pub fn component_var(c_idx: usize) -> syn::Ident {
-    format_ident!("c{c_idx}")
+// Names contain the item's id so adding or removing an item doesn't rename the others
+pub fn component_var(ids: &ItemIds, c_idx: usize) -> syn::Ident {
+    format_ident!("c_{:08x}", ids.component(c_idx))
 }
 
-pub fn global_var(g_idx: usize) -> syn::Ident {
-    format_ident!("g{g_idx}")
+pub fn global_var(ids: &ItemIds, g_idx: usize) -> syn::Ident {
+    format_ident!("g_{:08x}", ids.global(g_idx))
 }
 
-pub fn event_var(e_idx: usize) -> syn::Ident {
-    format_ident!("e{e_idx}")
+pub fn event_var(ids: &ItemIds, e_idx: usize) -> syn::Ident {
+    format_ident!("e_{:08x}", ids.event(e_idx))
 }
 
-pub fn event_variant(e_idx: usize) -> syn::Ident {
-    format_ident!("E{e_idx}")
+pub fn event_variant(ids: &ItemIds, e_idx: usize) -> syn::Ident {
+    format_ident!("E{:08X}", ids.event(e_idx))
 }
 
-pub fn component_set_var(cs_idx: usize) -> syn::Ident {
-    format_ident!("cs{cs_idx}")
+pub fn component_set_var(ids: &ItemIds, cs_idx: usize) -> syn::Ident {
+    format_ident!("cs_{:08x}", ids.component_set(cs_idx))
 }
 
-pub fn component_set_keys_fn(cs_idx: usize, ret_vec: bool) -> syn::Ident {
+pub fn component_set_keys_fn(ids: &ItemIds, cs_idx: usize, ret_vec: bool) -> syn::Ident {
     format_ident!(
         "get_{}_keys{}",
-        component_set_var(cs_idx),
+        component_set_var(ids, cs_idx),
         if ret_vec { "_vec" } else { "" }
     )
 }
 
-pub fn state_var(s_idx: usize) -> syn::Ident {
-    format_ident!("s{s_idx}")
+pub fn state_var(ids: &ItemIds, s_idx: usize) -> syn::Ident {
+    format_ident!("s_{:08x}", ids.state(s_idx))
 }
 
-pub fn state_variant(s_idx: usize) -> syn::Ident {
-    format_ident!("S{s_idx}")
+pub fn state_variant(ids: &ItemIds, s_idx: usize) -> syn::Ident {
+    format_ident!("S{:08X}", ids.state(s_idx))
 }
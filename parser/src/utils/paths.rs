@@ -15,6 +15,7 @@ use shared::{
 use crate::{
     codegen::Crates,
     parse::{resolve_path_from_crate, DiscardSymbol, GlobalSymbol, ItemPath, MatchSymbol},
+    resolve::ItemIds,
     utils::{constants::NAMESPACE, idents::global_var},
 };
 
@@ -163,7 +164,12 @@ macro_rules! engine_globals {
         }
 
         impl $ty {
-            pub fn get_global_vars(&self, crates: &Crates, cr_idx: usize) -> CriticalResult<$ty_res> {
+            pub fn get_global_vars(
+                &self,
+                crates: &Crates,
+                cr_idx: usize,
+                ids: &ItemIds,
+            ) -> CriticalResult<$ty_res> {
                 let cr = crates.try_get(cr_idx)?;
                 let get_global = |cr_path| {
                     crates
@@ -173,7 +179,7 @@ macro_rules! engine_globals {
                                 .expect_global()
                                 .discard_symbol()
                         })
-                        .map(|g_sym| global_var(g_sym.idx))
+                        .map(|g_sym| global_var(ids, g_sym.idx))
                 };
                 $($(let $var = get_global(&self.$var);)*)*
                 zip_match! {
@@ -0,0 +1,74 @@
+use hyperfold_engine::{
+    components,
+    ecs::{entities::Entity, events::core},
+};
+
+use crate::_engine::Events;
+
+hyperfold_engine::game_crate!();
+
+#[hyperfold_engine::component]
+struct Position(i32, i32);
+
+#[hyperfold_engine::component]
+struct Velocity(i32, i32);
+
+#[hyperfold_engine::component(Singleton)]
+struct Player;
+
+#[hyperfold_engine::global]
+struct Score(u32);
+
+#[hyperfold_engine::event]
+struct Collision(pub Entity);
+
+components!(Moving, pos: &'a mut Position, vel: &'a Velocity);
+
+components!(labels(Player), PlayerPos, pos: &'a Position);
+
+#[hyperfold_engine::system(Init)]
+fn init(score: &mut Score) {
+    score.0 = 0;
+}
+
+#[hyperfold_engine::system]
+fn movement(_: &core::Update, entities: Vec<Moving>, events: &mut dyn Events) {
+    for Moving { eid, pos, vel } in entities {
+        pos.0 += vel.0;
+        pos.1 += vel.1;
+        if pos.0 < 0 {
+            events.new_event(Collision(*eid));
+        }
+    }
+}
+
+#[hyperfold_engine::system]
+fn on_collision(_: &Collision, player: PlayerPos, score: &mut Score) {
+    score.0 += 1;
+}
+
+// Type aliases and std re-exports name argument types
+use std::vec::Vec as List;
+
+type Movers = List<Moving>;
+type Points = Score;
+type Hit = Collision;
+
+#[hyperfold_engine::system]
+fn aliases(_: &Hit, movers: Movers, points: &mut Points) {}
+
+// Mods are found from their declarations, not the file layout
+#[path = "platform/linux.rs"]
+mod platform;
+
+mod inline {
+    #[hyperfold_engine::component]
+    pub struct Tag;
+}
+
+components!(labels(inline::Tag), Tagged, health: &'a mut platform::Health);
+
+#[hyperfold_engine::system]
+fn heal(_: &core::Update, tagged: Vec<Tagged>) {}
+
+fn main() {}
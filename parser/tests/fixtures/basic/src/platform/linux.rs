@@ -0,0 +1,2 @@
+#[hyperfold_engine::component]
+pub struct Health(pub u32);
@@ -0,0 +1,6 @@
+#[hyperfold_engine::component]
+pub struct Broken(u32);
+
+pub struct MissingType {
+    value: ,
+}
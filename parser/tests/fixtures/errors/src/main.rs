@@ -0,0 +1,116 @@
+use hyperfold_engine::{components, ecs::events::core};
+
+#[hyperfold_engine::component]
+struct Position(i32, i32);
+
+#[hyperfold_engine::component(Singleton)]
+struct Camera;
+
+#[hyperfold_engine::global]
+struct Score(u32);
+
+#[hyperfold_engine::event]
+struct Scored;
+
+components!(Moving, pos: &'a mut Position);
+
+// Unresolved component
+components!(Typo, pos: &'a Postion);
+
+// Event argument is missing
+#[hyperfold_engine::system]
+fn no_event(score: &mut Score) {}
+
+// Two event arguments
+#[hyperfold_engine::system]
+fn two_events(_: &core::Update, _: &Scored) {}
+
+// Same global twice
+#[hyperfold_engine::system]
+fn same_global(_: &core::Update, a: &Score, b: &mut Score) {}
+
+// Components must be taken through a component set
+#[hyperfold_engine::system]
+fn conflicting(_: &core::Update, pos: &Position, moving: Vec<Moving>) {}
+
+// Singleton component sets need a singleton label
+#[hyperfold_engine::system]
+fn not_singleton(_: &Scored, moving: Moving) {}
+
+#[hyperfold_engine::component(Unknown)]
+struct BadArgs;
+
+mod private {
+    mod secret {
+        #[hyperfold_engine::component]
+        pub struct Secret;
+    }
+
+    pub(in crate::private) mod inner {
+        #[hyperfold_engine::component]
+        pub struct Hidden;
+    }
+
+    pub(crate) mod shared {
+        #[hyperfold_engine::component]
+        pub struct Shared;
+    }
+
+    use secret::Secret;
+}
+
+// Private mods and imports can't be named outside of their parent mod
+components!(Secrets, secret: &'a private::secret::Secret);
+components!(Hidden, hidden: &'a private::inner::Hidden);
+components!(Imported, secret: &'a private::Secret);
+components!(Shared, shared: &'a private::shared::Shared);
+
+#[hyperfold_engine::component]
+struct Pos1;
+
+#[hyperfold_engine::component]
+struct Pos2;
+
+#[hyperfold_engine::component]
+struct Pos3;
+
+#[hyperfold_engine::component]
+struct Pos4;
+
+// Only the closest matches are suggested
+components!(Positions, pos: &'a Pos);
+
+// Misspelled events and globals
+#[hyperfold_engine::system]
+fn misspelled(_: &Scord, score: &Scor) {}
+
+// Nothing is close enough
+#[hyperfold_engine::system]
+fn unknown(_: &core::Update, score: &Nothing) {}
+
+mod imports {
+    mod alloc {
+        pub struct Boxed;
+    }
+
+    // Names both the alloc crate and the local mod
+    use alloc::Boxed;
+
+    // Imports which import each other
+    use first as second;
+    use second as first;
+
+    // 'crate' can't follow '::'
+    use ::crate::Position;
+}
+
+#[hyperfold_engine::component]
+struct Wrapper<T>(T);
+
+// Names both the core crate and the import from hyperfold_engine
+components!(Ambiguous, wrapped: &'a Wrapper<core::Update>);
+
+// Syntax errors are reported and the rest of the crate is still checked
+mod broken;
+
+fn main() {}
@@ -0,0 +1,75 @@
+use hyperfold_engine::{
+    components,
+    ecs::{
+        components::AddComponent,
+        entities::Entity,
+        events::{core, AddEvent},
+    },
+};
+
+#[hyperfold_engine::component]
+struct Position(i32, i32);
+
+#[hyperfold_engine::component]
+struct Unused;
+
+#[hyperfold_engine::component(AllowUnused)]
+struct AllowedUnused;
+
+// Only added to entities
+#[hyperfold_engine::component]
+struct Added;
+
+// Only instantiated by being added to entities
+#[hyperfold_engine::component]
+struct Tagged<T>(T);
+
+#[hyperfold_engine::event]
+struct Unhandled;
+
+#[hyperfold_engine::event(AllowUnused)]
+struct AllowedUnhandled;
+
+#[hyperfold_engine::event]
+struct NeverSent(pub Entity);
+
+#[hyperfold_engine::event]
+struct Sometimes;
+
+// Only sent through a variable
+#[hyperfold_engine::event]
+struct FromVariable;
+
+components!(Positions, pos: &'a Position);
+
+#[hyperfold_engine::system]
+fn update(_: &core::Update, positions: Vec<Positions>) {}
+
+#[hyperfold_engine::system]
+fn unreachable(_: &NeverSent, positions: Vec<Positions>) {}
+
+#[hyperfold_engine::system(AllowUnreachable)]
+fn allowed(_: &Sometimes) {}
+
+#[hyperfold_engine::system]
+fn from_variable(_: &FromVariable) {}
+
+fn spawn(cm: &mut impl AddComponent<Added>, eid: Entity) {
+    cm.add_component(eid, Added);
+}
+
+fn tag(cm: &mut impl AddComponent<Tagged<u8>>, eid: Entity) {
+    cm.add_component(eid, Tagged::<u8>(0));
+}
+
+fn send(events: &mut impl AddEvent<FromVariable>) {
+    let e = FromVariable;
+    events.new_event(e);
+}
+
+// The type of the event isn't known, so it may be `NeverSent`
+fn send_unknown<E>(events: &mut impl AddEvent<E>, make: impl Fn() -> E) {
+    events.new_event(make());
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use hyper_fold::ecs::events::core;
+
+hyper_fold::game_crate!();
+
+#[hyper_fold::component]
+struct Position(i32, i32);
+
+hyper_fold::components!(Positions, pos: &'a mut Position);
+
+#[hyper_fold::system]
+fn update(_: &core::Update, positions: Vec<Positions>) {}
+
+fn main() {}
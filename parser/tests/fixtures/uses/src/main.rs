@@ -0,0 +1,55 @@
+use hyperfold_engine::{components, ecs::events::core};
+
+hyperfold_engine::game_crate!();
+
+mod game {
+    pub mod physics {
+        #[hyperfold_engine::component]
+        pub struct Position(pub i32, pub i32);
+
+        pub mod shapes {
+            use hyperfold_engine::{components, ecs::events::core};
+
+            // Relative to this mod and to the grandparent mod
+            use self::circle::Circle;
+            use super::super::state::Score;
+
+            pub mod circle {
+                #[hyperfold_engine::component]
+                pub struct Circle(pub u32);
+            }
+
+            components!(Circles, circle: &'a Circle);
+
+            #[hyperfold_engine::system]
+            fn grow(_: &core::Update, circles: Vec<Circles>, score: &Score) {}
+        }
+    }
+
+    pub mod state {
+        #[hyperfold_engine::global]
+        pub struct Score(pub u32);
+    }
+}
+
+// Imports of imports
+use game::physics as phys;
+use phys::shapes as geometry;
+use geometry::circle::Circle as Round;
+
+#[hyperfold_engine::component]
+struct Wrapper<T>(T);
+
+// Generic arguments follow imports the same way
+components!(
+    Shapes,
+    pos: &'a phys::Position,
+    round: &'a Round,
+    wrapped: &'a Wrapper<Round>,
+    count: &'a Wrapper<::std::primitive::u32>
+);
+
+#[hyperfold_engine::system]
+fn shapes(_: &core::Update, shapes: Vec<Shapes>, score: &mut game::state::Score) {}
+
+fn main() {}
@@ -0,0 +1,300 @@
+#[allow(unused_imports)]
+#[allow(unused_variables)]
+#[allow(unused_parens)]
+#[allow(dead_code)]
+#[allow(unused_doc_comments)]
+pub mod _engine {
+    struct GFoo {
+        g_67da7766: crate::Score,
+        g_8ab9fcd0: hyperfold_engine::ecs::entities::EntityTrash,
+        g_b6acaec9: hyperfold_engine::framework::event_system::mouse::DragState,
+        g_d079234a: hyperfold_engine::framework::render_system::Renderer,
+        g_49b5443e: hyperfold_engine::framework::render_system::AssetManager,
+        g_6017212d: hyperfold_engine::framework::render_system::Screen,
+        g_80f7efe2: hyperfold_engine::framework::render_system::Camera,
+        g_5d3255a6: hyperfold_engine::utils::event::Event,
+        g_ebe01264: crate::_engine::CFoo,
+        g_e432d496: crate::_engine::EFoo,
+    }
+    impl GFoo {
+        fn new() -> Self {
+            Self {
+                g_67da7766: <crate::Score>::new(),
+                g_8ab9fcd0: <hyperfold_engine::ecs::entities::EntityTrash>::new(),
+                g_b6acaec9: <hyperfold_engine::framework::event_system::mouse::DragState>::new(),
+                g_d079234a: <hyperfold_engine::framework::render_system::Renderer>::new(),
+                g_49b5443e: <hyperfold_engine::framework::render_system::AssetManager>::new(),
+                g_6017212d: <hyperfold_engine::framework::render_system::Screen>::new(),
+                g_80f7efe2: <hyperfold_engine::framework::render_system::Camera>::new(),
+                g_5d3255a6: <hyperfold_engine::utils::event::Event>::new(),
+                g_ebe01264: <crate::_engine::CFoo>::new(),
+                g_e432d496: <crate::_engine::EFoo>::new(),
+            }
+        }
+    }
+    struct CFoo {
+        eids: hyperfold_engine::ecs::entities::EntitySet,
+        /// Component crate::platform::Health at $DIR/src/platform/linux.rs:1:1
+        c_e3fa5c19: hyperfold_engine::ecs::entities::EntityMap<crate::platform::Health>,
+        /// Component crate::inline::Tag at $DIR/src/main.rs:65:5
+        c_7c94fd9f: hyperfold_engine::ecs::entities::EntityMap<crate::inline::Tag>,
+        /// Component crate::Position at $DIR/src/main.rs:10:1
+        c_17ec3cdf: hyperfold_engine::ecs::entities::EntityMap<crate::Position>,
+        /// Component crate::Velocity at $DIR/src/main.rs:13:1
+        c_240ed297: hyperfold_engine::ecs::entities::EntityMap<crate::Velocity>,
+        /// Component crate::Player at $DIR/src/main.rs:16:1
+        c_04700cd1: hyperfold_engine::ecs::components::Singleton<crate::Player>,
+        /// Component hyperfold_engine::framework::event_system::mouse::DragTrigger at $ENGINE/src/framework/event_system/mouse.rs:36:1
+        c_5b08ca18: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::event_system::mouse::DragTrigger,
+        >,
+        /// Component hyperfold_engine::framework::physics::Position at $ENGINE/src/framework/physics.rs:8:1
+        c_1dc06187: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::physics::Position,
+        >,
+        /// Component hyperfold_engine::framework::physics::HitBox at $ENGINE/src/framework/physics.rs:12:1
+        c_45d6f9c6: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::physics::HitBox,
+        >,
+        /// Component hyperfold_engine::framework::physics::PhysicsData at $ENGINE/src/framework/physics.rs:16:1
+        c_e6caf36d: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::physics::PhysicsData,
+        >,
+        /// Component hyperfold_engine::framework::render_system::render_data::RenderComponent at $ENGINE/src/framework/render_system/render_data.rs:476:1
+        c_790d7de1: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::render_system::render_data::RenderComponent,
+        >,
+        /// Component hyperfold_engine::framework::render_system::render_data::Animation at $ENGINE/src/framework/render_system/render_data.rs:506:1
+        c_66607cdc: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::render_system::render_data::Animation,
+        >,
+        /// Component hyperfold_engine::framework::render_system::render_text::RenderText at $ENGINE/src/framework/render_system/render_text.rs:29:1
+        c_9c068c1a: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::render_system::render_text::RenderText,
+        >,
+        /// Component hyperfold_engine::framework::render_system::RenderOpts at $ENGINE/src/framework/render_system/mod.rs:106:1
+        c_8698e6d7: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::render_system::RenderOpts,
+        >,
+        /// Component hyperfold_engine::utils::timer::Timer at $ENGINE/src/utils/timer.rs:36:1
+        c_2d350830: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::utils::timer::Timer,
+        >,
+    }
+    pub trait Components: hyperfold_engine::_engine::Components + hyperfold_engine::ecs::components::AddComponent<
+            crate::platform::Health,
+        > + hyperfold_engine::ecs::components::AddComponent<
+            crate::inline::Tag,
+        > + hyperfold_engine::ecs::components::AddComponent<
+            crate::Position,
+        > + hyperfold_engine::ecs::components::AddComponent<
+            crate::Velocity,
+        > + hyperfold_engine::ecs::components::AddComponent<crate::Player> {}
+    impl hyperfold_engine::ecs::components::AddComponent<crate::platform::Health>
+    for CFoo {
+        fn add_component(
+            &mut self,
+            e: hyperfold_engine::ecs::entities::Entity,
+            t: crate::platform::Health,
+        ) {
+            self.eids.insert(e);
+            self.c_e3fa5c19.insert(e, t);
+        }
+    }
+    impl hyperfold_engine::ecs::components::AddComponent<crate::inline::Tag> for CFoo {
+        fn add_component(
+            &mut self,
+            e: hyperfold_engine::ecs::entities::Entity,
+            t: crate::inline::Tag,
+        ) {
+            self.eids.insert(e);
+            self.c_7c94fd9f.insert(e, t);
+        }
+    }
+    impl hyperfold_engine::ecs::components::AddComponent<crate::Position> for CFoo {
+        fn add_component(
+            &mut self,
+            e: hyperfold_engine::ecs::entities::Entity,
+            t: crate::Position,
+        ) {
+            self.eids.insert(e);
+            self.c_17ec3cdf.insert(e, t);
+        }
+    }
+    impl hyperfold_engine::ecs::components::AddComponent<crate::Velocity> for CFoo {
+        fn add_component(
+            &mut self,
+            e: hyperfold_engine::ecs::entities::Entity,
+            t: crate::Velocity,
+        ) {
+            self.eids.insert(e);
+            self.c_240ed297.insert(e, t);
+        }
+    }
+    impl hyperfold_engine::ecs::components::AddComponent<crate::Player> for CFoo {
+        fn add_component(
+            &mut self,
+            e: hyperfold_engine::ecs::entities::Entity,
+            t: crate::Player,
+        ) {
+            self.eids.insert(e);
+            self.c_04700cd1 = hyperfold_engine::ecs::components::Singleton::new(e, t);
+        }
+    }
+    struct EFoo {
+        e_eb5b35f4: Vec<crate::Collision>,
+        e_336809c1: Vec<hyperfold_engine::ecs::events::core::Update>,
+        e_8d9dfc81: Vec<hyperfold_engine::ecs::events::core::Events>,
+        e_07c606c1: Vec<hyperfold_engine::ecs::events::core::PreRender>,
+        e_e4c6f410: Vec<hyperfold_engine::ecs::events::core::Render>,
+        e_0e6680a8: Vec<hyperfold_engine::framework::event_system::event::Mouse>,
+        e_ce3d3278: Vec<hyperfold_engine::framework::event_system::event::Key>,
+        e_283a947a: Vec<hyperfold_engine::framework::event_system::mouse::Click>,
+        e_b1b12418: Vec<hyperfold_engine::framework::event_system::mouse::DragStart>,
+        e_b5a79594: Vec<hyperfold_engine::framework::event_system::mouse::Drag>,
+        e_b6a74789: Vec<hyperfold_engine::framework::event_system::mouse::DragEnd>,
+        e_2585484a: Vec<hyperfold_engine::framework::physics::BoundaryCollision>,
+        events: std::collections::VecDeque<(E, usize)>,
+        state: Option<S>,
+        exiting_state: bool,
+    }
+    pub trait Events: hyperfold_engine::_engine::Events + hyperfold_engine::ecs::events::AddEvent<
+            crate::Collision,
+        > {}
+    impl hyperfold_engine::ecs::events::AddEvent<crate::Collision> for EFoo {
+        fn new_event(&mut self, t: crate::Collision) {
+            self.e_eb5b35f4.push(t);
+            self.add_event(E::EEB5B35F4);
+        }
+        fn get_event<'a>(&'a self) -> Option<&'a crate::Collision> {
+            self.e_eb5b35f4.last()
+        }
+    }
+    impl SFoo {
+        fn add_systems(&mut self) {
+            /// System crate::movement at $DIR/src/main.rs:35:4
+            self.add_system(
+                E::E336809C1,
+                Box::new(|cfoo: &mut CFoo, gfoo: &mut GFoo, efoo: &mut EFoo| {
+                    if let Some(e) = hyperfold_engine::ecs::events::AddEvent::get_event(
+                        efoo,
+                    ) {
+                        let cs_90df211a = Self::get_cs_90df211a_keys_vec(
+                            cfoo,
+                            &cfoo.eids,
+                        );
+                        let cs_90df211a = hyperfold_engine::intersect::intersect(
+                            cs_90df211a.into_iter(),
+                            cfoo.c_17ec3cdf.iter_mut(),
+                            |_, v| v,
+                        );
+                        let cs_90df211a = hyperfold_engine::intersect::intersect(
+                            cs_90df211a.into_iter(),
+                            cfoo.c_240ed297.iter(),
+                            |(v0), v1| (v0, v1),
+                        );
+                        let cs_90df211a = cs_90df211a
+                            .into_iter()
+                            .map(|(k, (c_17ec3cdf, c_240ed297))| crate::Moving {
+                                eid: k,
+                                pos: c_17ec3cdf,
+                                vel: c_240ed297,
+                            })
+                            .collect();
+                        crate::movement(e, cs_90df211a, &mut gfoo.g_e432d496)
+                    }
+                }),
+            );
+            /// System crate::on_collision at $DIR/src/main.rs:46:4
+            self.add_system(
+                E::EEB5B35F4,
+                Box::new(|cfoo: &mut CFoo, gfoo: &mut GFoo, efoo: &mut EFoo| {
+                    if let Some(e) = hyperfold_engine::ecs::events::AddEvent::get_event(
+                        efoo,
+                    ) {
+                        let cs_c97c2fc5 = Self::get_cs_c97c2fc5_keys(cfoo, &cfoo.eids);
+                        let cs_c97c2fc5 = cs_c97c2fc5
+                            .and_then(|k| match (cfoo.c_17ec3cdf.get(k)) {
+                                (Some(c_17ec3cdf)) => {
+                                    Some(crate::PlayerPos {
+                                        eid: k,
+                                        pos: c_17ec3cdf,
+                                    })
+                                }
+                                _ => None,
+                            });
+                        if let (Some(cs_c97c2fc5),) = (cs_c97c2fc5,) {
+                            crate::on_collision(e, cs_c97c2fc5, &mut gfoo.g_67da7766)
+                        }
+                    }
+                }),
+            );
+            /// System crate::aliases at $DIR/src/main.rs:58:4
+            self.add_system(
+                E::EEB5B35F4,
+                Box::new(|cfoo: &mut CFoo, gfoo: &mut GFoo, efoo: &mut EFoo| {
+                    if let Some(e) = hyperfold_engine::ecs::events::AddEvent::get_event(
+                        efoo,
+                    ) {
+                        let cs_90df211a = Self::get_cs_90df211a_keys_vec(
+                            cfoo,
+                            &cfoo.eids,
+                        );
+                        let cs_90df211a = hyperfold_engine::intersect::intersect(
+                            cs_90df211a.into_iter(),
+                            cfoo.c_17ec3cdf.iter_mut(),
+                            |_, v| v,
+                        );
+                        let cs_90df211a = hyperfold_engine::intersect::intersect(
+                            cs_90df211a.into_iter(),
+                            cfoo.c_240ed297.iter(),
+                            |(v0), v1| (v0, v1),
+                        );
+                        let cs_90df211a = cs_90df211a
+                            .into_iter()
+                            .map(|(k, (c_17ec3cdf, c_240ed297))| crate::Moving {
+                                eid: k,
+                                pos: c_17ec3cdf,
+                                vel: c_240ed297,
+                            })
+                            .collect();
+                        crate::aliases(e, cs_90df211a, &mut gfoo.g_67da7766)
+                    }
+                }),
+            );
+            /// System crate::heal at $DIR/src/main.rs:72:4
+            self.add_system(
+                E::E336809C1,
+                Box::new(|cfoo: &mut CFoo, gfoo: &mut GFoo, efoo: &mut EFoo| {
+                    if let Some(e) = hyperfold_engine::ecs::events::AddEvent::get_event(
+                        efoo,
+                    ) {
+                        let cs_5cbed920 = Self::get_cs_5cbed920_keys_vec(
+                            cfoo,
+                            &cfoo.eids,
+                        );
+                        let cs_5cbed920 = hyperfold_engine::intersect::intersect(
+                            cs_5cbed920.into_iter(),
+                            cfoo.c_e3fa5c19.iter_mut(),
+                            |_, v| v,
+                        );
+                        let cs_5cbed920 = cs_5cbed920
+                            .into_iter()
+                            .map(|(k, (c_e3fa5c19))| crate::Tagged {
+                                eid: k,
+                                health: c_e3fa5c19,
+                            })
+                            .collect();
+                        crate::heal(e, cs_5cbed920)
+                    }
+                }),
+            );
+        }
+    }
+    impl SFoo {
+        fn init(&mut self) {
+            /// System crate::init at $DIR/src/main.rs:30:4
+            crate::init(&mut self.gfoo.g_67da7766);
+        }
+    }
+}
@@ -0,0 +1,270 @@
+#[allow(unused_imports)]
+#[allow(unused_variables)]
+#[allow(unused_parens)]
+#[allow(dead_code)]
+#[allow(unused_doc_comments)]
+pub mod _engine {
+    struct CFoo {
+        eids: hyperfold_engine::ecs::entities::EntitySet,
+        /// Component crate::Position at $DIR/src/main.rs:10:1
+        c_17ec3cdf: hyperfold_engine::ecs::entities::EntityMap<crate::Position>,
+        /// Component crate::Unused at $DIR/src/main.rs:13:1
+        c_2afb8496: hyperfold_engine::ecs::entities::EntityMap<crate::Unused>,
+        /// Component crate::AllowedUnused at $DIR/src/main.rs:16:1
+        c_1e927fce: hyperfold_engine::ecs::entities::EntityMap<crate::AllowedUnused>,
+        /// Component crate::Added at $DIR/src/main.rs:20:1
+        c_d5903626: hyperfold_engine::ecs::entities::EntityMap<crate::Added>,
+        /// Component hyperfold_engine::framework::event_system::mouse::DragTrigger at $ENGINE/src/framework/event_system/mouse.rs:36:1
+        c_5b08ca18: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::event_system::mouse::DragTrigger,
+        >,
+        /// Component hyperfold_engine::framework::physics::Position at $ENGINE/src/framework/physics.rs:8:1
+        c_1dc06187: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::physics::Position,
+        >,
+        /// Component hyperfold_engine::framework::physics::HitBox at $ENGINE/src/framework/physics.rs:12:1
+        c_45d6f9c6: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::physics::HitBox,
+        >,
+        /// Component hyperfold_engine::framework::physics::PhysicsData at $ENGINE/src/framework/physics.rs:16:1
+        c_e6caf36d: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::physics::PhysicsData,
+        >,
+        /// Component hyperfold_engine::framework::render_system::render_data::RenderComponent at $ENGINE/src/framework/render_system/render_data.rs:476:1
+        c_790d7de1: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::render_system::render_data::RenderComponent,
+        >,
+        /// Component hyperfold_engine::framework::render_system::render_data::Animation at $ENGINE/src/framework/render_system/render_data.rs:506:1
+        c_66607cdc: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::render_system::render_data::Animation,
+        >,
+        /// Component hyperfold_engine::framework::render_system::render_text::RenderText at $ENGINE/src/framework/render_system/render_text.rs:29:1
+        c_9c068c1a: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::render_system::render_text::RenderText,
+        >,
+        /// Component hyperfold_engine::framework::render_system::RenderOpts at $ENGINE/src/framework/render_system/mod.rs:106:1
+        c_8698e6d7: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::render_system::RenderOpts,
+        >,
+        /// Component hyperfold_engine::utils::timer::Timer at $ENGINE/src/utils/timer.rs:36:1
+        c_2d350830: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::utils::timer::Timer,
+        >,
+        /// Component crate::Tagged<u8> at $DIR/src/main.rs:24:1
+        c_49eb4b3d: hyperfold_engine::ecs::entities::EntityMap<crate::Tagged<u8>>,
+    }
+    pub trait Components: hyperfold_engine::_engine::Components + hyperfold_engine::ecs::components::AddComponent<
+            crate::Position,
+        > + hyperfold_engine::ecs::components::AddComponent<
+            crate::Unused,
+        > + hyperfold_engine::ecs::components::AddComponent<
+            crate::AllowedUnused,
+        > + hyperfold_engine::ecs::components::AddComponent<
+            crate::Added,
+        > + hyperfold_engine::ecs::components::AddComponent<crate::Tagged<u8>> {}
+    impl hyperfold_engine::ecs::components::AddComponent<crate::Position> for CFoo {
+        fn add_component(
+            &mut self,
+            e: hyperfold_engine::ecs::entities::Entity,
+            t: crate::Position,
+        ) {
+            self.eids.insert(e);
+            self.c_17ec3cdf.insert(e, t);
+        }
+    }
+    impl hyperfold_engine::ecs::components::AddComponent<crate::Unused> for CFoo {
+        fn add_component(
+            &mut self,
+            e: hyperfold_engine::ecs::entities::Entity,
+            t: crate::Unused,
+        ) {
+            self.eids.insert(e);
+            self.c_2afb8496.insert(e, t);
+        }
+    }
+    impl hyperfold_engine::ecs::components::AddComponent<crate::AllowedUnused> for CFoo {
+        fn add_component(
+            &mut self,
+            e: hyperfold_engine::ecs::entities::Entity,
+            t: crate::AllowedUnused,
+        ) {
+            self.eids.insert(e);
+            self.c_1e927fce.insert(e, t);
+        }
+    }
+    impl hyperfold_engine::ecs::components::AddComponent<crate::Added> for CFoo {
+        fn add_component(
+            &mut self,
+            e: hyperfold_engine::ecs::entities::Entity,
+            t: crate::Added,
+        ) {
+            self.eids.insert(e);
+            self.c_d5903626.insert(e, t);
+        }
+    }
+    impl hyperfold_engine::ecs::components::AddComponent<crate::Tagged<u8>> for CFoo {
+        fn add_component(
+            &mut self,
+            e: hyperfold_engine::ecs::entities::Entity,
+            t: crate::Tagged<u8>,
+        ) {
+            self.eids.insert(e);
+            self.c_49eb4b3d.insert(e, t);
+        }
+    }
+    struct EFoo {
+        e_f99d7c0f: Vec<crate::Unhandled>,
+        e_70920f87: Vec<crate::AllowedUnhandled>,
+        e_b7600c14: Vec<crate::NeverSent>,
+        e_401f6a5a: Vec<crate::Sometimes>,
+        e_11430e5c: Vec<crate::FromVariable>,
+        e_336809c1: Vec<hyperfold_engine::ecs::events::core::Update>,
+        e_8d9dfc81: Vec<hyperfold_engine::ecs::events::core::Events>,
+        e_07c606c1: Vec<hyperfold_engine::ecs::events::core::PreRender>,
+        e_e4c6f410: Vec<hyperfold_engine::ecs::events::core::Render>,
+        e_0e6680a8: Vec<hyperfold_engine::framework::event_system::event::Mouse>,
+        e_ce3d3278: Vec<hyperfold_engine::framework::event_system::event::Key>,
+        e_283a947a: Vec<hyperfold_engine::framework::event_system::mouse::Click>,
+        e_b1b12418: Vec<hyperfold_engine::framework::event_system::mouse::DragStart>,
+        e_b5a79594: Vec<hyperfold_engine::framework::event_system::mouse::Drag>,
+        e_b6a74789: Vec<hyperfold_engine::framework::event_system::mouse::DragEnd>,
+        e_2585484a: Vec<hyperfold_engine::framework::physics::BoundaryCollision>,
+        events: std::collections::VecDeque<(E, usize)>,
+        state: Option<S>,
+        exiting_state: bool,
+    }
+    pub trait Events: hyperfold_engine::_engine::Events + hyperfold_engine::ecs::events::AddEvent<
+            crate::Unhandled,
+        > + hyperfold_engine::ecs::events::AddEvent<
+            crate::AllowedUnhandled,
+        > + hyperfold_engine::ecs::events::AddEvent<
+            crate::NeverSent,
+        > + hyperfold_engine::ecs::events::AddEvent<
+            crate::Sometimes,
+        > + hyperfold_engine::ecs::events::AddEvent<crate::FromVariable> {}
+    impl hyperfold_engine::ecs::events::AddEvent<crate::Unhandled> for EFoo {
+        fn new_event(&mut self, t: crate::Unhandled) {
+            self.e_f99d7c0f.push(t);
+            self.add_event(E::EF99D7C0F);
+        }
+        fn get_event<'a>(&'a self) -> Option<&'a crate::Unhandled> {
+            self.e_f99d7c0f.last()
+        }
+    }
+    impl hyperfold_engine::ecs::events::AddEvent<crate::AllowedUnhandled> for EFoo {
+        fn new_event(&mut self, t: crate::AllowedUnhandled) {
+            self.e_70920f87.push(t);
+            self.add_event(E::E70920F87);
+        }
+        fn get_event<'a>(&'a self) -> Option<&'a crate::AllowedUnhandled> {
+            self.e_70920f87.last()
+        }
+    }
+    impl hyperfold_engine::ecs::events::AddEvent<crate::NeverSent> for EFoo {
+        fn new_event(&mut self, t: crate::NeverSent) {
+            self.e_b7600c14.push(t);
+            self.add_event(E::EB7600C14);
+        }
+        fn get_event<'a>(&'a self) -> Option<&'a crate::NeverSent> {
+            self.e_b7600c14.last()
+        }
+    }
+    impl hyperfold_engine::ecs::events::AddEvent<crate::Sometimes> for EFoo {
+        fn new_event(&mut self, t: crate::Sometimes) {
+            self.e_401f6a5a.push(t);
+            self.add_event(E::E401F6A5A);
+        }
+        fn get_event<'a>(&'a self) -> Option<&'a crate::Sometimes> {
+            self.e_401f6a5a.last()
+        }
+    }
+    impl hyperfold_engine::ecs::events::AddEvent<crate::FromVariable> for EFoo {
+        fn new_event(&mut self, t: crate::FromVariable) {
+            self.e_11430e5c.push(t);
+            self.add_event(E::E11430E5C);
+        }
+        fn get_event<'a>(&'a self) -> Option<&'a crate::FromVariable> {
+            self.e_11430e5c.last()
+        }
+    }
+    impl SFoo {
+        fn add_systems(&mut self) {
+            /// System crate::update at $DIR/src/main.rs:46:4
+            self.add_system(
+                E::E336809C1,
+                Box::new(|cfoo: &mut CFoo, gfoo: &mut GFoo, efoo: &mut EFoo| {
+                    if let Some(e) = hyperfold_engine::ecs::events::AddEvent::get_event(
+                        efoo,
+                    ) {
+                        let cs_54e382c4 = Self::get_cs_54e382c4_keys_vec(
+                            cfoo,
+                            &cfoo.eids,
+                        );
+                        let cs_54e382c4 = hyperfold_engine::intersect::intersect(
+                            cs_54e382c4.into_iter(),
+                            cfoo.c_17ec3cdf.iter(),
+                            |_, v| v,
+                        );
+                        let cs_54e382c4 = cs_54e382c4
+                            .into_iter()
+                            .map(|(k, (c_17ec3cdf))| crate::Positions {
+                                eid: k,
+                                pos: c_17ec3cdf,
+                            })
+                            .collect();
+                        crate::update(e, cs_54e382c4)
+                    }
+                }),
+            );
+            /// System crate::unreachable at $DIR/src/main.rs:49:4
+            self.add_system(
+                E::EB7600C14,
+                Box::new(|cfoo: &mut CFoo, gfoo: &mut GFoo, efoo: &mut EFoo| {
+                    if let Some(e) = hyperfold_engine::ecs::events::AddEvent::get_event(
+                        efoo,
+                    ) {
+                        let cs_54e382c4 = Self::get_cs_54e382c4_keys_vec(
+                            cfoo,
+                            &cfoo.eids,
+                        );
+                        let cs_54e382c4 = hyperfold_engine::intersect::intersect(
+                            cs_54e382c4.into_iter(),
+                            cfoo.c_17ec3cdf.iter(),
+                            |_, v| v,
+                        );
+                        let cs_54e382c4 = cs_54e382c4
+                            .into_iter()
+                            .map(|(k, (c_17ec3cdf))| crate::Positions {
+                                eid: k,
+                                pos: c_17ec3cdf,
+                            })
+                            .collect();
+                        crate::unreachable(e, cs_54e382c4)
+                    }
+                }),
+            );
+            /// System crate::allowed at $DIR/src/main.rs:52:4
+            self.add_system(
+                E::E401F6A5A,
+                Box::new(|cfoo: &mut CFoo, gfoo: &mut GFoo, efoo: &mut EFoo| {
+                    if let Some(e) = hyperfold_engine::ecs::events::AddEvent::get_event(
+                        efoo,
+                    ) {
+                        crate::allowed(e)
+                    }
+                }),
+            );
+            /// System crate::from_variable at $DIR/src/main.rs:55:4
+            self.add_system(
+                E::E11430E5C,
+                Box::new(|cfoo: &mut CFoo, gfoo: &mut GFoo, efoo: &mut EFoo| {
+                    if let Some(e) = hyperfold_engine::ecs::events::AddEvent::get_event(
+                        efoo,
+                    ) {
+                        crate::from_variable(e)
+                    }
+                }),
+            );
+        }
+    }
+}
@@ -0,0 +1,90 @@
+#[allow(unused_imports)]
+#[allow(unused_variables)]
+#[allow(unused_parens)]
+#[allow(dead_code)]
+#[allow(unused_doc_comments)]
+pub mod _engine {
+    struct CFoo {
+        eids: hyper_fold::ecs::entities::EntitySet,
+        /// Component crate::Position at $DIR/src/main.rs:5:1
+        c_17ec3cdf: hyper_fold::ecs::entities::EntityMap<crate::Position>,
+        /// Component hyper_fold::framework::event_system::mouse::DragTrigger at $ENGINE/src/framework/event_system/mouse.rs:36:1
+        c_a6658744: hyper_fold::ecs::entities::EntityMap<
+            hyper_fold::framework::event_system::mouse::DragTrigger,
+        >,
+        /// Component hyper_fold::framework::physics::Position at $ENGINE/src/framework/physics.rs:8:1
+        c_0fa9ce33: hyper_fold::ecs::entities::EntityMap<
+            hyper_fold::framework::physics::Position,
+        >,
+        /// Component hyper_fold::framework::physics::HitBox at $ENGINE/src/framework/physics.rs:12:1
+        c_818edaf2: hyper_fold::ecs::entities::EntityMap<
+            hyper_fold::framework::physics::HitBox,
+        >,
+        /// Component hyper_fold::framework::physics::PhysicsData at $ENGINE/src/framework/physics.rs:16:1
+        c_8a082781: hyper_fold::ecs::entities::EntityMap<
+            hyper_fold::framework::physics::PhysicsData,
+        >,
+        /// Component hyper_fold::framework::render_system::render_data::RenderComponent at $ENGINE/src/framework/render_system/render_data.rs:476:1
+        c_55e36b6d: hyper_fold::ecs::entities::EntityMap<
+            hyper_fold::framework::render_system::render_data::RenderComponent,
+        >,
+        /// Component hyper_fold::framework::render_system::render_data::Animation at $ENGINE/src/framework/render_system/render_data.rs:506:1
+        c_57fe2828: hyper_fold::ecs::entities::EntityMap<
+            hyper_fold::framework::render_system::render_data::Animation,
+        >,
+        /// Component hyper_fold::framework::render_system::render_text::RenderText at $ENGINE/src/framework/render_system/render_text.rs:29:1
+        c_9963bd56: hyper_fold::ecs::entities::EntityMap<
+            hyper_fold::framework::render_system::render_text::RenderText,
+        >,
+        /// Component hyper_fold::framework::render_system::RenderOpts at $ENGINE/src/framework/render_system/mod.rs:106:1
+        c_28b1b3f3: hyper_fold::ecs::entities::EntityMap<
+            hyper_fold::framework::render_system::RenderOpts,
+        >,
+        /// Component hyper_fold::utils::timer::Timer at $ENGINE/src/utils/timer.rs:36:1
+        c_34f6e4d4: hyper_fold::ecs::entities::EntityMap<
+            hyper_fold::utils::timer::Timer,
+        >,
+    }
+    pub trait Components: hyper_fold::_engine::Components + hyper_fold::ecs::components::AddComponent<
+            crate::Position,
+        > {}
+    impl hyper_fold::ecs::components::AddComponent<crate::Position> for CFoo {
+        fn add_component(
+            &mut self,
+            e: hyper_fold::ecs::entities::Entity,
+            t: crate::Position,
+        ) {
+            self.eids.insert(e);
+            self.c_17ec3cdf.insert(e, t);
+        }
+    }
+    impl SFoo {
+        fn add_systems(&mut self) {
+            /// System crate::update at $DIR/src/main.rs:11:4
+            self.add_system(
+                E::E39D7791D,
+                Box::new(|cfoo: &mut CFoo, gfoo: &mut GFoo, efoo: &mut EFoo| {
+                    if let Some(e) = hyper_fold::ecs::events::AddEvent::get_event(efoo) {
+                        let cs_54e382c4 = Self::get_cs_54e382c4_keys_vec(
+                            cfoo,
+                            &cfoo.eids,
+                        );
+                        let cs_54e382c4 = hyper_fold::intersect::intersect(
+                            cs_54e382c4.into_iter(),
+                            cfoo.c_17ec3cdf.iter_mut(),
+                            |_, v| v,
+                        );
+                        let cs_54e382c4 = cs_54e382c4
+                            .into_iter()
+                            .map(|(k, (c_17ec3cdf))| crate::Positions {
+                                eid: k,
+                                pos: c_17ec3cdf,
+                            })
+                            .collect();
+                        crate::update(e, cs_54e382c4)
+                    }
+                }),
+            );
+        }
+    }
+}
@@ -0,0 +1,226 @@
+#[allow(unused_imports)]
+#[allow(unused_variables)]
+#[allow(unused_parens)]
+#[allow(dead_code)]
+#[allow(unused_doc_comments)]
+pub mod _engine {
+    struct GFoo {
+        g_05ad8aa3: crate::game::state::Score,
+        g_8ab9fcd0: hyperfold_engine::ecs::entities::EntityTrash,
+        g_b6acaec9: hyperfold_engine::framework::event_system::mouse::DragState,
+        g_d079234a: hyperfold_engine::framework::render_system::Renderer,
+        g_49b5443e: hyperfold_engine::framework::render_system::AssetManager,
+        g_6017212d: hyperfold_engine::framework::render_system::Screen,
+        g_80f7efe2: hyperfold_engine::framework::render_system::Camera,
+        g_5d3255a6: hyperfold_engine::utils::event::Event,
+        g_ebe01264: crate::_engine::CFoo,
+        g_e432d496: crate::_engine::EFoo,
+    }
+    impl GFoo {
+        fn new() -> Self {
+            Self {
+                g_05ad8aa3: <crate::game::state::Score>::new(),
+                g_8ab9fcd0: <hyperfold_engine::ecs::entities::EntityTrash>::new(),
+                g_b6acaec9: <hyperfold_engine::framework::event_system::mouse::DragState>::new(),
+                g_d079234a: <hyperfold_engine::framework::render_system::Renderer>::new(),
+                g_49b5443e: <hyperfold_engine::framework::render_system::AssetManager>::new(),
+                g_6017212d: <hyperfold_engine::framework::render_system::Screen>::new(),
+                g_80f7efe2: <hyperfold_engine::framework::render_system::Camera>::new(),
+                g_5d3255a6: <hyperfold_engine::utils::event::Event>::new(),
+                g_ebe01264: <crate::_engine::CFoo>::new(),
+                g_e432d496: <crate::_engine::EFoo>::new(),
+            }
+        }
+    }
+    struct CFoo {
+        eids: hyperfold_engine::ecs::entities::EntitySet,
+        /// Component crate::game::physics::shapes::circle::Circle at $DIR/src/main.rs:18:17
+        c_25ef849d: hyperfold_engine::ecs::entities::EntityMap<
+            crate::game::physics::shapes::circle::Circle,
+        >,
+        /// Component crate::game::physics::Position at $DIR/src/main.rs:7:9
+        c_d90287e8: hyperfold_engine::ecs::entities::EntityMap<
+            crate::game::physics::Position,
+        >,
+        /// Component hyperfold_engine::framework::event_system::mouse::DragTrigger at $ENGINE/src/framework/event_system/mouse.rs:36:1
+        c_5b08ca18: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::event_system::mouse::DragTrigger,
+        >,
+        /// Component hyperfold_engine::framework::physics::Position at $ENGINE/src/framework/physics.rs:8:1
+        c_1dc06187: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::physics::Position,
+        >,
+        /// Component hyperfold_engine::framework::physics::HitBox at $ENGINE/src/framework/physics.rs:12:1
+        c_45d6f9c6: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::physics::HitBox,
+        >,
+        /// Component hyperfold_engine::framework::physics::PhysicsData at $ENGINE/src/framework/physics.rs:16:1
+        c_e6caf36d: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::physics::PhysicsData,
+        >,
+        /// Component hyperfold_engine::framework::render_system::render_data::RenderComponent at $ENGINE/src/framework/render_system/render_data.rs:476:1
+        c_790d7de1: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::render_system::render_data::RenderComponent,
+        >,
+        /// Component hyperfold_engine::framework::render_system::render_data::Animation at $ENGINE/src/framework/render_system/render_data.rs:506:1
+        c_66607cdc: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::render_system::render_data::Animation,
+        >,
+        /// Component hyperfold_engine::framework::render_system::render_text::RenderText at $ENGINE/src/framework/render_system/render_text.rs:29:1
+        c_9c068c1a: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::render_system::render_text::RenderText,
+        >,
+        /// Component hyperfold_engine::framework::render_system::RenderOpts at $ENGINE/src/framework/render_system/mod.rs:106:1
+        c_8698e6d7: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::framework::render_system::RenderOpts,
+        >,
+        /// Component hyperfold_engine::utils::timer::Timer at $ENGINE/src/utils/timer.rs:36:1
+        c_2d350830: hyperfold_engine::ecs::entities::EntityMap<
+            hyperfold_engine::utils::timer::Timer,
+        >,
+        /// Component crate::Wrapper<crate::game::physics::shapes::circle::Circle> at $DIR/src/main.rs:40:1
+        c_15bb9bfd: hyperfold_engine::ecs::entities::EntityMap<
+            crate::Wrapper<crate::game::physics::shapes::circle::Circle>,
+        >,
+        /// Component crate::Wrapper<std::primitive::u32> at $DIR/src/main.rs:40:1
+        c_32fa2ee3: hyperfold_engine::ecs::entities::EntityMap<
+            crate::Wrapper<std::primitive::u32>,
+        >,
+    }
+    pub trait Components: hyperfold_engine::_engine::Components + hyperfold_engine::ecs::components::AddComponent<
+            crate::game::physics::shapes::circle::Circle,
+        > + hyperfold_engine::ecs::components::AddComponent<
+            crate::game::physics::Position,
+        > + hyperfold_engine::ecs::components::AddComponent<
+            crate::Wrapper<crate::game::physics::shapes::circle::Circle>,
+        > + hyperfold_engine::ecs::components::AddComponent<
+            crate::Wrapper<std::primitive::u32>,
+        > {}
+    impl hyperfold_engine::ecs::components::AddComponent<
+        crate::game::physics::shapes::circle::Circle,
+    > for CFoo {
+        fn add_component(
+            &mut self,
+            e: hyperfold_engine::ecs::entities::Entity,
+            t: crate::game::physics::shapes::circle::Circle,
+        ) {
+            self.eids.insert(e);
+            self.c_25ef849d.insert(e, t);
+        }
+    }
+    impl hyperfold_engine::ecs::components::AddComponent<crate::game::physics::Position>
+    for CFoo {
+        fn add_component(
+            &mut self,
+            e: hyperfold_engine::ecs::entities::Entity,
+            t: crate::game::physics::Position,
+        ) {
+            self.eids.insert(e);
+            self.c_d90287e8.insert(e, t);
+        }
+    }
+    impl hyperfold_engine::ecs::components::AddComponent<
+        crate::Wrapper<crate::game::physics::shapes::circle::Circle>,
+    > for CFoo {
+        fn add_component(
+            &mut self,
+            e: hyperfold_engine::ecs::entities::Entity,
+            t: crate::Wrapper<crate::game::physics::shapes::circle::Circle>,
+        ) {
+            self.eids.insert(e);
+            self.c_15bb9bfd.insert(e, t);
+        }
+    }
+    impl hyperfold_engine::ecs::components::AddComponent<
+        crate::Wrapper<std::primitive::u32>,
+    > for CFoo {
+        fn add_component(
+            &mut self,
+            e: hyperfold_engine::ecs::entities::Entity,
+            t: crate::Wrapper<std::primitive::u32>,
+        ) {
+            self.eids.insert(e);
+            self.c_32fa2ee3.insert(e, t);
+        }
+    }
+    impl SFoo {
+        fn add_systems(&mut self) {
+            /// System crate::game::physics::shapes::grow at $DIR/src/main.rs:25:16
+            self.add_system(
+                E::E336809C1,
+                Box::new(|cfoo: &mut CFoo, gfoo: &mut GFoo, efoo: &mut EFoo| {
+                    if let Some(e) = hyperfold_engine::ecs::events::AddEvent::get_event(
+                        efoo,
+                    ) {
+                        let cs_6db0c03e = Self::get_cs_6db0c03e_keys_vec(
+                            cfoo,
+                            &cfoo.eids,
+                        );
+                        let cs_6db0c03e = hyperfold_engine::intersect::intersect(
+                            cs_6db0c03e.into_iter(),
+                            cfoo.c_25ef849d.iter(),
+                            |_, v| v,
+                        );
+                        let cs_6db0c03e = cs_6db0c03e
+                            .into_iter()
+                            .map(|(k, (c_25ef849d))| crate::game::physics::shapes::Circles {
+                                eid: k,
+                                circle: c_25ef849d,
+                            })
+                            .collect();
+                        crate::game::physics::shapes::grow(
+                            e,
+                            cs_6db0c03e,
+                            &gfoo.g_05ad8aa3,
+                        )
+                    }
+                }),
+            );
+            /// System crate::shapes at $DIR/src/main.rs:53:4
+            self.add_system(
+                E::E336809C1,
+                Box::new(|cfoo: &mut CFoo, gfoo: &mut GFoo, efoo: &mut EFoo| {
+                    if let Some(e) = hyperfold_engine::ecs::events::AddEvent::get_event(
+                        efoo,
+                    ) {
+                        let cs_c6a9dee6 = Self::get_cs_c6a9dee6_keys_vec(
+                            cfoo,
+                            &cfoo.eids,
+                        );
+                        let cs_c6a9dee6 = hyperfold_engine::intersect::intersect(
+                            cs_c6a9dee6.into_iter(),
+                            cfoo.c_25ef849d.iter(),
+                            |_, v| v,
+                        );
+                        let cs_c6a9dee6 = hyperfold_engine::intersect::intersect(
+                            cs_c6a9dee6.into_iter(),
+                            cfoo.c_d90287e8.iter(),
+                            |(v0), v1| (v0, v1),
+                        );
+                        let cs_c6a9dee6 = hyperfold_engine::intersect::intersect(
+                            cs_c6a9dee6.into_iter(),
+                            cfoo.c_15bb9bfd.iter(),
+                            |(v0, v1), v2| (v0, v1, v2),
+                        );
+                        let cs_c6a9dee6 = hyperfold_engine::intersect::intersect(
+                            cs_c6a9dee6.into_iter(),
+                            cfoo.c_32fa2ee3.iter(),
+                            |(v0, v1, v2), v3| (v0, v1, v2, v3),
+                        );
+                        let cs_c6a9dee6 = cs_c6a9dee6
+                            .into_iter()
+                            .map(|(k, (c_25ef849d, c_d90287e8, c_15bb9bfd, c_32fa2ee3))| crate::Shapes {
+                                eid: k,
+                                pos: c_d90287e8,
+                                round: c_25ef849d,
+                                wrapped: c_15bb9bfd,
+                                count: c_32fa2ee3,
+                            })
+                            .collect();
+                        crate::shapes(e, cs_c6a9dee6, &mut gfoo.g_05ad8aa3)
+                    }
+                }),
+            );
+        }
+    }
+}
@@ -1,5 +1,8 @@
 pub const INDEX: &str = "hyperfold_engine_index.txt";
 pub const INDEX_SEP: &str = "\t";
+// Path to the index, set by the engine build script
+// Game crates forward it from `DEP_HYPERFOLD_INDEX` in their build scripts
+pub const INDEX_ENV: &str = "HYPERFOLD_INDEX";
 pub const TAB: &str = "  ";
 
 // Game state mod items
@@ -0,0 +1,83 @@
+use crate::{macros::expand_enum, traits::ExpandEnum};
+
+// Set to an error code to print its explanation from the build script
+pub const EXPLAIN_ENV: &str = "HYPERFOLD_EXPLAIN";
+
+// Codes for ECS validation errors - Edit this to add new codes
+// Codes are never reused or renumbered, explanations are in HF####.md
+#[expand_enum]
+#[derive(PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    // Systems
+    ConflictingReferences,
+    MissingEvent,
+    DuplicateEvent,
+    DuplicateGlobal,
+    InvalidInitArgument,
+    MissingSingleton,
+    WrongReferenceCount,
+    WrongMutability,
+    InvalidArgumentType,
+    // Component sets
+    ConstantLabels,
+    InvalidOptional,
+    // Macro arguments
+    UnknownMacroArgument,
+    WrongItemArgument,
+    // Lints
+    UnusedComponent,
+    UnhandledEvent,
+    UnreachableSystem,
+}
+
+impl ErrorCode {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorCode::ConflictingReferences => "HF0001",
+            ErrorCode::MissingEvent => "HF0002",
+            ErrorCode::DuplicateEvent => "HF0003",
+            ErrorCode::DuplicateGlobal => "HF0004",
+            ErrorCode::InvalidInitArgument => "HF0005",
+            ErrorCode::MissingSingleton => "HF0006",
+            ErrorCode::WrongReferenceCount => "HF0007",
+            ErrorCode::WrongMutability => "HF0008",
+            ErrorCode::ConstantLabels => "HF0009",
+            ErrorCode::InvalidOptional => "HF0010",
+            ErrorCode::UnknownMacroArgument => "HF0011",
+            ErrorCode::WrongItemArgument => "HF0012",
+            ErrorCode::UnusedComponent => "HF0013",
+            ErrorCode::UnhandledEvent => "HF0014",
+            ErrorCode::UnreachableSystem => "HF0015",
+            ErrorCode::InvalidArgumentType => "HF0016",
+        }
+    }
+
+    // Long form explanation with examples and fixes
+    pub fn explanation(&self) -> &'static str {
+        match self {
+            ErrorCode::ConflictingReferences => include_str!("HF0001.md"),
+            ErrorCode::MissingEvent => include_str!("HF0002.md"),
+            ErrorCode::DuplicateEvent => include_str!("HF0003.md"),
+            ErrorCode::DuplicateGlobal => include_str!("HF0004.md"),
+            ErrorCode::InvalidInitArgument => include_str!("HF0005.md"),
+            ErrorCode::MissingSingleton => include_str!("HF0006.md"),
+            ErrorCode::WrongReferenceCount => include_str!("HF0007.md"),
+            ErrorCode::WrongMutability => include_str!("HF0008.md"),
+            ErrorCode::ConstantLabels => include_str!("HF0009.md"),
+            ErrorCode::InvalidOptional => include_str!("HF0010.md"),
+            ErrorCode::UnknownMacroArgument => include_str!("HF0011.md"),
+            ErrorCode::WrongItemArgument => include_str!("HF0012.md"),
+            ErrorCode::UnusedComponent => include_str!("HF0013.md"),
+            ErrorCode::UnhandledEvent => include_str!("HF0014.md"),
+            ErrorCode::UnreachableSystem => include_str!("HF0015.md"),
+            ErrorCode::InvalidArgumentType => include_str!("HF0016.md"),
+        }
+    }
+
+    // Case insensitive, e.g. "HF0001" or "hf0001"
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::VARIANTS
+            .into_iter()
+            .find(|c| c.code().eq_ignore_ascii_case(code.trim()))
+    }
+}
@@ -2,6 +2,7 @@
 #![feature(pattern)]
 
 pub mod constants;
+pub mod error_codes;
 pub mod file;
 mod macro_args;
 pub mod syn;
@@ -9,7 +10,9 @@ pub mod traits;
 mod util_macros;
 
 pub mod parsing {
-    pub use crate::macro_args::{ComponentMacroArgs, GlobalMacroArgs, SystemMacroArgs};
+    pub use crate::macro_args::{
+        ComponentMacroArgs, EventMacroArgs, GlobalMacroArgs, SystemMacroArgs,
+    };
 }
 
 pub mod macros {
@@ -3,6 +3,7 @@ use proc_macro2::Span;
 use syn::spanned::Spanned;
 
 use crate::{
+    error_codes::ErrorCode,
     syn::{
         error::{CriticalResult, ToError},
         path_to_vec, Parse,
@@ -37,6 +38,7 @@ where
 pub struct ComponentMacroArgs {
     pub is_dummy: bool,
     pub is_singleton: bool,
+    pub allow_unused: bool,
 }
 
 impl Default for ComponentMacroArgs {
@@ -44,6 +46,7 @@ impl Default for ComponentMacroArgs {
         Self {
             is_dummy: false,
             is_singleton: false,
+            allow_unused: false,
         }
     }
 }
@@ -54,11 +57,17 @@ impl ParseFrom<Vec<syn::Ident>> for ComponentMacroArgs {
         vals.map_vec(|i| match i.to_string().as_str() {
             "Dummy" => Ok(c.is_dummy = true),
             "Singleton" => Ok(c.is_singleton = true),
+            "AllowUnused" => {
+                c.allow_unused = true;
+                Ok(())
+            }
             "Const" => i
                 .error("Component cannot be Const\nPerhaps you meant to declare this as 'global'?")
+                .with_code(ErrorCode::WrongItemArgument)
                 .as_err(),
             _ => i
                 .error(format!("Unknown macro argument for component: {i}"))
+                .with_code(ErrorCode::UnknownMacroArgument)
                 .as_err(),
         })
         .combine_results()?;
@@ -97,8 +106,8 @@ impl ParseFrom<Vec<syn::Ident>> for GlobalMacroArgs {
             "Const" => Ok(g.is_const = true),
             "Singleton" => i.error(
                 "Global cannot be a Singleton\nPerhaps you meant to declare this as 'component'?",
-            ).as_err(),
-            _ => i.error(format!("Unknown macro argument for global: {i}")).as_err(),
+            ).with_code(ErrorCode::WrongItemArgument).as_err(),
+            _ => i.error(format!("Unknown macro argument for global: {i}")).with_code(ErrorCode::UnknownMacroArgument).as_err(),
         }
         })
         .combine_results()
@@ -112,29 +121,69 @@ impl Parse for GlobalMacroArgs {
     }
 }
 
+// Event args
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone, Hash)]
+pub struct EventMacroArgs {
+    pub allow_unused: bool,
+}
+
+impl ParseFrom<Vec<syn::Ident>> for EventMacroArgs {
+    fn parse_from(vals: &Vec<syn::Ident>) -> CriticalResult<Self> {
+        let mut e = Self::default();
+        vals.map_vec(|i| match i.to_string().as_str() {
+            "AllowUnused" => {
+                e.allow_unused = true;
+                Ok(())
+            }
+            _ => i
+                .error(format!("Unknown macro argument for event: {i}"))
+                .with_code(ErrorCode::UnknownMacroArgument)
+                .as_err(),
+        })
+        .combine_results()
+        .map(|_| e)
+    }
+}
+
+impl Parse for EventMacroArgs {
+    fn parse(input: syn::parse::ParseStream) -> CriticalResult<Self> {
+        parse(input)
+    }
+}
+
 // System args
 #[derive(Debug, Clone)]
 pub enum SystemMacroArgs {
     Init(),
-    System { states: Vec<(Vec<String>, Span)> },
+    System {
+        states: Vec<(Vec<String>, Span)>,
+        allow_unreachable: bool,
+    },
 }
 
 impl Default for SystemMacroArgs {
     fn default() -> Self {
-        Self::System { states: Vec::new() }
+        Self::System {
+            states: Vec::new(),
+            allow_unreachable: false,
+        }
     }
 }
 
 impl ParseFrom<Vec<syn::Path>> for SystemMacroArgs {
     fn parse_from(vals: &Vec<syn::Path>) -> CriticalResult<Self> {
         let mut is_init = false;
-        let states = vals.filter_map_vec(|p| {
-            if p.get_ident().is_some_and(|i| i == "Init") {
+        let mut allow_unreachable = false;
+        let states = vals.filter_map_vec(|p| match p.get_ident() {
+            Some(i) if i == "Init" => {
                 is_init = true;
                 None
-            } else {
-                Some((path_to_vec(p), p.span()))
             }
+            Some(i) if i == "AllowUnreachable" => {
+                allow_unreachable = true;
+                None
+            }
+            _ => Some((path_to_vec(p), p.span())),
         });
         match is_init {
             true => match &states[..] {
@@ -144,9 +193,13 @@ impl ParseFrom<Vec<syn::Path>> for SystemMacroArgs {
                         "Unknown macro argument for init system: {}",
                         path.join("::")
                     ))
+                    .with_code(ErrorCode::UnknownMacroArgument)
                 })),
             },
-            false => Ok(Self::System { states }),
+            false => Ok(Self::System {
+                states,
+                allow_unreachable,
+            }),
         }
     }
 }
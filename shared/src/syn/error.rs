@@ -7,11 +7,15 @@ use std::{
 use backtrace::Backtrace;
 use codespan_reporting::{diagnostic::Label, files::SimpleFiles};
 use diagnostic::{
-    CatchErr, CodespanDiagnostic, Diagnostic, DiagnosticLevel, ErrorNote, ErrorSpan, ToErr,
+    CatchErr, CodespanDiagnostic, Diagnostic, DiagnosticCode, DiagnosticLevel, ErrorNote,
+    ErrorSpan, ToErr,
 };
 use syn::spanned::Spanned;
 
-use crate::traits::CollectVec;
+use crate::{
+    error_codes::{ErrorCode, EXPLAIN_ENV},
+    traits::CollectVec,
+};
 
 // ------- STRUCTS -------
 // Represents a file that has been loaded into the Renderer
@@ -57,17 +61,21 @@ impl Renderer {
         self.add_file_impl(Some((cr_idx, m_idx)), file.into(), start_span.into())
     }
 
-    fn add_file_impl(&mut self, idx: Option<(usize, usize)>, file: String, start_span: ErrorSpan) {
+    fn add_file_impl(
+        &mut self,
+        idx: Option<(usize, usize)>,
+        file: String,
+        mut start_span: ErrorSpan,
+    ) {
         self.files.insert(idx, {
             let mut success = Ok(());
+            let contents = fs::read_to_string(file.to_string()).unwrap_or_else(|e| {
+                success = Err(e);
+                String::new()
+            });
+            start_span.byte_start = start_span.file_start(&contents);
             File {
-                id: self.file_list.add(
-                    file.to_string(),
-                    fs::read_to_string(file.to_string()).unwrap_or_else(|e| {
-                        success = Err(e);
-                        String::new()
-                    }),
-                ),
+                id: self.file_list.add(file.to_string(), contents),
                 name: file,
                 start_span,
                 success,
@@ -85,14 +93,11 @@ impl Renderer {
     pub fn render_at(&self, mut diagnostic: CodespanDiagnostic<usize>, span: Span) -> RenderResult {
         const HEADER: &str = "Diagnostic Error";
 
-        // Default span is first byte of the default file
-        let idx = span.cr_idx.zip(span.m_idx);
-        let mut span = span.span.unwrap_or_default();
-
         // Attempt to generate the primary label for this diagnostic
-        let file = match self.files.get(&idx) {
+        let idx = span.cr_idx.zip(span.m_idx);
+        let (file, span) = self.locate(span);
+        match self.files.get(&idx) {
             Some(f) => {
-                span.subtract_bytes(f.start_span.byte_start);
                 diagnostic
                     .labels
                     .push(Label::primary(f.id, span.byte_range()));
@@ -101,7 +106,6 @@ impl Renderer {
                         .notes
                         .push(format!("{HEADER}: Failed to load file: {e}"))
                 }
-                f.name.to_string()
             }
             None => {
                 diagnostic.notes.push(format!(
@@ -109,8 +113,6 @@ impl Renderer {
                     idx.map_or("default mod".to_string(), |(_, m)| format!("mod {m}")),
                     idx.map_or("default crate".to_string(), |(c, _)| format!("crate {c}")),
                 ));
-                span = Default::default();
-                self.default_file.to_string()
             }
         };
         RenderResult {
@@ -122,6 +124,21 @@ impl Renderer {
     }
 }
 
+impl Renderer {
+    // Returns the file and the span relative to the start of the file
+    // Default span is first byte of the default file
+    pub fn locate(&self, span: Span) -> (String, ErrorSpan) {
+        match self.files.get(&span.cr_idx.zip(span.m_idx)) {
+            Some(f) => {
+                let mut err_span = span.span.unwrap_or_default();
+                err_span.subtract_bytes(f.start_span.byte_start);
+                (f.name.to_string(), err_span)
+            }
+            None => (self.default_file.to_string(), ErrorSpan::default()),
+        }
+    }
+}
+
 // Trait for get diagnostic
 pub trait DiagnosticTrait {
     fn diagnostic(&self) -> CodespanDiagnostic<usize>;
@@ -179,6 +196,8 @@ impl SpanTrait for Span {
 pub struct Note {
     pub msg: String,
     pub span: Span,
+    // Suggested text to replace the span with
+    pub replacement: Option<String>,
 }
 
 impl Note {
@@ -186,8 +205,14 @@ impl Note {
         Self {
             msg: msg.into(),
             span: Span::new(),
+            replacement: None,
         }
     }
+
+    pub fn with_replacement(mut self, text: impl Into<String>) -> Self {
+        self.replacement = Some(text.into());
+        self
+    }
 }
 
 impl SpanTrait for Note {
@@ -211,6 +236,7 @@ impl DiagnosticTrait for Note {
 pub struct Error {
     pub level: DiagnosticLevel,
     pub msg: String,
+    pub code: Option<ErrorCode>,
     pub notes: Vec<Note>,
     pub backtrace: Option<Note>,
     pub span: Span,
@@ -222,6 +248,7 @@ impl Error {
         Self {
             level,
             msg: String::new(),
+            code: None,
             notes: Vec::new(),
             backtrace: None,
             span: Span::new(),
@@ -242,6 +269,11 @@ impl Error {
         self
     }
 
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = Some(code);
+        self
+    }
+
     pub fn with_note(mut self, note: Note) -> Self {
         self.notes.push(note);
         self
@@ -280,15 +312,51 @@ impl Error {
     }
 
     pub fn render(&self, renderer: &Renderer) -> Diagnostic {
-        let (diagnostic_notes, error_notes) = self.notes.unzip_vec(|note| {
-            let RenderResult { text, file, span } = renderer.render(note);
-            let msg = note.msg.to_string();
-            (text, ErrorNote { span, msg, file })
-        });
+        let mut diagnostic_notes = Vec::new();
+        let mut error_notes = Vec::new();
+        let mut children = Vec::new();
+        for note in &self.notes {
+            match note.span.span {
+                // Secondary spans
+                Some(_) => {
+                    let RenderResult { text, file, span } = renderer.render(note);
+                    diagnostic_notes.push(text);
+                    error_notes.push(ErrorNote {
+                        span,
+                        file,
+                        msg: note.msg.to_string(),
+                    });
+                }
+                // Plain text, replacements apply to the error's span
+                None => {
+                    let (file, span) = renderer.locate(self.span);
+                    let (level, label) = match note.replacement {
+                        Some(_) => (DiagnosticLevel::Help, "help"),
+                        None => (DiagnosticLevel::Note, "note"),
+                    };
+                    diagnostic_notes.push(format!("{label}: {}", note.msg));
+                    children.push(
+                        Diagnostic::from_span(note.msg.to_string(), file, level, None, span)
+                            .with_replacement(note.replacement.clone()),
+                    );
+                }
+            }
+        }
+        if let Some(code) = self.code {
+            diagnostic_notes.push(format!(
+                "For more information about {0}, set {EXPLAIN_ENV}={0}",
+                code.code()
+            ));
+        }
         let RenderResult { text, file, span } =
             renderer.render_at(self.diagnostic().with_notes(diagnostic_notes), *self.span());
         Diagnostic::from_span(self.msg.to_string(), file, self.level, Some(text), span)
+            .with_code(self.code.map(|code| DiagnosticCode {
+                code: code.code().to_string(),
+                explanation: Some(code.explanation().to_string()),
+            }))
             .with_notes(error_notes)
+            .with_children(children)
     }
 }
 
@@ -304,7 +372,7 @@ impl SpanTrait for Error {
 
 impl DiagnosticTrait for Error {
     fn diagnostic(&self) -> CodespanDiagnostic<usize> {
-        match self.level {
+        let diagnostic = match self.level {
             DiagnosticLevel::Error | DiagnosticLevel::Ice | DiagnosticLevel::FailureNote => {
                 CodespanDiagnostic::error()
             }
@@ -312,7 +380,11 @@ impl DiagnosticTrait for Error {
             DiagnosticLevel::Note => CodespanDiagnostic::note(),
             DiagnosticLevel::Help => CodespanDiagnostic::help(),
         }
-        .with_message(&self.msg)
+        .with_message(&self.msg);
+        match self.code {
+            Some(code) => diagnostic.with_code(code.code()),
+            None => diagnostic,
+        }
     }
 }
 
@@ -27,9 +27,14 @@ pub fn use_path_from_vec(parent_path: &Vec<String>, path: &Vec<String>) -> Vec<S
     res_path
 }
 
+// A leading '::' becomes an empty first segment
 pub fn use_path_from_syn(parent_path: &Vec<String>, path: &syn::Path) -> Vec<String> {
-    use_path_from_vec(
+    let res_path = use_path_from_vec(
         parent_path,
         &path.segments.iter().map(|s| s.ident.to_string()).collect(),
-    )
+    );
+    match path.leading_colon {
+        Some(_) => [vec![String::new()], res_path].concat(),
+        None => res_path,
+    }
 }